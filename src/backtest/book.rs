@@ -0,0 +1,349 @@
+use super::order::{Fill, Order, OrderId};
+use crate::indicators::TradeSide;
+
+/// A position opened and then reduced or closed, net of fees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeRecord {
+    /// `Buy` for a long position, `Sell` for a short one.
+    pub side: TradeSide,
+    pub quantity: f64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub entry_bar: usize,
+    pub exit_bar: usize,
+    pub entry_time: u64,
+    pub exit_time: u64,
+    /// Entry and exit fees attributed to this quantity.
+    pub fees: f64,
+    pub pnl: f64,
+    /// Share of the amount the position risked, when one was set with
+    /// [`Context::set_risk`](super::Context::set_risk).
+    pub risk: Option<f64>,
+}
+
+impl TradeRecord {
+    /// `pnl` relative to the entry notional.
+    pub fn return_pct(&self) -> f64 {
+        self.pnl / (self.entry_price * self.quantity) * 100.0
+    }
+
+    /// `pnl` in units of the risk taken.
+    pub fn r_multiple(&self) -> Option<f64> {
+        self.risk.map(|risk| self.pnl / risk)
+    }
+
+    /// Rebuilds round trips from a fill log, e.g. a live account's, in the
+    /// order given.
+    pub fn from_fills(fills: &[Fill]) -> Vec<TradeRecord> {
+        let mut book = Book::new(0.0, 0.0, 0.0);
+        for fill in fills {
+            book.apply(
+                fill.side,
+                fill.quantity,
+                fill.price,
+                fill.fee,
+                fill.bar,
+                fill.time,
+            );
+        }
+        book.trades
+    }
+}
+
+/// Cash, position and working orders of a single-instrument account.
+/// Positions are signed (negative is short) and carry an average entry
+/// price; there is no margin check.
+#[derive(Debug, Clone)]
+pub(crate) struct Book {
+    fee_rate: f64,
+    slippage: f64,
+    pub(crate) cash: f64,
+    pub(crate) position: f64,
+    entry_price: f64,
+    entry_bar: usize,
+    entry_time: u64,
+    entry_fees: f64,
+    entry_risk: Option<f64>,
+    next_id: u64,
+    pub(crate) pending: Vec<(OrderId, Order)>,
+    pub(crate) fills: Vec<Fill>,
+    pub(crate) trades: Vec<TradeRecord>,
+}
+
+impl Book {
+    pub(crate) fn new(cash: f64, fee_rate: f64, slippage: f64) -> Self {
+        Self {
+            fee_rate,
+            slippage,
+            cash,
+            position: 0.0,
+            entry_price: 0.0,
+            entry_bar: 0,
+            entry_time: 0,
+            entry_fees: 0.0,
+            entry_risk: None,
+            next_id: 0,
+            pending: Vec::new(),
+            fills: Vec::new(),
+            trades: Vec::new(),
+        }
+    }
+
+    pub(crate) fn submit(&mut self, order: Order) -> OrderId {
+        let id = OrderId(self.next_id);
+        self.next_id += 1;
+        self.pending.push((id, order));
+        id
+    }
+
+    pub(crate) fn cancel(&mut self, id: OrderId) -> bool {
+        let before = self.pending.len();
+        self.pending.retain(|(pending, _)| *pending != id);
+        self.pending.len() != before
+    }
+
+    pub(crate) fn set_risk(&mut self, risk: f64) {
+        if self.position != 0.0 {
+            self.entry_risk = Some(risk);
+        }
+    }
+
+    pub(crate) fn equity(&self, price: f64) -> f64 {
+        self.cash + self.position * price
+    }
+
+    /// Fills every order that is marketable with price at `price`.
+    pub(crate) fn match_at(&mut self, price: f64, bar: usize, time: u64) {
+        let (hits, rest): (Vec<_>, Vec<_>) = self
+            .pending
+            .drain(..)
+            .partition(|(_, order)| order.fill_at(price).is_some());
+        self.pending = rest;
+
+        for (id, order) in hits {
+            self.fill(id, order, price, bar, time);
+        }
+    }
+
+    /// Fills orders whose level is traded through on the way from `from` to
+    /// `to`, nearest level first.
+    pub(crate) fn match_between(&mut self, from: f64, to: f64, bar: usize, time: u64) {
+        let (hits, rest): (Vec<_>, Vec<_>) = self
+            .pending
+            .drain(..)
+            .partition(|(_, order)| order.fill_between(from, to).is_some());
+        self.pending = rest;
+
+        let mut hits: Vec<_> = hits
+            .into_iter()
+            .map(|(id, order)| (id, order, order.fill_between(from, to).unwrap()))
+            .collect();
+        hits.sort_by(|a, b| (a.2 - from).abs().total_cmp(&(b.2 - from).abs()));
+
+        for (id, order, price) in hits {
+            self.fill(id, order, price, bar, time);
+        }
+    }
+
+    fn fill(&mut self, id: OrderId, order: Order, price: f64, bar: usize, time: u64) {
+        let slip = if order.pays_slippage() {
+            self.slippage
+        } else {
+            0.0
+        };
+        let price = match order.side {
+            TradeSide::Buy => price * (1.0 + slip),
+            TradeSide::Sell => price * (1.0 - slip),
+        };
+        let fee = price * order.quantity * self.fee_rate;
+
+        self.fills.push(Fill {
+            order: id,
+            side: order.side,
+            quantity: order.quantity,
+            price,
+            fee,
+            bar,
+            time,
+        });
+        self.apply(order.side, order.quantity, price, fee, bar, time);
+    }
+
+    fn apply(
+        &mut self,
+        side: TradeSide,
+        quantity: f64,
+        price: f64,
+        fee: f64,
+        bar: usize,
+        time: u64,
+    ) {
+        let direction = match side {
+            TradeSide::Buy => 1.0,
+            TradeSide::Sell => -1.0,
+        };
+        self.cash -= direction * quantity * price + fee;
+
+        let mut remaining = quantity;
+        if self.position != 0.0 && self.position.signum() != direction {
+            let held = self.position.abs();
+            let closed = remaining.min(held);
+            let entry_fees = self.entry_fees * closed / held;
+            let exit_fees = fee * closed / quantity;
+            let risk = self.entry_risk.map(|risk| risk * closed / held);
+
+            self.trades.push(TradeRecord {
+                side: if self.position > 0.0 {
+                    TradeSide::Buy
+                } else {
+                    TradeSide::Sell
+                },
+                quantity: closed,
+                entry_price: self.entry_price,
+                exit_price: price,
+                entry_bar: self.entry_bar,
+                exit_bar: bar,
+                entry_time: self.entry_time,
+                exit_time: time,
+                fees: entry_fees + exit_fees,
+                pnl: (price - self.entry_price) * closed * self.position.signum()
+                    - entry_fees
+                    - exit_fees,
+                risk,
+            });
+
+            self.entry_fees -= entry_fees;
+            self.entry_risk = self.entry_risk.zip(risk).map(|(total, used)| total - used);
+            remaining -= closed;
+            self.position = if closed == held {
+                0.0
+            } else {
+                self.position + direction * closed
+            };
+        }
+
+        if remaining > 0.0 {
+            if self.position == 0.0 {
+                self.entry_price = price;
+                self.entry_bar = bar;
+                self.entry_time = time;
+                self.entry_fees = 0.0;
+                self.entry_risk = None;
+            } else {
+                let held = self.position.abs();
+                self.entry_price =
+                    (self.entry_price * held + price * remaining) / (held + remaining);
+            }
+            self.entry_fees += fee * remaining / quantity;
+            self.position += direction * remaining;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn round_trip_records_pnl_net_of_fees() {
+        let mut book = Book::new(1_000.0, 0.001, 0.0);
+
+        book.submit(Order::market(TradeSide::Buy, 2.0));
+        book.match_at(100.0, 0, 0);
+        book.submit(Order::market(TradeSide::Sell, 2.0));
+        book.match_at(110.0, 1, 60);
+
+        let trade = book.trades[0];
+        approx_eq(trade.fees, 0.2 + 0.22);
+        approx_eq(trade.pnl, 20.0 - 0.42);
+        approx_eq(book.cash, 1_000.0 + 20.0 - 0.42);
+        assert_eq!(book.position, 0.0);
+    }
+
+    #[test]
+    fn reversal_closes_then_opens_the_rest() {
+        let mut book = Book::new(1_000.0, 0.0, 0.0);
+
+        book.submit(Order::market(TradeSide::Buy, 1.0));
+        book.match_at(100.0, 0, 0);
+        book.submit(Order::market(TradeSide::Sell, 3.0));
+        book.match_at(90.0, 1, 60);
+
+        assert_eq!(book.trades.len(), 1);
+        approx_eq(book.trades[0].pnl, -10.0);
+        assert_eq!(book.position, -2.0);
+
+        book.submit(Order::market(TradeSide::Buy, 2.0));
+        book.match_at(80.0, 2, 120);
+
+        assert_eq!(book.trades[1].side, TradeSide::Sell);
+        approx_eq(book.trades[1].pnl, 20.0);
+        approx_eq(book.equity(80.0), 1_010.0);
+    }
+
+    #[test]
+    fn slippage_applies_to_market_and_stop_orders_only() {
+        let mut book = Book::new(1_000.0, 0.0, 0.01);
+
+        book.submit(Order::stop(TradeSide::Buy, 1.0, 105.0));
+        book.submit(Order::limit(TradeSide::Sell, 1.0, 110.0));
+        book.match_between(100.0, 112.0, 0, 0);
+
+        assert_eq!(book.fills.len(), 2);
+        approx_eq(book.fills[0].price, 105.0 * 1.01);
+        approx_eq(book.fills[1].price, 110.0);
+    }
+
+    #[test]
+    fn risk_is_split_across_partial_exits() {
+        let mut book = Book::new(1_000.0, 0.0, 0.0);
+
+        book.submit(Order::market(TradeSide::Buy, 2.0));
+        book.match_at(100.0, 0, 0);
+        book.set_risk(20.0);
+        book.submit(Order::market(TradeSide::Sell, 1.0));
+        book.match_at(110.0, 1, 60);
+        book.submit(Order::market(TradeSide::Sell, 1.0));
+        book.match_at(95.0, 2, 120);
+
+        assert_eq!(book.trades[0].r_multiple(), Some(1.0));
+        assert_eq!(book.trades[1].r_multiple(), Some(-0.5));
+    }
+
+    #[test]
+    fn from_fills_rebuilds_round_trips() {
+        let fill = |side, price, time| Fill {
+            order: OrderId(0),
+            side,
+            quantity: 1.0,
+            price,
+            fee: 0.1,
+            bar: 0,
+            time,
+        };
+
+        let trades = TradeRecord::from_fills(&[
+            fill(TradeSide::Sell, 100.0, 0),
+            fill(TradeSide::Buy, 90.0, 60),
+        ]);
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].side, TradeSide::Sell);
+        approx_eq(trades[0].pnl, 9.8);
+        assert_eq!((trades[0].entry_time, trades[0].exit_time), (0, 60));
+    }
+
+    #[test]
+    fn cancel_removes_pending_orders() {
+        let mut book = Book::new(1_000.0, 0.0, 0.0);
+        let id = book.submit(Order::limit(TradeSide::Buy, 1.0, 90.0));
+
+        assert!(book.cancel(id));
+        assert!(!book.cancel(id));
+        assert!(book.pending.is_empty());
+    }
+}
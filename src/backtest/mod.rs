@@ -0,0 +1,501 @@
+mod book;
+mod order;
+
+pub use book::TradeRecord;
+pub use order::{Fill, Order, OrderId, OrderKind};
+
+use crate::indicators::{IndicatorKind, IndicatorSet, Price, TradeSide, Value};
+use book::Book;
+
+/// A trading strategy replayed by [`Backtest`].
+pub trait Strategy {
+    /// Indicators the engine keeps up to date and exposes through
+    /// [`Context::value`].
+    fn indicators(&self) -> Vec<IndicatorKind> {
+        Vec::new()
+    }
+
+    /// Called once per bar after it closes. Orders submitted here are
+    /// first matched at the next bar's open.
+    fn on_bar(&mut self, ctx: &mut Context<'_>);
+
+    /// Called on simulated intrabar ticks when enabled with
+    /// [`Backtest::with_intrabar_ticks`], after indicators took a
+    /// provisional update. Orders submitted here can fill later in the bar.
+    fn on_tick(&mut self, _ctx: &mut Context<'_>) {}
+}
+
+/// What a strategy sees and can do on a bar or tick.
+pub struct Context<'a> {
+    price: Price,
+    bar: usize,
+    provisional: bool,
+    indicators: &'a IndicatorSet,
+    book: &'a mut Book,
+}
+
+impl Context<'_> {
+    /// The closed bar, or the bar so far on a tick.
+    #[inline]
+    pub fn price(&self) -> Price {
+        self.price
+    }
+
+    #[inline]
+    pub fn bar(&self) -> usize {
+        self.bar
+    }
+
+    #[inline]
+    pub fn is_provisional(&self) -> bool {
+        self.provisional
+    }
+
+    pub fn indicators(&self) -> &IndicatorSet {
+        self.indicators
+    }
+
+    pub fn value(&self, kind: &IndicatorKind) -> Option<Value> {
+        self.indicators.value(kind)
+    }
+
+    /// Signed position size; negative when short.
+    #[inline]
+    pub fn position(&self) -> f64 {
+        self.book.position
+    }
+
+    #[inline]
+    pub fn cash(&self) -> f64 {
+        self.book.cash
+    }
+
+    /// Cash plus the position marked at the current close.
+    pub fn equity(&self) -> f64 {
+        self.book.equity(self.price.close)
+    }
+
+    pub fn pending(&self) -> &[(OrderId, Order)] {
+        &self.book.pending
+    }
+
+    pub fn submit(&mut self, order: Order) -> OrderId {
+        self.book.submit(order)
+    }
+
+    pub fn cancel(&mut self, id: OrderId) -> bool {
+        self.book.cancel(id)
+    }
+
+    pub fn cancel_all(&mut self) {
+        self.book.pending.clear();
+    }
+
+    /// Amount the open position risks, e.g. its stop distance times size.
+    /// Trades closing it carry their share as [`TradeRecord::risk`], from
+    /// which R multiples are computed. Cleared when the position is flat.
+    pub fn set_risk(&mut self, risk: f64) {
+        assert!(risk > 0.0, "Risk must be > 0, got {}", risk);
+        self.book.set_risk(risk);
+    }
+
+    /// Submits a market order that flattens the position, if there is one.
+    pub fn close_position(&mut self) -> Option<OrderId> {
+        let position = self.book.position;
+        if position == 0.0 {
+            return None;
+        }
+        let side = if position > 0.0 {
+            TradeSide::Sell
+        } else {
+            TradeSide::Buy
+        };
+        Some(self.submit(Order::market(side, position.abs())))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquityPoint {
+    /// `open_time` of the bar, matching the trade and signal timestamps.
+    pub time: u64,
+    pub equity: f64,
+    /// Signed position held at that close.
+    pub position: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestResult {
+    pub initial_cash: f64,
+    pub fills: Vec<Fill>,
+    pub trades: Vec<TradeRecord>,
+    /// Equity at every bar close.
+    pub equity: Vec<EquityPoint>,
+    /// Position still open after the last bar.
+    pub position: f64,
+    pub cash: f64,
+}
+
+impl BacktestResult {
+    pub fn final_equity(&self) -> f64 {
+        self.equity
+            .last()
+            .map_or(self.initial_cash, |point| point.equity)
+    }
+}
+
+/// Replays a price series through a [`Strategy`] and simulates its orders.
+///
+/// Within each bar price is assumed to travel open, low, high, close when
+/// the bar closes up, and open, high, low, close otherwise. Pending orders
+/// are matched along that path: marketable orders at each point, limits and
+/// stops at their level when a leg trades through it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backtest {
+    initial_cash: f64,
+    fee_rate: f64,
+    slippage: f64,
+    intrabar_ticks: bool,
+}
+
+impl Backtest {
+    pub fn new(initial_cash: f64) -> Self {
+        assert!(
+            initial_cash.is_finite() && initial_cash > 0.0,
+            "Backtest initial cash must be > 0, got {}",
+            initial_cash
+        );
+        Self {
+            initial_cash,
+            fee_rate: 0.0,
+            slippage: 0.0,
+            intrabar_ticks: false,
+        }
+    }
+
+    /// Fee as a fraction of fill notional, e.g. `0.001` for 10 bps.
+    pub fn with_fee_rate(mut self, fee_rate: f64) -> Self {
+        assert!(fee_rate >= 0.0, "Backtest fee rate must be >= 0");
+        self.fee_rate = fee_rate;
+        self
+    }
+
+    /// Adverse price move, as a fraction, on market and stop fills.
+    pub fn with_slippage(mut self, slippage: f64) -> Self {
+        assert!(slippage >= 0.0, "Backtest slippage must be >= 0");
+        self.slippage = slippage;
+        self
+    }
+
+    pub fn with_intrabar_ticks(mut self, enabled: bool) -> Self {
+        self.intrabar_ticks = enabled;
+        self
+    }
+
+    pub fn run<S: Strategy + ?Sized>(&self, strategy: &mut S, prices: &[Price]) -> BacktestResult {
+        self.run_from(strategy, prices, 0)
+    }
+
+    /// Like `run`, but bars before `start` only warm up the indicators: the
+    /// strategy trades, and equity is recorded, from `prices[start]` on.
+    ///
+    /// Panics if one of the strategy's indicator kinds fails
+    /// [`IndicatorKind::validate`].
+    pub fn run_from<S: Strategy + ?Sized>(
+        &self,
+        strategy: &mut S,
+        prices: &[Price],
+        start: usize,
+    ) -> BacktestResult {
+        assert!(
+            start <= prices.len(),
+            "Backtest start {} is past the end of {} prices",
+            start,
+            prices.len()
+        );
+        let mut indicators = IndicatorSet::new();
+        for kind in strategy.indicators() {
+            if let Err(err) = indicators.insert(kind) {
+                panic!("Backtest strategy indicator is invalid: {}", err);
+            }
+        }
+        let mut book = Book::new(self.initial_cash, self.fee_rate, self.slippage);
+        let mut equity = Vec::with_capacity(prices.len() - start);
+
+        for price in &prices[..start] {
+            indicators.update_after_close(*price);
+        }
+
+        for (bar, price) in prices.iter().enumerate().skip(start) {
+            let path = path(price);
+            let time = price.open_time;
+
+            for (i, &point) in path.iter().enumerate() {
+                if i > 0 {
+                    book.match_between(path[i - 1], point, bar, time);
+                }
+                if i == path.len() - 1 {
+                    break;
+                }
+
+                if i > 0 && self.intrabar_ticks {
+                    let partial = partial(price, &path[..=i]);
+                    indicators.update_before_close(partial);
+                    strategy.on_tick(&mut Context {
+                        price: partial,
+                        bar,
+                        provisional: true,
+                        indicators: &indicators,
+                        book: &mut book,
+                    });
+                }
+                book.match_at(point, bar, time);
+            }
+
+            indicators.update_after_close(*price);
+            strategy.on_bar(&mut Context {
+                price: *price,
+                bar,
+                provisional: false,
+                indicators: &indicators,
+                book: &mut book,
+            });
+
+            equity.push(EquityPoint {
+                time: price.open_time,
+                equity: book.equity(price.close),
+                position: book.position,
+            });
+        }
+
+        BacktestResult {
+            initial_cash: self.initial_cash,
+            fills: book.fills,
+            trades: book.trades,
+            equity,
+            position: book.position,
+            cash: book.cash,
+        }
+    }
+}
+
+fn path(price: &Price) -> [f64; 4] {
+    if price.close >= price.open {
+        [price.open, price.low, price.high, price.close]
+    } else {
+        [price.open, price.high, price.low, price.close]
+    }
+}
+
+/// The bar as it stands after the points travelled so far, with volume
+/// prorated along the path.
+fn partial(price: &Price, travelled: &[f64]) -> Price {
+    let last = travelled[travelled.len() - 1];
+    Price {
+        open: price.open,
+        high: travelled.iter().copied().fold(f64::MIN, f64::max),
+        low: travelled.iter().copied().fold(f64::MAX, f64::min),
+        close: last,
+        open_time: price.open_time,
+        close_time: price.close_time,
+        vlm: price.vlm * (travelled.len() - 1) as f64 / 3.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Source;
+
+    fn bar(i: u64, open: f64, high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open,
+            high,
+            low,
+            close,
+            open_time: i * 60,
+            close_time: i * 60 + 59,
+            vlm: 1.0,
+        }
+    }
+
+    fn flat(i: u64, close: f64) -> Price {
+        bar(i, close, close, close, close)
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    /// Buys when the close crosses above its SMA, sells when below.
+    struct SmaCross {
+        sma: IndicatorKind,
+        above: Option<bool>,
+    }
+
+    impl Strategy for SmaCross {
+        fn indicators(&self) -> Vec<IndicatorKind> {
+            vec![self.sma]
+        }
+
+        fn on_bar(&mut self, ctx: &mut Context<'_>) {
+            let Some(sma) = ctx.value(&self.sma).and_then(|v| v.field("value")) else {
+                return;
+            };
+            let above = ctx.price().close > sma;
+            if self.above == Some(!above) {
+                if above {
+                    ctx.submit(Order::market(TradeSide::Buy, 1.0));
+                } else {
+                    ctx.close_position();
+                }
+            }
+            self.above = Some(above);
+        }
+    }
+
+    #[test]
+    fn market_orders_fill_at_the_next_open() {
+        let prices = [
+            flat(0, 10.0),
+            flat(1, 9.0),
+            flat(2, 12.0),
+            bar(3, 13.0, 14.0, 12.5, 14.0),
+            flat(4, 8.0),
+            bar(5, 7.0, 7.0, 6.0, 6.5),
+        ];
+        let mut strategy = SmaCross {
+            sma: IndicatorKind::Sma {
+                periods: 2,
+                source: Source::Close,
+            },
+            above: None,
+        };
+
+        let result = Backtest::new(100.0).run(&mut strategy, &prices);
+
+        assert_eq!(result.fills.len(), 2);
+        assert_eq!((result.fills[0].bar, result.fills[0].price), (3, 13.0));
+        assert_eq!((result.fills[1].bar, result.fills[1].price), (5, 7.0));
+        approx_eq(result.trades[0].pnl, -6.0);
+        assert_eq!(result.equity.len(), prices.len());
+        approx_eq(result.final_equity(), 94.0);
+        assert_eq!(result.position, 0.0);
+    }
+
+    struct Bracket {
+        placed: bool,
+    }
+
+    impl Strategy for Bracket {
+        fn on_bar(&mut self, ctx: &mut Context<'_>) {
+            if !self.placed {
+                ctx.submit(Order::limit(TradeSide::Buy, 1.0, 95.0));
+                ctx.submit(Order::stop(TradeSide::Sell, 1.0, 90.0));
+                self.placed = true;
+            }
+        }
+    }
+
+    #[test]
+    fn levels_fill_in_path_order_with_fees_and_slippage() {
+        // Down bar: open, high, low, close. The buy limit at 95 is hit on
+        // the way down before the sell stop at 90.
+        let prices = [flat(0, 100.0), bar(1, 100.0, 101.0, 88.0, 89.0)];
+
+        let result = Backtest::new(1_000.0)
+            .with_fee_rate(0.001)
+            .with_slippage(0.01)
+            .run(&mut Bracket { placed: false }, &prices);
+
+        assert_eq!(result.fills.len(), 2);
+        approx_eq(result.fills[0].price, 95.0);
+        approx_eq(result.fills[1].price, 90.0 * 0.99);
+        let fees = 0.095 + 90.0 * 0.99 * 0.001;
+        approx_eq(result.trades[0].pnl, 90.0 * 0.99 - 95.0 - fees);
+        approx_eq(result.cash, 1_000.0 + result.trades[0].pnl);
+    }
+
+    struct TickBuyer {
+        ticks: Vec<(bool, f64)>,
+    }
+
+    impl Strategy for TickBuyer {
+        fn indicators(&self) -> Vec<IndicatorKind> {
+            vec![IndicatorKind::Sma {
+                periods: 2,
+                source: Source::Close,
+            }]
+        }
+
+        fn on_bar(&mut self, _ctx: &mut Context<'_>) {}
+
+        fn on_tick(&mut self, ctx: &mut Context<'_>) {
+            if ctx.bar() == 0 {
+                return;
+            }
+            self.ticks.push((ctx.is_provisional(), ctx.price().close));
+            if ctx.position() == 0.0 && ctx.pending().is_empty() {
+                ctx.submit(Order::market(TradeSide::Buy, 1.0));
+            }
+        }
+    }
+
+    #[test]
+    fn intrabar_ticks_see_provisional_bars_and_fill_within_the_bar() {
+        let prices = [flat(0, 10.0), bar(1, 10.0, 12.0, 9.0, 11.0)];
+        let mut strategy = TickBuyer { ticks: Vec::new() };
+
+        let result = Backtest::new(100.0)
+            .with_intrabar_ticks(true)
+            .run(&mut strategy, &prices);
+
+        // Up bar: ticks at the low, then the high.
+        assert_eq!(strategy.ticks, vec![(true, 9.0), (true, 12.0)]);
+        assert_eq!((result.fills[0].bar, result.fills[0].price), (1, 9.0));
+        approx_eq(result.final_equity(), 102.0);
+
+        let without_ticks = Backtest::new(100.0).run(&mut TickBuyer { ticks: Vec::new() }, &prices);
+        assert!(without_ticks.fills.is_empty());
+    }
+
+    #[test]
+    fn run_from_warms_up_without_trading() {
+        let prices = [
+            flat(0, 10.0),
+            flat(1, 9.0),
+            flat(2, 12.0),
+            bar(3, 13.0, 14.0, 12.5, 14.0),
+            flat(4, 8.0),
+            bar(5, 7.0, 7.0, 6.0, 6.5),
+        ];
+        let sma = IndicatorKind::Sma {
+            periods: 2,
+            source: Source::Close,
+        };
+
+        let result = Backtest::new(100.0).run_from(&mut SmaCross { sma, above: None }, &prices, 3);
+
+        // The strategy first sees bar 3, with the SMA already warm, and only
+        // catches the cross down on bar 4.
+        assert_eq!(result.equity.len(), 3);
+        assert_eq!(result.equity[0].time, 3 * 60);
+        assert!(result.fills.is_empty());
+    }
+
+    #[test]
+    fn gaps_fill_stops_at_the_open() {
+        struct Stop;
+        impl Strategy for Stop {
+            fn on_bar(&mut self, ctx: &mut Context<'_>) {
+                if ctx.bar() == 0 {
+                    ctx.submit(Order::stop(TradeSide::Buy, 1.0, 105.0));
+                }
+            }
+        }
+
+        let prices = [flat(0, 100.0), bar(1, 110.0, 112.0, 108.0, 111.0)];
+        let result = Backtest::new(1_000.0).run(&mut Stop, &prices);
+
+        approx_eq(result.fills[0].price, 110.0);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::indicators::TradeSide;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct OrderId(pub u64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderKind {
+    Market,
+    /// Buys at or below, sells at or above the price.
+    Limit(f64),
+    /// Becomes a market order once price trades through the level: up for
+    /// buys, down for sells.
+    Stop(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Order {
+    pub side: TradeSide,
+    pub quantity: f64,
+    pub kind: OrderKind,
+}
+
+impl Order {
+    pub fn new(side: TradeSide, quantity: f64, kind: OrderKind) -> Self {
+        assert!(
+            quantity.is_finite() && quantity > 0.0,
+            "Order quantity must be a positive number, got {}",
+            quantity
+        );
+        if let OrderKind::Limit(price) | OrderKind::Stop(price) = kind {
+            assert!(
+                price.is_finite() && price > 0.0,
+                "Order price must be a positive number, got {}",
+                price
+            );
+        }
+
+        Self {
+            side,
+            quantity,
+            kind,
+        }
+    }
+
+    pub fn market(side: TradeSide, quantity: f64) -> Self {
+        Self::new(side, quantity, OrderKind::Market)
+    }
+
+    pub fn limit(side: TradeSide, quantity: f64, price: f64) -> Self {
+        Self::new(side, quantity, OrderKind::Limit(price))
+    }
+
+    pub fn stop(side: TradeSide, quantity: f64, price: f64) -> Self {
+        Self::new(side, quantity, OrderKind::Stop(price))
+    }
+
+    /// Fill price if the order is marketable with the last traded price at
+    /// `price`, before slippage.
+    pub(crate) fn fill_at(&self, price: f64) -> Option<f64> {
+        let marketable = match (self.kind, self.side) {
+            (OrderKind::Market, _) => true,
+            (OrderKind::Limit(limit), TradeSide::Buy) => price <= limit,
+            (OrderKind::Limit(limit), TradeSide::Sell) => price >= limit,
+            (OrderKind::Stop(stop), TradeSide::Buy) => price >= stop,
+            (OrderKind::Stop(stop), TradeSide::Sell) => price <= stop,
+        };
+        marketable.then_some(price)
+    }
+
+    /// Fill price if price moving from `from` to `to` trades through the
+    /// order's level, before slippage. Levels already crossed at `from` are
+    /// left to [`Order::fill_at`].
+    pub(crate) fn fill_between(&self, from: f64, to: f64) -> Option<f64> {
+        let (level, rising) = match (self.kind, self.side) {
+            (OrderKind::Market, _) => return None,
+            (OrderKind::Limit(level), TradeSide::Sell)
+            | (OrderKind::Stop(level), TradeSide::Buy) => (level, true),
+            (OrderKind::Limit(level), TradeSide::Buy)
+            | (OrderKind::Stop(level), TradeSide::Sell) => (level, false),
+        };
+
+        let crossed = if rising {
+            from < level && level <= to
+        } else {
+            to <= level && level < from
+        };
+        crossed.then_some(level)
+    }
+
+    /// Market and stop orders take liquidity and pay slippage; limits fill
+    /// at their price.
+    pub(crate) fn pays_slippage(&self) -> bool {
+        !matches!(self.kind, OrderKind::Limit(_))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fill {
+    pub order: OrderId,
+    pub side: TradeSide,
+    pub quantity: f64,
+    /// Price after slippage.
+    pub price: f64,
+    pub fee: f64,
+    /// Index of the bar the fill happened in.
+    pub bar: usize,
+    /// `open_time` of that bar.
+    pub time: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_fill_at_better_prices_only() {
+        let buy = Order::limit(TradeSide::Buy, 1.0, 100.0);
+        let sell = Order::limit(TradeSide::Sell, 1.0, 100.0);
+
+        assert_eq!(buy.fill_at(99.0), Some(99.0));
+        assert_eq!(buy.fill_at(101.0), None);
+        assert_eq!(sell.fill_at(101.0), Some(101.0));
+        assert_eq!(sell.fill_at(99.0), None);
+    }
+
+    #[test]
+    fn levels_trigger_when_crossed_in_their_direction() {
+        let stop_buy = Order::stop(TradeSide::Buy, 1.0, 105.0);
+        let limit_buy = Order::limit(TradeSide::Buy, 1.0, 95.0);
+
+        assert_eq!(stop_buy.fill_between(100.0, 106.0), Some(105.0));
+        assert_eq!(stop_buy.fill_between(106.0, 100.0), None);
+        assert_eq!(limit_buy.fill_between(100.0, 94.0), Some(95.0));
+        assert_eq!(limit_buy.fill_between(94.0, 100.0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_non_positive_quantity() {
+        Order::market(TradeSide::Buy, 0.0);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::indicators::volatility::Calendar;
+use crate::indicators::{IndicatorKind, Source};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Param {
+    /// Whole number with the given minimum.
+    Period(u32),
+    /// Positive multiplier, kept to two decimals like the `_x100` kinds.
+    Multiplier,
+}
+
+#[derive(Debug)]
+pub(crate) struct Function {
+    pub name: &'static str,
+    pub params: &'static [Param],
+    /// Readable fields, as named by `Value::field`. The first is read when
+    /// the call has no `.field` suffix.
+    pub fields: &'static [&'static str],
+    /// Whether the kind reads a [`Source`], so a bare price series such as
+    /// `hl2` can be passed straight to the indicator.
+    pub sourced: bool,
+    pub kind: fn(&[f64], Source) -> IndicatorKind,
+}
+
+/// Largest period a formula may ask for. Periods size the indicator's
+/// buffers, so an unchecked value from formula text could exhaust memory.
+pub(crate) const MAX_PERIOD: u32 = 100_000;
+
+const P1: Param = Param::Period(1);
+const P2: Param = Param::Period(2);
+
+fn period(x: f64) -> u32 {
+    x as u32
+}
+
+fn x100(x: f64) -> u32 {
+    (x * 100.0).round() as u32
+}
+
+const FUNCTIONS: &[Function] = &[
+    Function {
+        name: "sma",
+        params: &[P2],
+        fields: &["value"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::Sma {
+            periods: period(a[0]),
+            source,
+        },
+    },
+    Function {
+        name: "ema",
+        params: &[P2],
+        fields: &["value"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::Ema {
+            periods: period(a[0]),
+            source,
+        },
+    },
+    Function {
+        name: "dema",
+        params: &[P2],
+        fields: &["value"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::Dema {
+            periods: period(a[0]),
+            source,
+        },
+    },
+    Function {
+        name: "tema",
+        params: &[P2],
+        fields: &["value"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::Tema {
+            periods: period(a[0]),
+            source,
+        },
+    },
+    Function {
+        name: "rsi",
+        params: &[P2],
+        fields: &["value"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::Rsi {
+            periods: period(a[0]),
+            source,
+        },
+    },
+    Function {
+        name: "stochrsi",
+        params: &[P2, P1, P1],
+        fields: &["k", "d"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::StochRsi {
+            periods: period(a[0]),
+            k_smoothing: Some(period(a[1])),
+            d_smoothing: Some(period(a[2])),
+            source,
+        },
+    },
+    Function {
+        name: "roc",
+        params: &[P1],
+        fields: &["value"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::Roc {
+            periods: period(a[0]),
+            source,
+        },
+    },
+    Function {
+        name: "cci",
+        params: &[P2],
+        fields: &["value"],
+        sourced: false,
+        kind: |a, _| IndicatorKind::Cci(period(a[0])),
+    },
+    Function {
+        name: "macd",
+        params: &[P2, P2, P2],
+        fields: &["macd", "signal", "histogram"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::Macd {
+            fast: period(a[0]),
+            slow: period(a[1]),
+            signal: period(a[2]),
+            source,
+        },
+    },
+    Function {
+        name: "trix",
+        params: &[P2],
+        fields: &["value"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::Trix {
+            periods: period(a[0]),
+            source,
+        },
+    },
+    Function {
+        name: "adx",
+        params: &[P2, P2],
+        fields: &["value"],
+        sourced: false,
+        kind: |a, _| IndicatorKind::Adx {
+            periods: period(a[0]),
+            di_length: period(a[1]),
+        },
+    },
+    Function {
+        name: "aroon",
+        params: &[P1],
+        fields: &["oscillator", "up", "down"],
+        sourced: false,
+        kind: |a, _| IndicatorKind::Aroon(period(a[0])),
+    },
+    Function {
+        name: "vortex",
+        params: &[P2],
+        fields: &["plus", "minus"],
+        sourced: false,
+        kind: |a, _| IndicatorKind::Vortex(period(a[0])),
+    },
+    Function {
+        name: "chop",
+        params: &[P2],
+        fields: &["value"],
+        sourced: false,
+        kind: |a, _| IndicatorKind::Choppiness(period(a[0])),
+    },
+    Function {
+        name: "atr",
+        params: &[P1],
+        fields: &["value"],
+        sourced: false,
+        kind: |a, _| IndicatorKind::Atr(period(a[0])),
+    },
+    Function {
+        name: "bb",
+        params: &[P2, Param::Multiplier],
+        fields: &["mid", "upper", "lower", "width"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::BollingerBands {
+            periods: period(a[0]),
+            std_multiplier_x100: x100(a[1]),
+            source,
+        },
+    },
+    Function {
+        name: "hv",
+        params: &[P2],
+        fields: &["value"],
+        sourced: true,
+        kind: |a, source| IndicatorKind::HistVolatility {
+            periods: period(a[0]),
+            calendar: Calendar::default(),
+            interval_ms: None,
+            source,
+        },
+    },
+    Function {
+        name: "obv",
+        params: &[],
+        fields: &["value"],
+        sourced: false,
+        kind: |_, _| IndicatorKind::Obv,
+    },
+    Function {
+        name: "volma",
+        params: &[P2],
+        fields: &["value"],
+        sourced: false,
+        kind: |a, _| IndicatorKind::VolMa(period(a[0])),
+    },
+];
+
+pub(crate) fn lookup(name: &str) -> Option<&'static Function> {
+    FUNCTIONS.iter().find(|function| function.name == name)
+}
+
+impl Param {
+    pub(crate) fn accepts(self, x: f64) -> bool {
+        match self {
+            Param::Period(min) => x.fract() == 0.0 && x >= min as f64 && x <= MAX_PERIOD as f64,
+            Param::Multiplier => x.is_finite() && x100(x) > 0,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use super::ExpressionError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Token {
+    Number(f64),
+    Ident(String),
+    LParen,
+    RParen,
+    Comma,
+    Dot,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    And,
+    Or,
+    Not,
+}
+
+/// A token and the byte offset it starts at.
+pub(crate) type Spanned = (Token, usize);
+
+pub(crate) fn tokenize(source: &str) -> Result<Vec<Spanned>, ExpressionError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let c = bytes[i] as char;
+
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() {
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            if i + 1 < bytes.len() && bytes[i] == b'.' && bytes[i + 1].is_ascii_digit() {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let number = source[start..i]
+                .parse()
+                .map_err(|_| ExpressionError::new("invalid number", start))?;
+            tokens.push((Token::Number(number), start));
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() || c == '_' {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let ident = source[start..i].to_ascii_lowercase();
+            let token = match ident.as_str() {
+                "and" => Token::And,
+                "or" => Token::Or,
+                "not" => Token::Not,
+                _ => Token::Ident(ident),
+            };
+            tokens.push((token, start));
+            continue;
+        }
+
+        let next = bytes.get(i + 1).copied().map(char::from);
+        let (token, len) = match (c, next) {
+            ('<', Some('=')) => (Token::Le, 2),
+            ('>', Some('=')) => (Token::Ge, 2),
+            ('=', Some('=')) => (Token::Eq, 2),
+            ('!', Some('=')) => (Token::Ne, 2),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('<', _) => (Token::Lt, 1),
+            ('>', _) => (Token::Gt, 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            (',', _) => (Token::Comma, 1),
+            ('.', _) => (Token::Dot, 1),
+            ('+', _) => (Token::Plus, 1),
+            ('-', _) => (Token::Minus, 1),
+            ('*', _) => (Token::Star, 1),
+            ('/', _) => (Token::Slash, 1),
+            _ => {
+                return Err(ExpressionError::new(
+                    format!("unexpected character '{}'", c),
+                    start,
+                ));
+            }
+        };
+        tokens.push((token, start));
+        i += len;
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<Token> {
+        tokenize(source)
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect()
+    }
+
+    #[test]
+    fn tokenizes_calls_fields_and_operators() {
+        assert_eq!(
+            kinds("macd(12, 26, 9).signal >= 1.5 && !x"),
+            vec![
+                Token::Ident("macd".into()),
+                Token::LParen,
+                Token::Number(12.0),
+                Token::Comma,
+                Token::Number(26.0),
+                Token::Comma,
+                Token::Number(9.0),
+                Token::RParen,
+                Token::Dot,
+                Token::Ident("signal".into()),
+                Token::Ge,
+                Token::Number(1.5),
+                Token::And,
+                Token::Not,
+                Token::Ident("x".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn keywords_and_identifiers_ignore_case() {
+        assert_eq!(
+            kinds("RSI AND Not x Or y"),
+            vec![
+                Token::Ident("rsi".into()),
+                Token::And,
+                Token::Not,
+                Token::Ident("x".into()),
+                Token::Or,
+                Token::Ident("y".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_characters_with_position() {
+        let err = tokenize("close $ 2").unwrap_err();
+        assert_eq!(err.position(), 6);
+    }
+}
@@ -0,0 +1,473 @@
+mod functions;
+mod lexer;
+mod parser;
+
+use crate::indicators::{Indicator, Price, Source, Value};
+use functions::{MAX_PERIOD, Param};
+use parser::{Ast, BinaryOp, Parser, UnaryOp};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpressionError {
+    message: String,
+    position: usize,
+}
+
+impl ExpressionError {
+    pub(crate) fn new(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Byte offset into the source text.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+#[derive(Debug)]
+enum Node {
+    Constant(f64),
+    Price(Source),
+    Indicator {
+        /// Series fed to the indicator; the candle itself when `None`.
+        source: Option<Box<Node>>,
+        indicator: Box<dyn Indicator>,
+        field: String,
+    },
+    Unary(UnaryOp, Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+}
+
+fn truth(b: bool) -> f64 {
+    if b { 1.0 } else { 0.0 }
+}
+
+impl Node {
+    /// Advances every indicator in the subtree by one tick and returns the
+    /// node's value. Children are always evaluated, so `and`/`or` never skip
+    /// an indicator update.
+    fn eval(&mut self, price: Price, closed: bool) -> Option<f64> {
+        match self {
+            Node::Constant(x) => Some(*x),
+            Node::Price(field) => Some(field.get(&price)),
+            Node::Indicator {
+                source,
+                indicator,
+                field,
+            } => {
+                let input = match source {
+                    Some(source) => price.with_value(source.eval(price, closed)?),
+                    None => price,
+                };
+                if closed {
+                    indicator.update_after_close(input);
+                } else {
+                    indicator.update_before_close(input);
+                }
+                indicator.get_last()?.field(field)
+            }
+            Node::Unary(op, node) => {
+                let x = node.eval(price, closed)?;
+                Some(match op {
+                    UnaryOp::Neg => -x,
+                    UnaryOp::Not => truth(x == 0.0),
+                })
+            }
+            Node::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.eval(price, closed), rhs.eval(price, closed));
+                let (a, b) = (lhs?, rhs?);
+                match op {
+                    BinaryOp::Add => Some(a + b),
+                    BinaryOp::Sub => Some(a - b),
+                    BinaryOp::Mul => Some(a * b),
+                    BinaryOp::Div => (b != 0.0).then(|| a / b),
+                    BinaryOp::Lt => Some(truth(a < b)),
+                    BinaryOp::Le => Some(truth(a <= b)),
+                    BinaryOp::Gt => Some(truth(a > b)),
+                    BinaryOp::Ge => Some(truth(a >= b)),
+                    BinaryOp::Eq => Some(truth(a == b)),
+                    BinaryOp::Ne => Some(truth(a != b)),
+                    BinaryOp::And => Some(truth(a != 0.0 && b != 0.0)),
+                    BinaryOp::Or => Some(truth(a != 0.0 || b != 0.0)),
+                }
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Node::Constant(_) | Node::Price(_) => {}
+            Node::Indicator {
+                source, indicator, ..
+            } => {
+                indicator.reset();
+                if let Some(source) = source {
+                    source.reset();
+                }
+            }
+            Node::Unary(_, node) => node.reset(),
+            Node::Binary(_, lhs, rhs) => {
+                lhs.reset();
+                rhs.reset();
+            }
+        }
+    }
+
+    fn period(&self) -> u32 {
+        match self {
+            Node::Constant(_) | Node::Price(_) => 0,
+            Node::Indicator {
+                source, indicator, ..
+            } => indicator.period() + source.as_ref().map_or(0, |source| source.period()),
+            Node::Unary(_, node) => node.period(),
+            Node::Binary(_, lhs, rhs) => lhs.period().max(rhs.period()),
+        }
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        match self {
+            Node::Constant(_) | Node::Price(_) => 1,
+            Node::Indicator {
+                source, indicator, ..
+            } => {
+                indicator.warmup_bars()
+                    + source.as_ref().map_or(0, |source| source.warmup_bars() - 1)
+            }
+            Node::Unary(_, node) => node.warmup_bars(),
+            Node::Binary(_, lhs, rhs) => lhs.warmup_bars().max(rhs.warmup_bars()),
+        }
+    }
+}
+
+fn price_source(name: &str) -> Option<Source> {
+    match name {
+        "close" => Some(Source::Close),
+        "open" => Some(Source::Open),
+        "high" => Some(Source::High),
+        "low" => Some(Source::Low),
+        "hl2" => Some(Source::Hl2),
+        "hlc3" => Some(Source::Hlc3),
+        "ohlc4" => Some(Source::Ohlc4),
+        "hlcc4" => Some(Source::Hlcc4),
+        "volume" => Some(Source::Volume),
+        _ => None,
+    }
+}
+
+fn compile(ast: Ast) -> Result<Node, ExpressionError> {
+    match ast {
+        Ast::Number(x) => Ok(Node::Constant(x)),
+        Ast::Ident { name, position } => price_source(&name)
+            .map(Node::Price)
+            .ok_or_else(|| ExpressionError::new(format!("unknown name '{}'", name), position)),
+        Ast::Unary(op, node) => Ok(Node::Unary(op, Box::new(compile(*node)?))),
+        Ast::Binary(op, lhs, rhs) => Ok(Node::Binary(
+            op,
+            Box::new(compile(*lhs)?),
+            Box::new(compile(*rhs)?),
+        )),
+        Ast::Call {
+            name,
+            mut args,
+            field,
+            position,
+        } => {
+            let function = functions::lookup(&name).ok_or_else(|| {
+                ExpressionError::new(format!("unknown function '{}'", name), position)
+            })?;
+
+            let mut price = Source::default();
+            let source = if args.len() == function.params.len() + 1 {
+                let first = args.remove(0);
+                // A bare series is read by the indicator itself, keeping the
+                // real high/low/volume of the candle.
+                let series = match &first {
+                    Ast::Ident { name, .. } if function.sourced => price_source(name),
+                    _ => None,
+                };
+                match series {
+                    Some(series) => {
+                        price = series;
+                        None
+                    }
+                    None => Some(Box::new(compile(first)?)),
+                }
+            } else if args.len() == function.params.len() {
+                None
+            } else {
+                return Err(ExpressionError::new(
+                    format!(
+                        "{} takes {} parameter(s) after an optional source",
+                        name,
+                        function.params.len()
+                    ),
+                    position,
+                ));
+            };
+
+            let mut values = Vec::with_capacity(args.len());
+            for (arg, param) in args.into_iter().zip(function.params) {
+                match arg {
+                    Ast::Number(x) if param.accepts(x) => values.push(x),
+                    _ => {
+                        let expected = match param {
+                            Param::Period(min) => {
+                                format!("a whole number from {} to {}", min, MAX_PERIOD)
+                            }
+                            Param::Multiplier => "a positive number".to_string(),
+                        };
+                        return Err(ExpressionError::new(
+                            format!("{} parameters must be {}", name, expected),
+                            position,
+                        ));
+                    }
+                }
+            }
+
+            let field = match field {
+                None => function.fields[0].to_string(),
+                Some(field) if function.fields.contains(&field.as_str()) => field,
+                Some(field) => {
+                    return Err(ExpressionError::new(
+                        format!("{} has no field '{}'", name, field),
+                        position,
+                    ));
+                }
+            };
+
+            let kind = (function.kind)(&values, price);
+            if let Err(err) = kind.validate() {
+                return Err(ExpressionError::new(
+                    format!("{} {}", name, err.message()),
+                    position,
+                ));
+            }
+
+            Ok(Node::Indicator {
+                source,
+                indicator: kind.build(),
+                field,
+            })
+        }
+    }
+}
+
+/// An indicator defined by a text formula such as
+/// `ema(close, 20) - ema(close, 50)` or `rsi(14) > 70 and adx(14, 14) > 25`.
+///
+/// Every indicator call in the formula owns its own instance, so the whole
+/// expression updates incrementally. Comparisons and logic yield `1.0`/`0.0`.
+#[derive(Debug)]
+pub struct Expression {
+    source: String,
+    root: Node,
+    value: Option<f64>,
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let tokens = lexer::tokenize(source)?;
+        let ast = Parser::new(tokens, source.len()).parse()?;
+        Ok(Self {
+            source: source.to_string(),
+            root: compile(ast)?,
+            value: None,
+        })
+    }
+
+    #[inline]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+impl Indicator for Expression {
+    fn update_after_close(&mut self, price: Price) {
+        self.value = self.root.eval(price, true);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.value = self.root.eval(price, false);
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::ExpressionValue)
+    }
+
+    fn reset(&mut self) {
+        self.root.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.root.period().max(1)
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.root.warmup_bars()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Ema, Sma};
+
+    fn p(close: f64) -> Price {
+        Price {
+            open: close - 1.0,
+            high: close + 2.0,
+            low: close - 2.0,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 10.0,
+        }
+    }
+
+    fn value(expr: &Expression) -> Option<f64> {
+        match expr.get_last() {
+            Some(Value::ExpressionValue(x)) => Some(x),
+            _ => None,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn evaluates_arithmetic_over_price_fields() {
+        let mut expr = Expression::parse("(high - low) / 2 + -open * 2").unwrap();
+
+        expr.update_after_close(p(10.0));
+
+        approx_eq(value(&expr).unwrap(), 2.0 - 18.0);
+    }
+
+    #[test]
+    fn matches_the_indicators_it_calls() {
+        let mut expr: Expression = "ema(close, 3) - sma(4)".parse().unwrap();
+        let mut ema = Ema::new(3);
+        let mut sma = Sma::new(4);
+
+        for close in [10.0, 12.0, 11.0, 15.0, 14.0, 18.0] {
+            expr.update_after_close(p(close));
+            ema.update_after_close(p(close));
+            sma.update_after_close(p(close));
+        }
+
+        let expected = ema.get_last().unwrap().field("value").unwrap()
+            - sma.get_last().unwrap().field("value").unwrap();
+        approx_eq(value(&expr).unwrap(), expected);
+        assert_eq!(expr.period(), 4);
+    }
+
+    #[test]
+    fn nested_calls_and_logic() {
+        let mut expr = Expression::parse("sma(sma(close, 2), 2) > 11 and not close < 0").unwrap();
+        assert_eq!(expr.warmup_bars(), 3);
+
+        expr.load(&[p(10.0), p(12.0)]);
+        assert!(!expr.is_ready());
+
+        expr.update_after_close(p(14.0));
+        assert_eq!(value(&expr), Some(1.0));
+
+        let mut fields = Expression::parse("bb(3, 2).upper > bb(3, 2)").unwrap();
+        fields.load(&[p(10.0), p(12.0), p(14.0)]);
+        assert_eq!(value(&fields), Some(1.0));
+    }
+
+    #[test]
+    fn bare_series_become_indicator_sources() {
+        let mut expr = Expression::parse("sma(hl2, 2) - sma(close, 2)").unwrap();
+
+        expr.load(&[p(10.0), p(12.0)]);
+
+        approx_eq(value(&expr).unwrap(), 0.0);
+        assert_eq!(
+            Expression::parse("sma(hl3, 2)").unwrap_err().message(),
+            "unknown name 'hl3'"
+        );
+    }
+
+    #[test]
+    fn before_close_is_provisional() {
+        let mut expr = Expression::parse("sma(close, 2)").unwrap();
+        expr.load(&[p(10.0), p(12.0)]);
+
+        expr.update_before_close(p(100.0));
+        approx_eq(value(&expr).unwrap(), 56.0);
+
+        expr.update_after_close(p(14.0));
+        approx_eq(value(&expr).unwrap(), 13.0);
+    }
+
+    #[test]
+    fn rejects_bad_formulas() {
+        let err = |source: &str| Expression::parse(source).unwrap_err();
+
+        assert_eq!(err("foo(3)").message(), "unknown function 'foo'");
+        assert_eq!(err("close + opn").position(), 8);
+        assert_eq!(
+            err("rsi(1)").message(),
+            "rsi parameters must be a whole number from 2 to 100000"
+        );
+        assert_eq!(err("ema(close, 2, 3)").position(), 0);
+        assert_eq!(
+            err("sma(close, 4000000000)").message(),
+            "sma parameters must be a whole number from 2 to 100000"
+        );
+        assert_eq!(
+            err("macd(12, 26, 9).upper").message(),
+            "macd has no field 'upper'"
+        );
+    }
+
+    #[test]
+    fn reset_clears_state() {
+        let mut expr = Expression::parse("sma(2)").unwrap();
+        expr.load(&[p(10.0), p(12.0)]);
+        assert!(expr.is_ready());
+
+        expr.reset();
+
+        assert!(!expr.is_ready());
+        assert_eq!(expr.get_last(), None);
+    }
+}
@@ -0,0 +1,349 @@
+use super::ExpressionError;
+use super::lexer::{Spanned, Token};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum UnaryOp {
+    Neg,
+    Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    And,
+    Or,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Ast {
+    Number(f64),
+    Ident {
+        name: String,
+        position: usize,
+    },
+    Call {
+        name: String,
+        args: Vec<Ast>,
+        field: Option<String>,
+        position: usize,
+    },
+    Unary(UnaryOp, Box<Ast>),
+    Binary(BinaryOp, Box<Ast>, Box<Ast>),
+}
+
+/// Deepest nesting accepted. Parentheses, calls, prefix operators and each
+/// link of an operator chain count, so hostile input fails with an error
+/// instead of overflowing the stack.
+pub(crate) const MAX_DEPTH: usize = 100;
+
+/// Recursive descent over, loosest first:
+/// `or`, `and`, `not`, comparisons, `+ -`, `* /`, unary `-`, `call.field`.
+pub(crate) struct Parser {
+    tokens: Vec<Spanned>,
+    pos: usize,
+    end: usize,
+    depth: usize,
+}
+
+impl Parser {
+    pub(crate) fn new(tokens: Vec<Spanned>, end: usize) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            end,
+            depth: 0,
+        }
+    }
+
+    pub(crate) fn parse(mut self) -> Result<Ast, ExpressionError> {
+        let ast = self.or()?;
+        match self.tokens.get(self.pos) {
+            None => Ok(ast),
+            Some((_, position)) => Err(ExpressionError::new("unexpected token", *position)),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.end, |(_, position)| *position)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token, what: &str) -> Result<(), ExpressionError> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(ExpressionError::new(
+                format!("expected {}", what),
+                self.position(),
+            ))
+        }
+    }
+
+    /// Enters one nesting level; callers restore `depth` once the nested
+    /// part has parsed.
+    fn nest(&mut self) -> Result<(), ExpressionError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(ExpressionError::new(
+                "expression is nested too deeply",
+                self.position(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn binary_chain(
+        &mut self,
+        ops: &[(Token, BinaryOp)],
+        next: fn(&mut Self) -> Result<Ast, ExpressionError>,
+    ) -> Result<Ast, ExpressionError> {
+        let depth = self.depth;
+        let mut lhs = next(self)?;
+        'outer: loop {
+            for (token, op) in ops {
+                if self.eat(token) {
+                    // The tree deepens by one per link.
+                    self.nest()?;
+                    let rhs = next(self)?;
+                    lhs = Ast::Binary(*op, Box::new(lhs), Box::new(rhs));
+                    continue 'outer;
+                }
+            }
+            self.depth = depth;
+            return Ok(lhs);
+        }
+    }
+
+    fn or(&mut self) -> Result<Ast, ExpressionError> {
+        self.binary_chain(&[(Token::Or, BinaryOp::Or)], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Ast, ExpressionError> {
+        self.binary_chain(&[(Token::And, BinaryOp::And)], Self::not)
+    }
+
+    fn not(&mut self) -> Result<Ast, ExpressionError> {
+        if self.eat(&Token::Not) {
+            self.nest()?;
+            let operand = self.not()?;
+            self.depth -= 1;
+            Ok(Ast::Unary(UnaryOp::Not, Box::new(operand)))
+        } else {
+            self.comparison()
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Ast, ExpressionError> {
+        let lhs = self.additive()?;
+        let op = match self.peek() {
+            Some(Token::Lt) => BinaryOp::Lt,
+            Some(Token::Le) => BinaryOp::Le,
+            Some(Token::Gt) => BinaryOp::Gt,
+            Some(Token::Ge) => BinaryOp::Ge,
+            Some(Token::Eq) => BinaryOp::Eq,
+            Some(Token::Ne) => BinaryOp::Ne,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        let rhs = self.additive()?;
+        Ok(Ast::Binary(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn additive(&mut self) -> Result<Ast, ExpressionError> {
+        self.binary_chain(
+            &[(Token::Plus, BinaryOp::Add), (Token::Minus, BinaryOp::Sub)],
+            Self::multiplicative,
+        )
+    }
+
+    fn multiplicative(&mut self) -> Result<Ast, ExpressionError> {
+        self.binary_chain(
+            &[(Token::Star, BinaryOp::Mul), (Token::Slash, BinaryOp::Div)],
+            Self::unary,
+        )
+    }
+
+    fn unary(&mut self) -> Result<Ast, ExpressionError> {
+        if self.eat(&Token::Minus) {
+            self.nest()?;
+            let operand = self.unary()?;
+            self.depth -= 1;
+            Ok(Ast::Unary(UnaryOp::Neg, Box::new(operand)))
+        } else {
+            self.postfix()
+        }
+    }
+
+    fn postfix(&mut self) -> Result<Ast, ExpressionError> {
+        let mut ast = self.primary()?;
+        if self.peek() == Some(&Token::Dot) {
+            let position = self.position();
+            self.pos += 1;
+            let Some((Token::Ident(name), _)) = self.tokens.get(self.pos).cloned() else {
+                return Err(ExpressionError::new("expected field name", self.position()));
+            };
+            self.pos += 1;
+            match &mut ast {
+                Ast::Call { field, .. } if field.is_none() => *field = Some(name),
+                _ => {
+                    return Err(ExpressionError::new(
+                        "fields can only be read from indicator calls",
+                        position,
+                    ));
+                }
+            }
+        }
+        Ok(ast)
+    }
+
+    fn primary(&mut self) -> Result<Ast, ExpressionError> {
+        let position = self.position();
+        match self.tokens.get(self.pos).cloned() {
+            Some((Token::Number(x), _)) => {
+                self.pos += 1;
+                Ok(Ast::Number(x))
+            }
+            Some((Token::LParen, _)) => {
+                self.pos += 1;
+                self.nest()?;
+                let ast = self.or()?;
+                self.expect(Token::RParen, "')'")?;
+                self.depth -= 1;
+                Ok(ast)
+            }
+            Some((Token::Ident(name), _)) => {
+                self.pos += 1;
+                if !self.eat(&Token::LParen) {
+                    return Ok(Ast::Ident { name, position });
+                }
+                self.nest()?;
+                let mut args = Vec::new();
+                if !self.eat(&Token::RParen) {
+                    loop {
+                        args.push(self.or()?);
+                        if self.eat(&Token::RParen) {
+                            break;
+                        }
+                        self.expect(Token::Comma, "',' or ')'")?;
+                    }
+                }
+                self.depth -= 1;
+                Ok(Ast::Call {
+                    name,
+                    args,
+                    field: None,
+                    position,
+                })
+            }
+            _ => Err(ExpressionError::new("expected a value", position)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lexer::tokenize;
+    use super::*;
+
+    fn parse(source: &str) -> Result<Ast, ExpressionError> {
+        Parser::new(tokenize(source)?, source.len()).parse()
+    }
+
+    fn ident(name: &str, position: usize) -> Box<Ast> {
+        Box::new(Ast::Ident {
+            name: name.into(),
+            position,
+        })
+    }
+
+    #[test]
+    fn respects_precedence_and_associativity() {
+        assert_eq!(
+            parse("close - open - 1 * 2").unwrap(),
+            Ast::Binary(
+                BinaryOp::Sub,
+                Box::new(Ast::Binary(
+                    BinaryOp::Sub,
+                    ident("close", 0),
+                    ident("open", 8)
+                )),
+                Box::new(Ast::Binary(
+                    BinaryOp::Mul,
+                    Box::new(Ast::Number(1.0)),
+                    Box::new(Ast::Number(2.0))
+                )),
+            )
+        );
+    }
+
+    #[test]
+    fn comparisons_bind_tighter_than_logic() {
+        let ast = parse("high > 1 or not low < 2").unwrap();
+        assert!(matches!(ast, Ast::Binary(BinaryOp::Or, _, ref rhs)
+            if matches!(**rhs, Ast::Unary(UnaryOp::Not, _))));
+    }
+
+    #[test]
+    fn reads_call_fields() {
+        assert_eq!(
+            parse("bb(20, 2).upper").unwrap(),
+            Ast::Call {
+                name: "bb".into(),
+                args: vec![Ast::Number(20.0), Ast::Number(2.0)],
+                field: Some("upper".into()),
+                position: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        assert_eq!(parse("ema(close, 20").unwrap_err().position(), 13);
+        assert_eq!(parse("close.x").unwrap_err().position(), 5);
+        assert_eq!(parse("1 2").unwrap_err().position(), 2);
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let nested = |open: &str, depth: usize, close: &str| {
+            format!("{}1{}", open.repeat(depth), close.repeat(depth))
+        };
+
+        assert!(parse(&nested("(", MAX_DEPTH, ")")).is_ok());
+        for source in [
+            nested("(", 100_000, ")"),
+            nested("-", 100_000, ""),
+            nested("not ", 100_000, ""),
+            nested("sma(", 100_000, ")"),
+            nested("1 + ", 100_000, ""),
+        ] {
+            assert_eq!(
+                parse(&source).unwrap_err().message(),
+                "expression is nested too deeply"
+            );
+        }
+    }
+}
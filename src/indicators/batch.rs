@@ -0,0 +1,322 @@
+use crate::indicators::{Indicator, Price};
+use std::borrow::Borrow;
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+/// Columnar candle input: one slice per field, all the same length.
+///
+/// Iterating yields a [`Price`] per row, so a `&Candles` can be passed
+/// anywhere the batch methods on [`Indicator`](crate::indicators::Indicator)
+/// accept prices. Times are zero unless set with `with_times`.
+#[derive(Clone, Copy, Debug)]
+pub struct Candles<'a> {
+    open: &'a [f64],
+    high: &'a [f64],
+    low: &'a [f64],
+    close: &'a [f64],
+    volume: &'a [f64],
+    open_time: Option<&'a [u64]>,
+    close_time: Option<&'a [u64]>,
+}
+
+impl<'a> Candles<'a> {
+    pub fn new(
+        open: &'a [f64],
+        high: &'a [f64],
+        low: &'a [f64],
+        close: &'a [f64],
+        volume: &'a [f64],
+    ) -> Self {
+        let len = close.len();
+        assert!(
+            open.len() == len && high.len() == len && low.len() == len && volume.len() == len,
+            "Candles columns must all have the same length"
+        );
+
+        Self {
+            open,
+            high,
+            low,
+            close,
+            volume,
+            open_time: None,
+            close_time: None,
+        }
+    }
+
+    pub fn with_times(mut self, open_time: &'a [u64], close_time: &'a [u64]) -> Self {
+        assert!(
+            open_time.len() == self.len() && close_time.len() == self.len(),
+            "Candles time columns must match the price columns in length"
+        );
+        self.open_time = Some(open_time);
+        self.close_time = Some(close_time);
+        self
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.close.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.close.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> Option<Price> {
+        (i < self.len()).then(|| self.row(i))
+    }
+
+    #[inline]
+    fn row(&self, i: usize) -> Price {
+        Price {
+            open: self.open[i],
+            high: self.high[i],
+            low: self.low[i],
+            close: self.close[i],
+            open_time: self.open_time.map_or(0, |t| t[i]),
+            close_time: self.close_time.map_or(0, |t| t[i]),
+            vlm: self.volume[i],
+        }
+    }
+
+    pub fn iter(&self) -> CandlesIter<'a> {
+        CandlesIter {
+            candles: *self,
+            rows: 0..self.len(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for Candles<'a> {
+    type Item = Price;
+    type IntoIter = CandlesIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &Candles<'a> {
+    type Item = Price;
+    type IntoIter = CandlesIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Yields the rows of a [`Candles`] as [`Price`]s, oldest first.
+#[derive(Clone, Debug)]
+pub struct CandlesIter<'a> {
+    candles: Candles<'a>,
+    rows: Range<usize>,
+}
+
+impl Iterator for CandlesIter<'_> {
+    type Item = Price;
+
+    #[inline]
+    fn next(&mut self) -> Option<Price> {
+        self.rows.next().map(|i| self.candles.row(i))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for CandlesIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Price> {
+        self.rows.next_back().map(|i| self.candles.row(i))
+    }
+}
+
+impl ExactSizeIterator for CandlesIter<'_> {}
+
+impl FusedIterator for CandlesIter<'_> {}
+
+/// Reads one column straight from an indicator's state.
+pub(crate) type ColumnReader<I> = fn(&I) -> Option<f64>;
+
+/// Feeds every candle as a close and fills one column per reader, with `NaN`
+/// where a reader has no value. Indicators overriding `compute_columns` map
+/// each field name to a reader once, before the loop.
+pub(crate) fn fill_columns<I, P>(
+    indicator: &mut I,
+    price_data: P,
+    readers: &[ColumnReader<I>],
+) -> Vec<Vec<f64>>
+where
+    I: Indicator,
+    P: IntoIterator,
+    P::Item: Borrow<Price>,
+{
+    let price_data = price_data.into_iter();
+    let mut columns: Vec<Vec<f64>> = readers
+        .iter()
+        .map(|_| Vec::with_capacity(price_data.size_hint().0))
+        .collect();
+
+    for price in price_data {
+        indicator.update_after_close(*price.borrow());
+        for (column, read) in columns.iter_mut().zip(readers) {
+            column.push(read(indicator).unwrap_or(f64::NAN));
+        }
+    }
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Atr, BollingerBands, Ema, Indicator, Rsi, Sma, Value};
+
+    fn p(close: f64) -> Price {
+        Price {
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 1.0,
+        }
+    }
+
+    #[test]
+    fn compute_aligns_values_with_input() {
+        let prices = vec![p(10.0), p(12.0), p(14.0)];
+
+        let values = Sma::new(2).compute(&prices);
+
+        assert_eq!(
+            values,
+            vec![
+                None,
+                Some(Value::SmaValue(11.0)),
+                Some(Value::SmaValue(13.0))
+            ]
+        );
+    }
+
+    #[test]
+    fn compute_matches_streaming_updates() {
+        let prices: Vec<Price> = (0..30).map(|i| p(100.0 + (i as f64).sin())).collect();
+        let mut streamed = BollingerBands::new(5, 2.0);
+        let expected: Vec<Option<Value>> = prices
+            .iter()
+            .map(|price| {
+                streamed.update_after_close(*price);
+                streamed.get_last()
+            })
+            .collect();
+
+        assert_eq!(BollingerBands::new(5, 2.0).compute(&prices), expected);
+    }
+
+    #[test]
+    fn columns_fill_missing_values_with_nan() {
+        let prices = vec![p(10.0), p(12.0), p(14.0)];
+
+        let columns = BollingerBands::new(2, 2.0).compute_columns(&prices, &["upper", "mid"]);
+
+        assert_eq!(columns.len(), 2);
+        assert!(columns[0][0].is_nan());
+        assert_eq!(columns[1][1..], [11.0, 13.0]);
+    }
+
+    #[test]
+    fn columnar_input_matches_rows() {
+        let prices: Vec<Price> = (0..20).map(|i| p(50.0 + i as f64 * 0.5)).collect();
+        let open: Vec<f64> = prices.iter().map(|p| p.open).collect();
+        let high: Vec<f64> = prices.iter().map(|p| p.high).collect();
+        let low: Vec<f64> = prices.iter().map(|p| p.low).collect();
+        let close: Vec<f64> = prices.iter().map(|p| p.close).collect();
+        let volume: Vec<f64> = prices.iter().map(|p| p.vlm).collect();
+        let candles = Candles::new(&open, &high, &low, &close, &volume);
+
+        assert_eq!(candles.get(3), Some(prices[3]));
+        assert_eq!(Atr::new(5).compute(&candles), Atr::new(5).compute(&prices));
+    }
+
+    fn streamed<I: Indicator>(mut indicator: I, prices: &[Price], field: &str) -> Vec<f64> {
+        prices
+            .iter()
+            .map(|price| {
+                indicator.update_after_close(*price);
+                indicator
+                    .get_last()
+                    .and_then(|value| value.field(field))
+                    .unwrap_or(f64::NAN)
+            })
+            .collect()
+    }
+
+    fn same_column(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b) {
+            assert!(a == b || (a.is_nan() && b.is_nan()), "left={a}, right={b}");
+        }
+    }
+
+    #[test]
+    fn column_overrides_match_streaming_values() {
+        let prices: Vec<Price> = (0..40)
+            .map(|i| p(100.0 + (i as f64 * 0.7).sin() * 5.0))
+            .collect();
+
+        for field in ["value", "upper"] {
+            same_column(
+                &Sma::new(5).compute_column(&prices, field),
+                &streamed(Sma::new(5), &prices, field),
+            );
+            same_column(
+                &Ema::new(5).compute_column(&prices, field),
+                &streamed(Ema::new(5), &prices, field),
+            );
+            same_column(
+                &Rsi::new(5, 5, None, None, None).compute_column(&prices, field),
+                &streamed(Rsi::new(5, 5, None, None, None), &prices, field),
+            );
+            same_column(
+                &Atr::new(5).compute_column(&prices, field),
+                &streamed(Atr::new(5), &prices, field),
+            );
+        }
+
+        let fields = ["upper", "mid", "lower", "width", "value"];
+        let columns = BollingerBands::new(5, 2.0).compute_columns(&prices, &fields);
+        for (column, field) in columns.iter().zip(fields) {
+            same_column(
+                column,
+                &streamed(BollingerBands::new(5, 2.0), &prices, field),
+            );
+        }
+    }
+
+    #[test]
+    fn candles_iterate_rows_in_both_directions() {
+        let close = [1.0, 2.0, 3.0];
+        let candles = Candles::new(&close, &close, &close, &close, &close);
+        let mut rows = candles.iter();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows.next_back().map(|price| price.close), Some(3.0));
+        assert_eq!(rows.next().map(|price| price.close), Some(1.0));
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            Sma::new(2).compute_column(candles, "value")[1..],
+            [1.5, 2.5]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_ragged_columns() {
+        Candles::new(&[1.0], &[1.0], &[1.0], &[1.0, 2.0], &[1.0]);
+    }
+}
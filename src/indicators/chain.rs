@@ -0,0 +1,198 @@
+use crate::indicators::{Indicator, Price, Value};
+
+/// Feeds one field of `source`'s output into `target`, e.g. an `Ema` over
+/// `Obv` or `BollingerBands` over `Rsi`.
+///
+/// The target sees each source value as a candle whose open, high, low and
+/// close are that value (see [`Price::with_value`]); times and volume come
+/// from the original candle. Bars where the source is not ready are skipped.
+/// Provisional updates reach the target as provisional updates, so a chain
+/// never commits a value before its candle closes.
+#[derive(Clone, Debug)]
+pub struct Chain<S, T> {
+    source: S,
+    field: String,
+    target: T,
+}
+
+impl<S: Indicator, T: Indicator> Chain<S, T> {
+    /// `field` is read with [`Value::field`]; single-line indicators use `"value"`.
+    pub fn new(source: S, field: &str, target: T) -> Self {
+        Self {
+            source,
+            field: field.to_string(),
+            target,
+        }
+    }
+
+    #[inline]
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    #[inline]
+    pub fn target(&self) -> &T {
+        &self.target
+    }
+
+    fn input(&self, price: Price) -> Option<Price> {
+        let x = self.source.get_last()?.field(&self.field)?;
+        Some(price.with_value(x))
+    }
+}
+
+impl<S: Indicator, T: Indicator> Indicator for Chain<S, T> {
+    fn update_after_close(&mut self, price: Price) {
+        self.source.update_after_close(price);
+        if let Some(input) = self.input(price) {
+            self.target.update_after_close(input);
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.source.update_before_close(price);
+        if let Some(input) = self.input(price) {
+            self.target.update_before_close(input);
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.target.is_ready()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.target.get_last()
+    }
+
+    fn reset(&mut self) {
+        self.source.reset();
+        self.target.reset();
+    }
+
+    fn period(&self) -> u32 {
+        self.source.period() + self.target.period()
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.source.warmup_bars() + self.target.warmup_bars() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{BollingerBands, Ema, IndicatorKind, Obv, Rsi, Sma};
+
+    fn p(close: f64, vlm: f64) -> Price {
+        Price {
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    fn series() -> Vec<Price> {
+        [10.0, 11.0, 10.5, 12.0, 13.0, 12.5, 14.0, 13.5, 15.0, 14.0]
+            .iter()
+            .zip([5.0, 7.0, 3.0, 8.0, 6.0, 4.0, 9.0, 2.0, 7.0, 5.0])
+            .map(|(close, vlm)| p(*close, vlm))
+            .collect()
+    }
+
+    #[test]
+    fn warmup_adds_source_and_target_bars() {
+        let mut chain = Chain::new(Ema::new(3), "value", Sma::new(4));
+        let prices = series();
+
+        assert_eq!(chain.warmup_bars(), 6);
+
+        chain.load(&prices[..5]);
+        assert!(!chain.is_ready());
+
+        chain.update_after_close(prices[5]);
+        assert!(chain.is_ready());
+    }
+
+    #[test]
+    fn ema_over_obv_matches_manual_feed() {
+        let mut chain = Chain::new(Obv::new(), "value", Ema::new(3));
+        let mut obv = Obv::new();
+        let mut ema = Ema::new(3);
+
+        for price in series() {
+            chain.update_after_close(price);
+            obv.update_after_close(price);
+            if let Some(Value::ObvValue(x)) = obv.get_last() {
+                ema.update_after_close(price.with_value(x));
+            }
+        }
+
+        assert!(chain.is_ready());
+        assert_eq!(chain.get_last(), ema.get_last());
+    }
+
+    #[test]
+    fn chains_boxed_indicators() {
+        let mut chain = Chain::new(IndicatorKind::Atr(3).build(), "value", Sma::new(2));
+
+        chain.load(&series()[..3]);
+        assert!(!chain.is_ready());
+
+        chain.update_after_close(series()[3]);
+        assert!(chain.is_ready());
+        assert_eq!(chain.period(), 5);
+    }
+
+    #[test]
+    fn before_close_is_provisional() {
+        let mut live = Chain::new(Rsi::default(), "value", BollingerBands::new(3, 2.0));
+        let mut close_only = Chain::new(Rsi::default(), "value", BollingerBands::new(3, 2.0));
+        let prices: Vec<Price> = (0..20)
+            .map(|i| p(100.0 + ((i * 7) % 5) as f64, 1.0))
+            .collect();
+
+        live.load(&prices);
+        close_only.load(&prices);
+
+        live.update_before_close(p(150.0, 1.0));
+        assert_ne!(live.get_last(), close_only.get_last());
+
+        live.update_after_close(p(101.0, 1.0));
+        close_only.update_after_close(p(101.0, 1.0));
+
+        let (
+            Some(Value::BollingerValue { upper: a, .. }),
+            Some(Value::BollingerValue { upper: b, .. }),
+        ) = (live.get_last(), close_only.get_last())
+        else {
+            panic!("Bollinger not ready");
+        };
+        approx_eq(a, b);
+    }
+
+    #[test]
+    fn reset_clears_state() {
+        let mut chain = Chain::new(Obv::new(), "value", Ema::new(3));
+        chain.load(&series());
+
+        chain.reset();
+
+        assert!(!chain.is_ready());
+        assert!(!chain.source().is_ready());
+        assert_eq!(chain.get_last(), None);
+    }
+}
@@ -0,0 +1,636 @@
+use crate::indicators::trend::{PivotHits, PivotWindow, SwingPoint};
+use crate::indicators::{Indicator, Price, Value};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+#[derive(PartialEq, PartialOrd, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DivergenceKind {
+    /// Price makes a lower low, the oscillator a higher low.
+    RegularBullish,
+    /// Price makes a higher low, the oscillator a lower low.
+    HiddenBullish,
+    /// Price makes a higher high, the oscillator a lower high.
+    RegularBearish,
+    /// Price makes a lower high, the oscillator a higher high.
+    HiddenBearish,
+}
+
+/// A price pivot paired with the nearest oscillator pivot of the same type.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DivergencePivot {
+    /// `open_time` of the price pivot bar.
+    pub time: u64,
+    /// Index of the price pivot bar, counted from the first update.
+    pub bar: u64,
+    pub price: f64,
+    pub oscillator: f64,
+    /// Index of the oscillator pivot bar.
+    pub oscillator_bar: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DivergenceEvent {
+    pub kind: DivergenceKind,
+    pub from: DivergencePivot,
+    pub to: DivergencePivot,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PricePivot {
+    bar: u64,
+    time: u64,
+    price: f64,
+}
+
+/// Pivots of one type, lows or highs.
+#[derive(Clone, Debug, Default)]
+struct PivotPairs {
+    prices: VecDeque<PricePivot>,
+    /// Oscillator pivots waiting for every price pivot that could pair
+    /// with them to be confirmed.
+    pending: VecDeque<SwingPoint>,
+    last: Option<DivergencePivot>,
+}
+
+impl PivotPairs {
+    /// Pairs the pending oscillator pivots, followed by `oscillator_hit`,
+    /// with the price pivots plus `price_hit`, with price pivots known up to
+    /// bar `known`. An oscillator pivot is paired once no unconfirmed price
+    /// pivot could be nearer than the best one so far; ties go to the earlier
+    /// price pivot. Returns how many oscillator pivots were settled and the
+    /// last pair, leaving `self` untouched.
+    fn settle(
+        &self,
+        price_hit: Option<PricePivot>,
+        oscillator_hit: Option<SwingPoint>,
+        known: u64,
+        high: bool,
+        (tolerance, lookback): (u64, u64),
+        events: &mut Vec<DivergenceEvent>,
+    ) -> (usize, Option<DivergencePivot>) {
+        let prices = self.prices.iter().copied().chain(price_hit);
+        let mut last = self.last;
+        let mut settled = 0;
+
+        for oscillator in self.pending.iter().copied().chain(oscillator_hit) {
+            let best = prices
+                .clone()
+                .filter(|price| price.bar.abs_diff(oscillator.time) <= tolerance)
+                .min_by_key(|price| (price.bar.abs_diff(oscillator.time), price.bar));
+            let distance = best.map_or(tolerance + 1, |price| price.bar.abs_diff(oscillator.time));
+            if known + 1 < oscillator.time + distance {
+                break;
+            }
+            settled += 1;
+
+            if let Some(price) = best {
+                let to = DivergencePivot {
+                    time: price.time,
+                    bar: price.bar,
+                    price: price.price,
+                    oscillator: oscillator.price,
+                    oscillator_bar: oscillator.time,
+                };
+                events.extend(compare(last, to, high, lookback));
+                last = Some(to);
+            }
+        }
+
+        (settled, last)
+    }
+
+    /// Stores the new pivots and drops the `settled` oscillator pivots.
+    fn commit(
+        &mut self,
+        price_hit: Option<PricePivot>,
+        oscillator_hit: Option<SwingPoint>,
+        settled: usize,
+        last: Option<DivergencePivot>,
+    ) {
+        self.prices.extend(price_hit);
+        self.pending.extend(oscillator_hit);
+        self.pending.drain(..settled);
+        self.last = last;
+    }
+
+    /// Drops price pivots too old to pair with any future oscillator pivot.
+    fn prune(&mut self, known: u64, tolerance: u64) {
+        while self
+            .prices
+            .front()
+            .is_some_and(|price| price.bar + 2 * tolerance < known)
+        {
+            self.prices.pop_front();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.prices.clear();
+        self.pending.clear();
+        self.last = None;
+    }
+}
+
+/// Finds swing pivots on price (`high`/`low`) and on an oscillator line,
+/// pairs each oscillator pivot with the nearest price pivot of the same type
+/// at most `tolerance` bars away, and compares consecutive pairs.
+#[derive(Clone, Debug)]
+pub(crate) struct DivergenceTracker {
+    price_window: PivotWindow,
+    oscillator_window: PivotWindow,
+    right: u64,
+    tolerance: u64,
+    lookback: u64,
+    /// `open_time` of the last `right + 1` bars.
+    times: VecDeque<u64>,
+    bars: u64,
+    lows: PivotPairs,
+    highs: PivotPairs,
+}
+
+impl DivergenceTracker {
+    pub fn new(left: u32, right: u32, tolerance: u32, lookback: u32) -> Self {
+        assert!(
+            lookback > 0,
+            "Divergence lookback must be > 0, got {}",
+            lookback
+        );
+        Self {
+            price_window: PivotWindow::new(left, right),
+            oscillator_window: PivotWindow::new(left, right),
+            right: right as u64,
+            tolerance: tolerance as u64,
+            lookback: lookback as u64,
+            times: VecDeque::with_capacity(right as usize + 1),
+            bars: 0,
+            lows: PivotPairs::default(),
+            highs: PivotPairs::default(),
+        }
+    }
+
+    pub fn with_tolerance(mut self, bars: u32) -> Self {
+        self.tolerance = bars as u64;
+        self
+    }
+
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.price_window.is_ready() && self.oscillator_window.is_ready()
+    }
+
+    /// Commits a closed bar and returns the divergences it confirms. Bars
+    /// without an oscillator value still count for price pivots and
+    /// distances.
+    pub fn push(&mut self, price: &Price, oscillator: Option<f64>) -> Vec<DivergenceEvent> {
+        let pivot_time = self.pivot_time(price);
+        if self.times.len() as u64 == self.right + 1 {
+            self.times.pop_front();
+        }
+        self.times.push_back(price.open_time);
+
+        let price_hits = self.price_window.push(self.bars, price.high, price.low);
+        let oscillator_hits = oscillator
+            .map(|value| self.oscillator_window.push(self.bars, value, value))
+            .unwrap_or_default();
+
+        let mut events = Vec::new();
+        let limits = (self.tolerance, self.lookback);
+        if let Some(known) = self.bars.checked_sub(self.right) {
+            for high in [false, true] {
+                let (price_hit, oscillator_hit) =
+                    hits(high, pivot_time, price_hits, oscillator_hits);
+                let pairs = self.pairs_mut(high);
+                let (settled, last) =
+                    pairs.settle(price_hit, oscillator_hit, known, high, limits, &mut events);
+                pairs.commit(price_hit, oscillator_hit, settled, last);
+                pairs.prune(known, limits.0);
+            }
+        }
+
+        self.bars += 1;
+        events
+    }
+
+    /// The divergences [`DivergenceTracker::push`] would return for this
+    /// bar, without committing it.
+    pub fn preview(&self, price: &Price, oscillator: Option<f64>) -> Vec<DivergenceEvent> {
+        let mut events = Vec::new();
+        let Some(known) = self.bars.checked_sub(self.right) else {
+            return events;
+        };
+
+        let pivot_time = self.pivot_time(price);
+        let price_hits = self
+            .price_window
+            .peek(self.bars, price.high, price.low)
+            .unwrap_or_default();
+        let oscillator_hits = oscillator
+            .and_then(|value| self.oscillator_window.peek(self.bars, value, value))
+            .unwrap_or_default();
+
+        for high in [false, true] {
+            let (price_hit, oscillator_hit) = hits(high, pivot_time, price_hits, oscillator_hits);
+            let pairs = if high { &self.highs } else { &self.lows };
+            let limits = (self.tolerance, self.lookback);
+            pairs.settle(price_hit, oscillator_hit, known, high, limits, &mut events);
+        }
+
+        events
+    }
+
+    /// `open_time` of the bar `right` bars before `price`, the bar a price
+    /// pivot confirmed by `price` sits on.
+    fn pivot_time(&self, price: &Price) -> u64 {
+        let skip = usize::from(self.times.len() as u64 == self.right + 1);
+        self.times.get(skip).copied().unwrap_or(price.open_time)
+    }
+
+    fn pairs_mut(&mut self, high: bool) -> &mut PivotPairs {
+        if high {
+            &mut self.highs
+        } else {
+            &mut self.lows
+        }
+    }
+
+    pub fn period(&self) -> u32 {
+        self.price_window.period()
+    }
+
+    pub fn reset(&mut self) {
+        self.price_window.reset();
+        self.oscillator_window.reset();
+        self.times.clear();
+        self.bars = 0;
+        self.lows.clear();
+        self.highs.clear();
+    }
+}
+
+/// The price and oscillator hits of one pivot type; a price pivot sits on
+/// the bar that opened at `pivot_time`.
+fn hits(
+    high: bool,
+    pivot_time: u64,
+    price: PivotHits,
+    oscillator: PivotHits,
+) -> (Option<PricePivot>, Option<SwingPoint>) {
+    let (price, oscillator) = if high {
+        (price.high, oscillator.high)
+    } else {
+        (price.low, oscillator.low)
+    };
+    let price = price.map(|hit| PricePivot {
+        bar: hit.time,
+        time: pivot_time,
+        price: hit.price,
+    });
+    (price, oscillator)
+}
+
+fn compare(
+    from: Option<DivergencePivot>,
+    to: DivergencePivot,
+    high: bool,
+    lookback: u64,
+) -> Option<DivergenceEvent> {
+    let from = from.filter(|from| from.bar < to.bar && to.bar - from.bar <= lookback)?;
+
+    let kind = match (
+        high,
+        to.price.partial_cmp(&from.price)?,
+        to.oscillator.partial_cmp(&from.oscillator)?,
+    ) {
+        (false, Ordering::Less, Ordering::Greater) => DivergenceKind::RegularBullish,
+        (false, Ordering::Greater, Ordering::Less) => DivergenceKind::HiddenBullish,
+        (true, Ordering::Greater, Ordering::Less) => DivergenceKind::RegularBearish,
+        (true, Ordering::Less, Ordering::Greater) => DivergenceKind::HiddenBearish,
+        _ => return None,
+    };
+
+    Some(DivergenceEvent { kind, from, to })
+}
+
+pub(crate) fn divergence_value(events: &[DivergenceEvent]) -> Value {
+    let has = |kind| events.iter().any(|event| event.kind == kind);
+    Value::DivergenceValue {
+        regular_bullish: has(DivergenceKind::RegularBullish),
+        hidden_bullish: has(DivergenceKind::HiddenBullish),
+        regular_bearish: has(DivergenceKind::RegularBearish),
+        hidden_bearish: has(DivergenceKind::HiddenBearish),
+    }
+}
+
+/// Compares swing pivots of an oscillator line with swing pivots of price.
+///
+/// Both are found with `left`/`right` confirmation bars. Each oscillator
+/// pivot is paired with the nearest price pivot of the same type at most
+/// `tolerance` bars away (2 by default), and consecutive pairs are compared
+/// when their price pivots are at most `lookback` bars apart.
+#[derive(Debug)]
+pub struct Divergence {
+    source: Box<dyn Indicator>,
+    extract: fn(&Value) -> Option<f64>,
+    tracker: DivergenceTracker,
+    events: Vec<DivergenceEvent>,
+    ready: bool,
+}
+
+impl Divergence {
+    pub fn new(
+        source: impl Indicator + 'static,
+        extract: fn(&Value) -> Option<f64>,
+        left: u32,
+        right: u32,
+        lookback: u32,
+    ) -> Self {
+        Self {
+            source: Box::new(source),
+            extract,
+            tracker: DivergenceTracker::new(left, right, 2, lookback),
+            events: Vec::new(),
+            ready: false,
+        }
+    }
+
+    pub fn with_tolerance(mut self, bars: u32) -> Self {
+        self.tracker = self.tracker.with_tolerance(bars);
+        self
+    }
+
+    /// Divergences confirmed on the latest update, including provisional ones.
+    #[inline]
+    pub fn events(&self) -> &[DivergenceEvent] {
+        &self.events
+    }
+
+    fn oscillator(&self) -> Option<f64> {
+        self.source.get_last().as_ref().and_then(self.extract)
+    }
+}
+
+impl Indicator for Divergence {
+    fn update_after_close(&mut self, price: Price) {
+        self.source.update_after_close(price);
+        self.events = self.tracker.push(&price, self.oscillator());
+        self.ready = self.tracker.is_ready();
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.source.update_before_close(price);
+        self.events = self.tracker.preview(&price, self.oscillator());
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.ready.then(|| divergence_value(&self.events))
+    }
+
+    fn reset(&mut self) {
+        self.source.reset();
+        self.tracker.reset();
+        self.events.clear();
+        self.ready = false;
+    }
+
+    fn period(&self) -> u32 {
+        self.source.period() + self.tracker.period()
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.source.warmup_bars() + self.tracker.period() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reports each bar's volume as the oscillator line.
+    #[derive(Debug, Default)]
+    struct Echo(Option<f64>);
+
+    impl Indicator for Echo {
+        fn update_after_close(&mut self, price: Price) {
+            self.0 = Some(price.vlm);
+        }
+        fn update_before_close(&mut self, price: Price) {
+            self.0 = Some(price.vlm);
+        }
+        fn load(&mut self, price_data: &[Price]) {
+            for price in price_data {
+                self.update_after_close(*price);
+            }
+        }
+        fn is_ready(&self) -> bool {
+            self.0.is_some()
+        }
+        fn get_last(&self) -> Option<Value> {
+            self.0.map(Value::ObvValue)
+        }
+        fn reset(&mut self) {
+            self.0 = None;
+        }
+        fn period(&self) -> u32 {
+            1
+        }
+    }
+
+    fn echo(value: &Value) -> Option<f64> {
+        match value {
+            Value::ObvValue(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    fn p(time: u64, close: f64, oscillator: f64) -> Price {
+        Price {
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            open_time: time,
+            close_time: time,
+            vlm: oscillator,
+        }
+    }
+
+    fn divergence() -> Divergence {
+        Divergence::new(Echo::default(), echo, 1, 1, 10)
+    }
+
+    // Oscillator lows at bars 1 and 4; price lows at 9 and 7.
+    fn bullish_setup() -> [Price; 5] {
+        [
+            p(0, 12.0, 50.0),
+            p(1, 10.0, 30.0),
+            p(2, 11.0, 45.0),
+            p(3, 11.0, 45.0),
+            p(4, 8.0, 35.0),
+        ]
+    }
+
+    #[test]
+    fn detects_regular_bullish_divergence() {
+        let mut div = divergence();
+
+        div.load(&bullish_setup());
+        assert_eq!(
+            div.get_last(),
+            Some(Value::DivergenceValue {
+                regular_bullish: false,
+                hidden_bullish: false,
+                regular_bearish: false,
+                hidden_bearish: false,
+            })
+        );
+
+        div.update_after_close(p(5, 9.0, 40.0));
+
+        assert_eq!(
+            div.events(),
+            &[DivergenceEvent {
+                kind: DivergenceKind::RegularBullish,
+                from: DivergencePivot {
+                    time: 1,
+                    bar: 1,
+                    price: 9.0,
+                    oscillator: 30.0,
+                    oscillator_bar: 1,
+                },
+                to: DivergencePivot {
+                    time: 4,
+                    bar: 4,
+                    price: 7.0,
+                    oscillator: 35.0,
+                    oscillator_bar: 4,
+                },
+            }]
+        );
+        assert!(matches!(
+            div.get_last(),
+            Some(Value::DivergenceValue {
+                regular_bullish: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn detects_hidden_bearish_divergence() {
+        let mut div = divergence();
+
+        div.load(&[
+            p(0, 10.0, 50.0),
+            p(1, 14.0, 70.0),
+            p(2, 12.0, 60.0),
+            p(3, 13.0, 75.0),
+            p(4, 11.0, 55.0),
+        ]);
+
+        assert_eq!(div.events().len(), 1);
+        assert_eq!(div.events()[0].kind, DivergenceKind::HiddenBearish);
+        assert_eq!(div.events()[0].from.time, 1);
+        assert_eq!(div.events()[0].to.time, 3);
+    }
+
+    #[test]
+    fn pairs_oscillator_pivots_with_nearby_price_pivots() {
+        // Price bottoms at bar 3, the oscillator a bar later. Price at bar 4
+        // alone is a higher low and would hide the divergence.
+        let bars = [
+            p(0, 12.0, 50.0),
+            p(1, 10.0, 30.0),
+            p(2, 11.0, 45.0),
+            p(3, 8.0, 40.0),
+            p(4, 11.0, 35.0),
+            p(5, 10.0, 40.0),
+        ];
+
+        let mut div = divergence();
+        div.load(&bars);
+
+        assert_eq!(div.events().len(), 1);
+        let event = div.events()[0];
+        assert_eq!(event.kind, DivergenceKind::RegularBullish);
+        assert_eq!((event.to.bar, event.to.price), (3, 7.0));
+        assert_eq!((event.to.oscillator_bar, event.to.oscillator), (4, 35.0));
+
+        let mut strict = divergence().with_tolerance(0);
+        strict.load(&bars);
+        assert!(strict.events().is_empty());
+    }
+
+    #[test]
+    fn pivots_beyond_lookback_are_not_compared() {
+        let mut div = Divergence::new(Echo::default(), echo, 1, 1, 2);
+
+        div.load(&bullish_setup());
+        div.update_after_close(p(5, 9.0, 40.0));
+
+        assert!(div.events().is_empty());
+    }
+
+    #[test]
+    fn before_close_divergence_is_provisional() {
+        let mut div = divergence();
+
+        div.load(&bullish_setup());
+
+        div.update_before_close(p(5, 9.0, 40.0));
+        assert_eq!(div.events().len(), 1);
+
+        div.update_before_close(p(5, 9.0, 30.0));
+        assert!(div.events().is_empty());
+
+        div.update_after_close(p(5, 9.0, 40.0));
+        assert_eq!(div.events().len(), 1);
+    }
+
+    #[test]
+    fn preview_matches_push_without_committing() {
+        for (left, right, tolerance) in [(1, 1, 2), (2, 3, 1), (3, 2, 0)] {
+            let mut tracker = DivergenceTracker::new(left, right, tolerance, 20);
+            let mut events = 0;
+            let mut seed = 7u64;
+            for bar in 0..300 {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let close = 100.0 + (seed >> 58) as f64;
+                let oscillator = (bar % 7 != 3).then_some(50.0 + ((seed >> 40) & 0x1f) as f64);
+                let price = p(bar, close, 0.0);
+
+                let preview = tracker.preview(&price, oscillator);
+                assert_eq!(preview, tracker.clone().push(&price, oscillator));
+                events += tracker.push(&price, oscillator).len();
+            }
+            assert!(events > 0);
+        }
+    }
+
+    #[test]
+    fn divergence_reset_clears_state() {
+        let mut div = divergence();
+
+        div.load(&bullish_setup());
+        assert!(div.is_ready());
+
+        div.reset();
+
+        assert!(!div.is_ready());
+        assert_eq!(div.get_last(), None);
+        assert!(div.events().is_empty());
+    }
+}
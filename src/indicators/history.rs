@@ -0,0 +1,229 @@
+use crate::indicators::{Indicator, Price, Value};
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HistoryEntry {
+    /// `close_time` of the candle that produced the value.
+    pub time: u64,
+    pub value: Value,
+}
+
+/// Keeps the last `capacity` confirmed values of the wrapped indicator.
+///
+/// Only closes are recorded, and only once the indicator has a value; the
+/// in-candle value from `update_before_close` is held apart in
+/// [`History::provisional`] and dropped when the candle closes.
+#[derive(Clone, Debug)]
+pub struct History<I> {
+    inner: I,
+    capacity: usize,
+    entries: VecDeque<HistoryEntry>,
+    provisional: Option<HistoryEntry>,
+}
+
+impl<I: Indicator> History<I> {
+    pub fn new(inner: I, capacity: usize) -> Self {
+        assert!(capacity > 0, "History capacity must be > 0");
+        Self {
+            inner,
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+            provisional: None,
+        }
+    }
+
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// The confirmed entry `n` closes back, `0` being the latest close.
+    #[inline]
+    pub fn get(&self, n: usize) -> Option<&HistoryEntry> {
+        let i = self.entries.len().checked_sub(n.checked_add(1)?)?;
+        self.entries.get(i)
+    }
+
+    /// Confirmed entries, newest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> + ExactSizeIterator {
+        self.entries.iter().rev()
+    }
+
+    #[inline]
+    pub fn provisional(&self) -> Option<&HistoryEntry> {
+        self.provisional.as_ref()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn entry(&self, price: &Price) -> Option<HistoryEntry> {
+        self.inner.get_last().map(|value| HistoryEntry {
+            time: price.close_time,
+            value,
+        })
+    }
+}
+
+impl<I: Indicator> Indicator for History<I> {
+    fn update_after_close(&mut self, price: Price) {
+        self.inner.update_after_close(price);
+        self.provisional = None;
+
+        if let Some(entry) = self.entry(&price) {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
+            self.entries.push_back(entry);
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.inner.update_before_close(price);
+        self.provisional = self.entry(&price);
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.inner.get_last()
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.entries.clear();
+        self.provisional = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.inner.period()
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.inner.warmup_bars()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Macd, Sma};
+
+    fn p(time: u64, close: f64) -> Price {
+        Price {
+            open: close,
+            high: close,
+            low: close,
+            close,
+            open_time: time,
+            close_time: time + 59,
+            vlm: 1.0,
+        }
+    }
+
+    fn sma(entry: Option<&HistoryEntry>) -> Option<f64> {
+        entry.and_then(|entry| entry.value.field("value"))
+    }
+
+    #[test]
+    fn lookback_indexes_from_latest_close() {
+        let mut history = History::new(Sma::new(2), 3);
+
+        history.load(&[p(0, 10.0), p(60, 12.0), p(120, 14.0), p(180, 16.0)]);
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(sma(history.get(0)), Some(15.0));
+        assert_eq!(sma(history.get(2)), Some(11.0));
+        assert_eq!(history.get(0).unwrap().time, 239);
+        assert_eq!(history.get(3), None);
+        assert_eq!(history.get(usize::MAX), None);
+    }
+
+    #[test]
+    fn capacity_evicts_oldest_values() {
+        let mut history = History::new(Sma::new(2), 2);
+
+        history.load(&[p(0, 10.0), p(60, 12.0), p(120, 14.0), p(180, 16.0)]);
+
+        let values: Vec<f64> = history
+            .iter()
+            .filter_map(|entry| entry.value.field("value"))
+            .collect();
+        assert_eq!(values, vec![15.0, 13.0]);
+    }
+
+    #[test]
+    fn provisional_value_stays_out_of_history() {
+        let mut history = History::new(Sma::new(2), 5);
+        history.load(&[p(0, 10.0), p(60, 12.0)]);
+
+        history.update_before_close(p(120, 20.0));
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(sma(history.provisional()), Some(16.0));
+        assert_eq!(sma(history.get(0)), Some(11.0));
+
+        history.update_after_close(p(120, 14.0));
+
+        assert_eq!(history.provisional(), None);
+        assert_eq!(sma(history.get(0)), Some(13.0));
+        assert_eq!(sma(history.get(1)), Some(11.0));
+    }
+
+    #[test]
+    fn reads_fields_of_multi_line_values() {
+        let mut history = History::new(Macd::new(2, 3, 2), 5);
+
+        history.load(
+            &(0..8)
+                .map(|i| p(i * 60, (i * i) as f64))
+                .collect::<Vec<_>>(),
+        );
+
+        let histogram: Vec<f64> = history
+            .iter()
+            .take(2)
+            .filter_map(|entry| entry.value.field("histogram"))
+            .collect();
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(
+            history.get(0).unwrap().value.field("histogram"),
+            history
+                .get_last()
+                .and_then(|value| value.field("histogram"))
+        );
+    }
+
+    #[test]
+    fn reset_clears_history() {
+        let mut history = History::new(Sma::new(2), 3);
+        history.load(&[p(0, 10.0), p(60, 12.0)]);
+        history.update_before_close(p(120, 14.0));
+
+        history.reset();
+
+        assert!(history.is_empty());
+        assert_eq!(history.provisional(), None);
+        assert!(!history.is_ready());
+    }
+}
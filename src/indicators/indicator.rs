@@ -1,7 +1,26 @@
-use crate::indicators::Price;
-use std::fmt::Debug;
+use crate::indicators::momentum::{
+    AcceleratorOscillator, Apo, AwesomeOscillator, Cci, ElderRay, Macd, Ppo, Roc, Rsi, SmaRsi,
+    StochasticRsi, Trix, UltimateOscillator,
+};
+use crate::indicators::trend::{
+    Adx, Aroon, Choppiness, Dema, Ema, EmaCross, Ichimoku, MarketStructure, PivotAnchor,
+    PivotMethod, PivotPoints, Sma, StructureEvent, SwingDetector, SwingLabel, Tema, Vortex, ZigZag,
+    ZigZagThreshold,
+};
+use crate::indicators::volatility::{
+    Annualization, Atr, AtrTrailingStop, BollingerBands, Calendar, ChaikinVolatility,
+    ChandelierExit, GarmanKlass, HistVolatility, MassIndex, Parkinson, RogersSatchell, UlcerIndex,
+    YangZhang,
+};
+use crate::indicators::volume::{
+    Cvd, EaseOfMovement, ForceIndex, Klinger, Obv, VolumeDelta, VolumeMa, VolumeOscillator,
+    VwapDeviation,
+};
+use crate::indicators::{Price, Source};
+use std::borrow::Borrow;
+use std::fmt::{self, Debug};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub trait Indicator: Debug + Sync + Send {
     fn update_after_close(&mut self, last_price: Price);
@@ -11,6 +30,93 @@ pub trait Indicator: Debug + Sync + Send {
     fn get_last(&self) -> Option<Value>;
     fn reset(&mut self);
     fn period(&self) -> u32;
+
+    /// Closed candles needed before `is_ready` turns true. Exact for
+    /// fixed-window indicators and a lower bound for event-driven ones
+    /// (`PivotPoints`, `ZigZag`), which also wait on the data itself.
+    fn warmup_bars(&self) -> u32 {
+        self.period()
+    }
+
+    /// Feeds every candle as a close and returns the value after each one,
+    /// aligned with the input. Like `load`, this continues from the current
+    /// state; `reset` first to recompute from scratch.
+    fn compute<P>(&mut self, price_data: P) -> Vec<Option<Value>>
+    where
+        Self: Sized,
+        P: IntoIterator,
+        P::Item: Borrow<Price>,
+    {
+        price_data
+            .into_iter()
+            .map(|price| {
+                self.update_after_close(*price.borrow());
+                self.get_last()
+            })
+            .collect()
+    }
+
+    /// Like `compute`, but writes the named `Value` fields straight into one
+    /// `f64` column each, with `NaN` where there is no value.
+    fn compute_columns<P>(&mut self, price_data: P, fields: &[&str]) -> Vec<Vec<f64>>
+    where
+        Self: Sized,
+        P: IntoIterator,
+        P::Item: Borrow<Price>,
+    {
+        let price_data = price_data.into_iter();
+        let mut columns: Vec<Vec<f64>> = fields
+            .iter()
+            .map(|_| Vec::with_capacity(price_data.size_hint().0))
+            .collect();
+
+        for price in price_data {
+            self.update_after_close(*price.borrow());
+            let value = self.get_last();
+            for (column, field) in columns.iter_mut().zip(fields) {
+                column.push(value.and_then(|v| v.field(field)).unwrap_or(f64::NAN));
+            }
+        }
+        columns
+    }
+
+    fn compute_column<P>(&mut self, price_data: P, field: &str) -> Vec<f64>
+    where
+        Self: Sized,
+        P: IntoIterator,
+        P::Item: Borrow<Price>,
+    {
+        self.compute_columns(price_data, &[field])
+            .pop()
+            .unwrap_or_default()
+    }
+}
+
+impl<I: Indicator + ?Sized> Indicator for Box<I> {
+    fn update_after_close(&mut self, last_price: Price) {
+        (**self).update_after_close(last_price)
+    }
+    fn update_before_close(&mut self, last_price: Price) {
+        (**self).update_before_close(last_price)
+    }
+    fn load(&mut self, price_data: &[Price]) {
+        (**self).load(price_data)
+    }
+    fn is_ready(&self) -> bool {
+        (**self).is_ready()
+    }
+    fn get_last(&self) -> Option<Value> {
+        (**self).get_last()
+    }
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+    fn period(&self) -> u32 {
+        (**self).period()
+    }
+    fn warmup_bars(&self) -> u32 {
+        (**self).warmup_bars()
+    }
 }
 
 #[derive(PartialEq, PartialOrd, Copy, Clone, Debug, Serialize, Deserialize)]
@@ -58,29 +164,360 @@ pub enum Value {
     VolumeMaValue(f64),
     StdDevValue(f64),
     HistVolatilityValue(f64),
+    CvdValue(f64),
+    VolumeDeltaValue {
+        delta: f64,
+        bullish_absorption: bool,
+        bearish_absorption: bool,
+    },
+    AroonValue {
+        up: f64,
+        down: f64,
+        oscillator: f64,
+    },
+    VortexValue {
+        plus: f64,
+        minus: f64,
+    },
+    ChoppinessValue(f64),
+    TrixValue(f64),
+    PpoValue {
+        ppo: f64,
+        signal: f64,
+        histogram: f64,
+    },
+    ApoValue(f64),
+    AwesomeOscillatorValue(f64),
+    AcceleratorOscillatorValue(f64),
+    UltimateOscillatorValue(f64),
+    ElderRayValue {
+        bull: f64,
+        bear: f64,
+    },
+    ForceIndexValue(f64),
+    EaseOfMovementValue(f64),
+    KlingerValue {
+        kvo: f64,
+        signal: f64,
+        histogram: f64,
+    },
+    VolumeOscillatorValue(f64),
+    ParkinsonValue(f64),
+    GarmanKlassValue(f64),
+    RogersSatchellValue(f64),
+    YangZhangValue(f64),
+    UlcerIndexValue(f64),
+    MassIndexValue(f64),
+    ChaikinVolatilityValue(f64),
+    ChandelierExitValue {
+        long_stop: f64,
+        short_stop: f64,
+        is_long: bool,
+        stop_hit: bool,
+    },
+    AtrTrailingStopValue {
+        long_stop: f64,
+        short_stop: f64,
+        is_long: bool,
+        stop_hit: bool,
+    },
+    PivotValue {
+        p: f64,
+        r1: f64,
+        r2: Option<f64>,
+        r3: Option<f64>,
+        s1: f64,
+        s2: Option<f64>,
+        s3: Option<f64>,
+    },
+    SwingValue {
+        high: Option<f64>,
+        low: Option<f64>,
+        new_high: bool,
+        new_low: bool,
+    },
+    MarketStructureValue {
+        swing_high: Option<f64>,
+        swing_low: Option<f64>,
+        high_label: Option<SwingLabel>,
+        low_label: Option<SwingLabel>,
+        bullish: Option<bool>,
+        event: Option<StructureEvent>,
+    },
+    ZigZagValue {
+        pivot: Option<f64>,
+        extreme: f64,
+        is_up: bool,
+        new_pivot: bool,
+    },
+    DivergenceValue {
+        regular_bullish: bool,
+        hidden_bullish: bool,
+        regular_bearish: bool,
+        hidden_bearish: bool,
+    },
+    ExpressionValue(f64),
 }
 
+impl Value {
+    /// Reads one numeric field by name. Single-line values answer to
+    /// `"value"`; flags read as `1.0`/`0.0`. Unknown fields yield `None`.
+    pub fn field(&self, name: &str) -> Option<f64> {
+        let flag = |b: bool| if b { 1.0 } else { 0.0 };
+
+        match *self {
+            Value::RsiValue(value)
+            | Value::EmaValue(value)
+            | Value::DemaValue(value)
+            | Value::TemaValue(value)
+            | Value::ObvValue(value)
+            | Value::VwapDeviationValue(value)
+            | Value::CciValue(value)
+            | Value::SmaValue(value)
+            | Value::SmaRsiValue(value)
+            | Value::RocValue(value)
+            | Value::AdxValue(value)
+            | Value::AtrValue(value)
+            | Value::VolumeMaValue(value)
+            | Value::StdDevValue(value)
+            | Value::HistVolatilityValue(value)
+            | Value::CvdValue(value)
+            | Value::ChoppinessValue(value)
+            | Value::TrixValue(value)
+            | Value::ApoValue(value)
+            | Value::AwesomeOscillatorValue(value)
+            | Value::AcceleratorOscillatorValue(value)
+            | Value::UltimateOscillatorValue(value)
+            | Value::ForceIndexValue(value)
+            | Value::EaseOfMovementValue(value)
+            | Value::VolumeOscillatorValue(value)
+            | Value::ParkinsonValue(value)
+            | Value::GarmanKlassValue(value)
+            | Value::RogersSatchellValue(value)
+            | Value::YangZhangValue(value)
+            | Value::UlcerIndexValue(value)
+            | Value::MassIndexValue(value)
+            | Value::ChaikinVolatilityValue(value)
+            | Value::ExpressionValue(value) => (name == "value").then_some(value),
+            Value::StochRsiValue { k, d } => match name {
+                "k" => Some(k),
+                "d" => Some(d),
+                _ => None,
+            },
+            Value::IchimokuValue {
+                tenkan,
+                kijun,
+                span_a,
+                span_b,
+                chikou,
+            } => match name {
+                "tenkan" => Some(tenkan),
+                "kijun" => Some(kijun),
+                "span_a" => Some(span_a),
+                "span_b" => Some(span_b),
+                "chikou" => Some(chikou),
+                _ => None,
+            },
+            Value::EmaCrossValue { short, long, trend } => match name {
+                "short" => Some(short),
+                "long" => Some(long),
+                "trend" => Some(flag(trend)),
+                _ => None,
+            },
+            Value::MacdValue {
+                macd,
+                signal,
+                histogram,
+            }
+            | Value::PpoValue {
+                ppo: macd,
+                signal,
+                histogram,
+            }
+            | Value::KlingerValue {
+                kvo: macd,
+                signal,
+                histogram,
+            } => match name {
+                "macd" if matches!(self, Value::MacdValue { .. }) => Some(macd),
+                "ppo" if matches!(self, Value::PpoValue { .. }) => Some(macd),
+                "kvo" if matches!(self, Value::KlingerValue { .. }) => Some(macd),
+                "signal" => Some(signal),
+                "histogram" => Some(histogram),
+                _ => None,
+            },
+            Value::BollingerValue {
+                upper,
+                mid,
+                lower,
+                width,
+            } => match name {
+                "upper" => Some(upper),
+                "mid" => Some(mid),
+                "lower" => Some(lower),
+                "width" => Some(width),
+                _ => None,
+            },
+            Value::VolumeDeltaValue {
+                delta,
+                bullish_absorption,
+                bearish_absorption,
+            } => match name {
+                "delta" => Some(delta),
+                "bullish_absorption" => Some(flag(bullish_absorption)),
+                "bearish_absorption" => Some(flag(bearish_absorption)),
+                _ => None,
+            },
+            Value::AroonValue {
+                up,
+                down,
+                oscillator,
+            } => match name {
+                "up" => Some(up),
+                "down" => Some(down),
+                "oscillator" => Some(oscillator),
+                _ => None,
+            },
+            Value::VortexValue { plus, minus } => match name {
+                "plus" => Some(plus),
+                "minus" => Some(minus),
+                _ => None,
+            },
+            Value::ElderRayValue { bull, bear } => match name {
+                "bull" => Some(bull),
+                "bear" => Some(bear),
+                _ => None,
+            },
+            Value::ChandelierExitValue {
+                long_stop,
+                short_stop,
+                is_long,
+                stop_hit,
+            }
+            | Value::AtrTrailingStopValue {
+                long_stop,
+                short_stop,
+                is_long,
+                stop_hit,
+            } => match name {
+                "long_stop" => Some(long_stop),
+                "short_stop" => Some(short_stop),
+                "is_long" => Some(flag(is_long)),
+                "stop_hit" => Some(flag(stop_hit)),
+                _ => None,
+            },
+            Value::PivotValue {
+                p,
+                r1,
+                r2,
+                r3,
+                s1,
+                s2,
+                s3,
+            } => match name {
+                "p" => Some(p),
+                "r1" => Some(r1),
+                "r2" => r2,
+                "r3" => r3,
+                "s1" => Some(s1),
+                "s2" => s2,
+                "s3" => s3,
+                _ => None,
+            },
+            Value::SwingValue {
+                high,
+                low,
+                new_high,
+                new_low,
+            } => match name {
+                "high" => high,
+                "low" => low,
+                "new_high" => Some(flag(new_high)),
+                "new_low" => Some(flag(new_low)),
+                _ => None,
+            },
+            Value::MarketStructureValue {
+                swing_high,
+                swing_low,
+                bullish,
+                ..
+            } => match name {
+                "swing_high" => swing_high,
+                "swing_low" => swing_low,
+                "bullish" => bullish.map(flag),
+                _ => None,
+            },
+            Value::ZigZagValue {
+                pivot,
+                extreme,
+                is_up,
+                new_pivot,
+            } => match name {
+                "pivot" => pivot,
+                "extreme" => Some(extreme),
+                "is_up" => Some(flag(is_up)),
+                "new_pivot" => Some(flag(new_pivot)),
+                _ => None,
+            },
+            Value::DivergenceValue {
+                regular_bullish,
+                hidden_bullish,
+                regular_bearish,
+                hidden_bearish,
+            } => match name {
+                "regular_bullish" => Some(flag(regular_bullish)),
+                "hidden_bullish" => Some(flag(hidden_bullish)),
+                "regular_bearish" => Some(flag(regular_bearish)),
+                "hidden_bearish" => Some(flag(hidden_bearish)),
+                _ => None,
+            },
+        }
+    }
+}
+
+// `remote = "Self"` keeps the derived impls as inherent functions so the
+// trait impls below can also accept the older tuple forms.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", remote = "Self")]
 pub enum IndicatorKind {
-    Rsi(u32),
+    Rsi {
+        periods: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
     SmaOnRsi {
         periods: u32,
         smoothing_length: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
     },
     StochRsi {
         periods: u32,
         k_smoothing: Option<u32>,
         d_smoothing: Option<u32>,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
     },
     Adx {
         periods: u32,
         di_length: u32,
     },
     Atr(u32),
-    Ema(u32),
-    Dema(u32),
-    Tema(u32),
+    Ema {
+        periods: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
+    Dema {
+        periods: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
+    Tema {
+        periods: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
     Obv,
     VwapDeviation(u32),
     Cci(u32),
@@ -92,56 +529,530 @@ pub enum IndicatorKind {
     EmaCross {
         short: u32,
         long: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
     },
     Macd {
         fast: u32,
         slow: u32,
         signal: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
+    Sma {
+        periods: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
+    Roc {
+        periods: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
     },
-    Sma(u32),
-    Roc(u32),
     BollingerBands {
         periods: u32,
         std_multiplier_x100: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
     },
     VolMa(u32),
+    HistVolatility {
+        periods: u32,
+        #[serde(default)]
+        calendar: Calendar,
+        #[serde(default)]
+        interval_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
+    Cvd,
+    VolumeDelta,
+    Aroon(u32),
+    Vortex(u32),
+    Choppiness(u32),
+    Trix {
+        periods: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
+    Ppo {
+        fast: u32,
+        slow: u32,
+        signal: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
+    Apo {
+        fast: u32,
+        slow: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
+    AwesomeOscillator {
+        fast: u32,
+        slow: u32,
+    },
+    AcceleratorOscillator {
+        fast: u32,
+        slow: u32,
+        signal: u32,
+    },
+    UltimateOscillator {
+        short: u32,
+        mid: u32,
+        long: u32,
+    },
+    ElderRay(u32),
+    ForceIndex(u32),
+    EaseOfMovement(u32),
+    Klinger {
+        fast: u32,
+        slow: u32,
+        signal: u32,
+    },
+    VolumeOscillator {
+        fast: u32,
+        slow: u32,
+    },
+    Parkinson {
+        periods: u32,
+        #[serde(default)]
+        calendar: Calendar,
+        #[serde(default)]
+        interval_ms: Option<u64>,
+    },
+    GarmanKlass {
+        periods: u32,
+        #[serde(default)]
+        calendar: Calendar,
+        #[serde(default)]
+        interval_ms: Option<u64>,
+    },
+    RogersSatchell {
+        periods: u32,
+        #[serde(default)]
+        calendar: Calendar,
+        #[serde(default)]
+        interval_ms: Option<u64>,
+    },
+    YangZhang {
+        periods: u32,
+        #[serde(default)]
+        calendar: Calendar,
+        #[serde(default)]
+        interval_ms: Option<u64>,
+    },
+    UlcerIndex {
+        periods: u32,
+        #[serde(default, skip_serializing_if = "is_close")]
+        source: Source,
+    },
+    MassIndex {
+        ema: u32,
+        sum: u32,
+    },
+    ChaikinVolatility {
+        ema: u32,
+        roc: u32,
+    },
+    ChandelierExit {
+        periods: u32,
+        multiplier_x100: u32,
+    },
+    AtrTrailingStop {
+        periods: u32,
+        multiplier_x100: u32,
+    },
+    PivotPoints {
+        anchor: PivotAnchor,
+        method: PivotMethod,
+    },
+    Swing {
+        left: u32,
+        right: u32,
+    },
+    MarketStructure {
+        left: u32,
+        right: u32,
+    },
+    ZigZagPercent {
+        percent_x100: u32,
+    },
+    ZigZagAtr {
+        periods: u32,
+        multiplier_x100: u32,
+    },
+}
+
+impl Serialize for IndicatorKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match LegacyKind::from_kind(self) {
+            Some(legacy) => legacy.serialize(serializer),
+            None => IndicatorKind::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IndicatorKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            #[serde(with = "IndicatorKind")]
+            Current(IndicatorKind),
+            Legacy(LegacyKind),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Current(kind) => Ok(kind),
+            Repr::Legacy(kind) => Ok(kind.into()),
+        }
+    }
+}
+
+/// Bare-period forms of variants that have since gained fields, e.g.
+/// `{"histVolatility":20}`. They read as the default configuration, and the
+/// default configuration is still written in this form.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum LegacyKind {
+    Rsi(u32),
+    Ema(u32),
+    Dema(u32),
+    Tema(u32),
+    Sma(u32),
+    Roc(u32),
     HistVolatility(u32),
+    Trix(u32),
+    UlcerIndex(u32),
+    Parkinson(u32),
+    GarmanKlass(u32),
+    RogersSatchell(u32),
+    YangZhang(u32),
+}
+
+impl LegacyKind {
+    fn from_kind(kind: &IndicatorKind) -> Option<Self> {
+        Some(match *kind {
+            IndicatorKind::Rsi {
+                periods,
+                source: Source::Close,
+            } => LegacyKind::Rsi(periods),
+            IndicatorKind::Ema {
+                periods,
+                source: Source::Close,
+            } => LegacyKind::Ema(periods),
+            IndicatorKind::Dema {
+                periods,
+                source: Source::Close,
+            } => LegacyKind::Dema(periods),
+            IndicatorKind::Tema {
+                periods,
+                source: Source::Close,
+            } => LegacyKind::Tema(periods),
+            IndicatorKind::Sma {
+                periods,
+                source: Source::Close,
+            } => LegacyKind::Sma(periods),
+            IndicatorKind::Roc {
+                periods,
+                source: Source::Close,
+            } => LegacyKind::Roc(periods),
+            IndicatorKind::Trix {
+                periods,
+                source: Source::Close,
+            } => LegacyKind::Trix(periods),
+            IndicatorKind::UlcerIndex {
+                periods,
+                source: Source::Close,
+            } => LegacyKind::UlcerIndex(periods),
+            IndicatorKind::HistVolatility {
+                periods,
+                calendar,
+                interval_ms: None,
+                source: Source::Close,
+            } if calendar == Calendar::default() => LegacyKind::HistVolatility(periods),
+            IndicatorKind::Parkinson {
+                periods,
+                calendar,
+                interval_ms: None,
+            } if calendar == Calendar::default() => LegacyKind::Parkinson(periods),
+            IndicatorKind::GarmanKlass {
+                periods,
+                calendar,
+                interval_ms: None,
+            } if calendar == Calendar::default() => LegacyKind::GarmanKlass(periods),
+            IndicatorKind::RogersSatchell {
+                periods,
+                calendar,
+                interval_ms: None,
+            } if calendar == Calendar::default() => LegacyKind::RogersSatchell(periods),
+            IndicatorKind::YangZhang {
+                periods,
+                calendar,
+                interval_ms: None,
+            } if calendar == Calendar::default() => LegacyKind::YangZhang(periods),
+            _ => return None,
+        })
+    }
+}
+
+impl From<LegacyKind> for IndicatorKind {
+    fn from(kind: LegacyKind) -> Self {
+        let source = Source::default();
+        match kind {
+            LegacyKind::Rsi(periods) => IndicatorKind::Rsi { periods, source },
+            LegacyKind::Ema(periods) => IndicatorKind::Ema { periods, source },
+            LegacyKind::Dema(periods) => IndicatorKind::Dema { periods, source },
+            LegacyKind::Tema(periods) => IndicatorKind::Tema { periods, source },
+            LegacyKind::Sma(periods) => IndicatorKind::Sma { periods, source },
+            LegacyKind::Roc(periods) => IndicatorKind::Roc { periods, source },
+            LegacyKind::Trix(periods) => IndicatorKind::Trix { periods, source },
+            LegacyKind::UlcerIndex(periods) => IndicatorKind::UlcerIndex { periods, source },
+            LegacyKind::HistVolatility(periods) => IndicatorKind::HistVolatility {
+                periods,
+                calendar: Calendar::default(),
+                interval_ms: None,
+                source,
+            },
+            LegacyKind::Parkinson(periods) => IndicatorKind::Parkinson {
+                periods,
+                calendar: Calendar::default(),
+                interval_ms: None,
+            },
+            LegacyKind::GarmanKlass(periods) => IndicatorKind::GarmanKlass {
+                periods,
+                calendar: Calendar::default(),
+                interval_ms: None,
+            },
+            LegacyKind::RogersSatchell(periods) => IndicatorKind::RogersSatchell {
+                periods,
+                calendar: Calendar::default(),
+                interval_ms: None,
+            },
+            LegacyKind::YangZhang(periods) => IndicatorKind::YangZhang {
+                periods,
+                calendar: Calendar::default(),
+                interval_ms: None,
+            },
+        }
+    }
+}
+
+/// An [`IndicatorKind`] whose parameters its indicator would reject, as
+/// returned by [`IndicatorKind::validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KindError {
+    key: String,
+    message: String,
 }
 
+impl KindError {
+    /// Key of the rejected kind.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for KindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+impl std::error::Error for KindError {}
+
 impl IndicatorKind {
     pub fn key(&self) -> String {
         match self {
-            IndicatorKind::Rsi(p) => format!("rsi_{}", p),
+            IndicatorKind::Rsi { periods, source } => {
+                format!("rsi_{}{}", periods, source_suffix(*source))
+            }
             IndicatorKind::Atr(p) => format!("atr_{}", p),
-            IndicatorKind::Ema(p) => format!("ema_{}", p),
-            IndicatorKind::Dema(p) => format!("dema_{}", p),
-            IndicatorKind::Tema(p) => format!("tema_{}", p),
+            IndicatorKind::Ema { periods, source } => {
+                format!("ema_{}{}", periods, source_suffix(*source))
+            }
+            IndicatorKind::Dema { periods, source } => {
+                format!("dema_{}{}", periods, source_suffix(*source))
+            }
+            IndicatorKind::Tema { periods, source } => {
+                format!("tema_{}{}", periods, source_suffix(*source))
+            }
             IndicatorKind::Obv => "obv".to_string(),
             IndicatorKind::VwapDeviation(p) => format!("vwapDeviation_{}", p),
             IndicatorKind::Cci(p) => format!("cci_{}", p),
-            IndicatorKind::Sma(p) => format!("sma_{}", p),
-            IndicatorKind::Roc(p) => format!("roc_{}", p),
+            IndicatorKind::Sma { periods, source } => {
+                format!("sma_{}{}", periods, source_suffix(*source))
+            }
+            IndicatorKind::Roc { periods, source } => {
+                format!("roc_{}{}", periods, source_suffix(*source))
+            }
             IndicatorKind::VolMa(p) => format!("volMa_{}", p),
-            IndicatorKind::HistVolatility(p) => format!("histVol_{}", p),
+            IndicatorKind::HistVolatility {
+                periods,
+                calendar,
+                interval_ms,
+                source,
+            } => format!(
+                "histVol_{}{}{}",
+                periods,
+                annualization_suffix(*calendar, *interval_ms),
+                source_suffix(*source)
+            ),
+            IndicatorKind::Parkinson {
+                periods,
+                calendar,
+                interval_ms,
+            } => format!(
+                "parkinson_{}{}",
+                periods,
+                annualization_suffix(*calendar, *interval_ms)
+            ),
+            IndicatorKind::GarmanKlass {
+                periods,
+                calendar,
+                interval_ms,
+            } => format!(
+                "garmanKlass_{}{}",
+                periods,
+                annualization_suffix(*calendar, *interval_ms)
+            ),
+            IndicatorKind::RogersSatchell {
+                periods,
+                calendar,
+                interval_ms,
+            } => format!(
+                "rogersSatchell_{}{}",
+                periods,
+                annualization_suffix(*calendar, *interval_ms)
+            ),
+            IndicatorKind::YangZhang {
+                periods,
+                calendar,
+                interval_ms,
+            } => format!(
+                "yangZhang_{}{}",
+                periods,
+                annualization_suffix(*calendar, *interval_ms)
+            ),
+            IndicatorKind::UlcerIndex { periods, source } => {
+                format!("ulcer_{}{}", periods, source_suffix(*source))
+            }
+            IndicatorKind::MassIndex { ema, sum } => format!("massIndex_{}_{}", ema, sum),
+            IndicatorKind::ChaikinVolatility { ema, roc } => {
+                format!("chaikinVol_{}_{}", ema, roc)
+            }
+            IndicatorKind::ChandelierExit {
+                periods,
+                multiplier_x100,
+            } => format!(
+                "chandelier_{}_{}",
+                periods,
+                format_multiplier_x100(*multiplier_x100)
+            ),
+            IndicatorKind::AtrTrailingStop {
+                periods,
+                multiplier_x100,
+            } => format!(
+                "atrStop_{}_{}",
+                periods,
+                format_multiplier_x100(*multiplier_x100)
+            ),
+            IndicatorKind::PivotPoints { anchor, method } => {
+                format!("pivots_{}_{}", anchor.key(), method.key())
+            }
+            IndicatorKind::Swing { left, right } => format!("swing_{}_{}", left, right),
+            IndicatorKind::MarketStructure { left, right } => {
+                format!("structure_{}_{}", left, right)
+            }
+            IndicatorKind::ZigZagPercent { percent_x100 } => {
+                format!("zigzag_{}pct", format_multiplier_x100(*percent_x100))
+            }
+            IndicatorKind::ZigZagAtr {
+                periods,
+                multiplier_x100,
+            } => format!(
+                "zigzag_atr_{}_{}",
+                periods,
+                format_multiplier_x100(*multiplier_x100)
+            ),
+            IndicatorKind::Cvd => "cvd".to_string(),
+            IndicatorKind::VolumeDelta => "volumeDelta".to_string(),
+            IndicatorKind::Aroon(p) => format!("aroon_{}", p),
+            IndicatorKind::Vortex(p) => format!("vortex_{}", p),
+            IndicatorKind::Choppiness(p) => format!("chop_{}", p),
+            IndicatorKind::Trix { periods, source } => {
+                format!("trix_{}{}", periods, source_suffix(*source))
+            }
+            IndicatorKind::Ppo {
+                fast,
+                slow,
+                signal,
+                source,
+            } => format!("ppo_{}_{}_{}{}", fast, slow, signal, source_suffix(*source)),
+            IndicatorKind::Apo { fast, slow, source } => {
+                format!("apo_{}_{}{}", fast, slow, source_suffix(*source))
+            }
+            IndicatorKind::AwesomeOscillator { fast, slow } => format!("ao_{}_{}", fast, slow),
+            IndicatorKind::AcceleratorOscillator { fast, slow, signal } => {
+                format!("ac_{}_{}_{}", fast, slow, signal)
+            }
+            IndicatorKind::UltimateOscillator { short, mid, long } => {
+                format!("uo_{}_{}_{}", short, mid, long)
+            }
+            IndicatorKind::ElderRay(p) => format!("elderRay_{}", p),
+            IndicatorKind::ForceIndex(p) => format!("forceIndex_{}", p),
+            IndicatorKind::EaseOfMovement(p) => format!("eom_{}", p),
+            IndicatorKind::Klinger { fast, slow, signal } => {
+                format!("klinger_{}_{}_{}", fast, slow, signal)
+            }
+            IndicatorKind::VolumeOscillator { fast, slow } => {
+                format!("volOsc_{}_{}", fast, slow)
+            }
             IndicatorKind::SmaOnRsi {
                 periods,
                 smoothing_length,
-            } => format!("smaRsi_{}_{}", periods, smoothing_length),
+                source,
+            } => format!(
+                "smaRsi_{}_{}{}",
+                periods,
+                smoothing_length,
+                source_suffix(*source)
+            ),
             IndicatorKind::StochRsi {
                 periods,
                 k_smoothing,
                 d_smoothing,
+                source,
             } => format!(
-                "stochRsi_{}_{}_{}",
+                "stochRsi_{}_{}_{}{}",
                 periods,
                 k_smoothing.unwrap_or(3),
-                d_smoothing.unwrap_or(3)
+                d_smoothing.unwrap_or(3),
+                source_suffix(*source)
             ),
             IndicatorKind::Adx { periods, di_length } => format!("adx_{}_{}", periods, di_length),
-            IndicatorKind::EmaCross { short, long } => format!("emaCross_{}_{}", short, long),
-            IndicatorKind::Macd { fast, slow, signal } => {
-                format!("macd_{}_{}_{}", fast, slow, signal)
-            }
+            IndicatorKind::EmaCross {
+                short,
+                long,
+                source,
+            } => format!("emaCross_{}_{}{}", short, long, source_suffix(*source)),
+            IndicatorKind::Macd {
+                fast,
+                slow,
+                signal,
+                source,
+            } => format!(
+                "macd_{}_{}_{}{}",
+                fast,
+                slow,
+                signal,
+                source_suffix(*source)
+            ),
             IndicatorKind::Ichimoku {
                 tenkan,
                 kijun,
@@ -150,13 +1061,432 @@ impl IndicatorKind {
             IndicatorKind::BollingerBands {
                 periods,
                 std_multiplier_x100,
+                source,
             } => format!(
-                "bollinger_{}_{}",
+                "bollinger_{}_{}{}",
                 periods,
-                format_multiplier_x100(*std_multiplier_x100)
+                format_multiplier_x100(*std_multiplier_x100),
+                source_suffix(*source)
             ),
         }
     }
+
+    /// Checks the parameters against the constraints the indicator's
+    /// constructor asserts, so kinds read from config can be rejected
+    /// instead of panicking in [`IndicatorKind::build`].
+    pub fn validate(&self) -> Result<(), KindError> {
+        let invalid = |message: String| {
+            Err(KindError {
+                key: self.key(),
+                message,
+            })
+        };
+
+        for (name, value, min) in self.bounds() {
+            if value < min {
+                return invalid(format!("{} must be >= {}, got {}", name, min, value));
+            }
+        }
+        match *self {
+            IndicatorKind::StochRsi {
+                k_smoothing: Some(0),
+                ..
+            } => invalid("k_smoothing must be >= 1, got 0".to_string()),
+            IndicatorKind::StochRsi {
+                d_smoothing: Some(0),
+                ..
+            } => invalid("d_smoothing must be >= 1, got 0".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// `(parameter, value, minimum)` for every integer parameter.
+    fn bounds(&self) -> Vec<(&'static str, u32, u32)> {
+        match *self {
+            IndicatorKind::Sma { periods: p, .. }
+            | IndicatorKind::Ema { periods: p, .. }
+            | IndicatorKind::Dema { periods: p, .. }
+            | IndicatorKind::Tema { periods: p, .. }
+            | IndicatorKind::Rsi { periods: p, .. }
+            | IndicatorKind::StochRsi { periods: p, .. }
+            | IndicatorKind::Trix { periods: p, .. }
+            | IndicatorKind::HistVolatility { periods: p, .. }
+            | IndicatorKind::Parkinson { periods: p, .. }
+            | IndicatorKind::GarmanKlass { periods: p, .. }
+            | IndicatorKind::RogersSatchell { periods: p, .. }
+            | IndicatorKind::YangZhang { periods: p, .. } => vec![("periods", p, 2)],
+            IndicatorKind::BollingerBands {
+                periods,
+                std_multiplier_x100,
+                ..
+            } => vec![
+                ("periods", periods, 2),
+                ("std_multiplier_x100", std_multiplier_x100, 1),
+            ],
+            IndicatorKind::VwapDeviation(p)
+            | IndicatorKind::Cci(p)
+            | IndicatorKind::VolMa(p)
+            | IndicatorKind::Vortex(p)
+            | IndicatorKind::Choppiness(p)
+            | IndicatorKind::ElderRay(p)
+            | IndicatorKind::ForceIndex(p) => vec![("periods", p, 2)],
+            IndicatorKind::Roc { periods: p, .. }
+            | IndicatorKind::UlcerIndex { periods: p, .. } => {
+                vec![("periods", p, 1)]
+            }
+            IndicatorKind::ChandelierExit {
+                periods,
+                multiplier_x100,
+            }
+            | IndicatorKind::AtrTrailingStop {
+                periods,
+                multiplier_x100,
+            }
+            | IndicatorKind::ZigZagAtr {
+                periods,
+                multiplier_x100,
+            } => vec![
+                ("periods", periods, 1),
+                ("multiplier_x100", multiplier_x100, 1),
+            ],
+            IndicatorKind::Atr(p) | IndicatorKind::Aroon(p) | IndicatorKind::EaseOfMovement(p) => {
+                vec![("periods", p, 1)]
+            }
+            IndicatorKind::SmaOnRsi {
+                periods,
+                smoothing_length,
+                ..
+            } => vec![
+                ("periods", periods, 2),
+                ("smoothing_length", smoothing_length, 2),
+            ],
+            IndicatorKind::Adx { periods, di_length } => {
+                vec![("periods", periods, 1), ("di_length", di_length, 1)]
+            }
+            IndicatorKind::Ichimoku {
+                tenkan,
+                kijun,
+                senkou_b,
+            } => vec![
+                ("tenkan", tenkan, 1),
+                ("kijun", kijun, 1),
+                ("senkou_b", senkou_b, 1),
+            ],
+            IndicatorKind::EmaCross { short, long, .. } => {
+                vec![("short", short, 2), ("long", long, 2)]
+            }
+            IndicatorKind::Apo { fast, slow, .. }
+            | IndicatorKind::AwesomeOscillator { fast, slow }
+            | IndicatorKind::VolumeOscillator { fast, slow } => {
+                vec![("fast", fast, 2), ("slow", slow, 2)]
+            }
+            IndicatorKind::Macd {
+                fast, slow, signal, ..
+            }
+            | IndicatorKind::Ppo {
+                fast, slow, signal, ..
+            }
+            | IndicatorKind::AcceleratorOscillator { fast, slow, signal }
+            | IndicatorKind::Klinger { fast, slow, signal } => {
+                vec![("fast", fast, 2), ("slow", slow, 2), ("signal", signal, 2)]
+            }
+            IndicatorKind::UltimateOscillator { short, mid, long } => {
+                vec![("short", short, 1), ("mid", mid, 1), ("long", long, 1)]
+            }
+            IndicatorKind::MassIndex { ema, sum } => vec![("ema", ema, 2), ("sum", sum, 1)],
+            IndicatorKind::ChaikinVolatility { ema, roc } => {
+                vec![("ema", ema, 2), ("roc", roc, 1)]
+            }
+            IndicatorKind::Swing { left, right }
+            | IndicatorKind::MarketStructure { left, right } => {
+                vec![("left", left, 1), ("right", right, 1)]
+            }
+            IndicatorKind::ZigZagPercent { percent_x100 } => {
+                vec![("percent_x100", percent_x100, 1)]
+            }
+            IndicatorKind::Obv
+            | IndicatorKind::Cvd
+            | IndicatorKind::VolumeDelta
+            | IndicatorKind::PivotPoints { .. } => Vec::new(),
+        }
+    }
+
+    /// Constructs the indicator this kind describes. Panics on parameters
+    /// [`IndicatorKind::validate`] rejects.
+    pub fn build(&self) -> Box<dyn Indicator> {
+        match *self {
+            IndicatorKind::Rsi { periods: p, source } => {
+                Box::new(Rsi::new(p, p, None, None, None).with_source(source))
+            }
+            IndicatorKind::SmaOnRsi {
+                periods,
+                smoothing_length,
+                source,
+            } => Box::new(SmaRsi::new(periods, smoothing_length).with_source(source)),
+            IndicatorKind::StochRsi {
+                periods,
+                k_smoothing,
+                d_smoothing,
+                source,
+            } => {
+                Box::new(StochasticRsi::new(periods, k_smoothing, d_smoothing).with_source(source))
+            }
+            IndicatorKind::Adx { periods, di_length } => Box::new(Adx::new(periods, di_length)),
+            IndicatorKind::Atr(p) => Box::new(Atr::new(p)),
+            IndicatorKind::Ema { periods: p, source } => Box::new(Ema::new(p).with_source(source)),
+            IndicatorKind::Dema { periods: p, source } => {
+                Box::new(Dema::new(p).with_source(source))
+            }
+            IndicatorKind::Tema { periods: p, source } => {
+                Box::new(Tema::new(p).with_source(source))
+            }
+            IndicatorKind::Obv => Box::new(Obv::new()),
+            IndicatorKind::VwapDeviation(p) => Box::new(VwapDeviation::new(p)),
+            IndicatorKind::Cci(p) => Box::new(Cci::new(p)),
+            IndicatorKind::Ichimoku {
+                tenkan,
+                kijun,
+                senkou_b,
+            } => Box::new(Ichimoku::new(tenkan, kijun, senkou_b)),
+            IndicatorKind::EmaCross {
+                short,
+                long,
+                source,
+            } => Box::new(EmaCross::new(short, long).with_source(source)),
+            IndicatorKind::Macd {
+                fast,
+                slow,
+                signal,
+                source,
+            } => Box::new(Macd::new(fast, slow, signal).with_source(source)),
+            IndicatorKind::Sma { periods: p, source } => Box::new(Sma::new(p).with_source(source)),
+            IndicatorKind::Roc { periods: p, source } => Box::new(Roc::new(p).with_source(source)),
+            IndicatorKind::BollingerBands {
+                periods,
+                std_multiplier_x100,
+                source,
+            } => Box::new(
+                BollingerBands::new(periods, std_multiplier_x100 as f64 / 100.0)
+                    .with_source(source),
+            ),
+            IndicatorKind::VolMa(p) => Box::new(VolumeMa::new(p)),
+            IndicatorKind::HistVolatility {
+                periods,
+                calendar,
+                interval_ms,
+                source,
+            } => Box::new(
+                HistVolatility::new(periods)
+                    .with_annualization(annualization(calendar, interval_ms))
+                    .with_source(source),
+            ),
+            IndicatorKind::Cvd => Box::new(Cvd::new()),
+            IndicatorKind::VolumeDelta => Box::new(VolumeDelta::new()),
+            IndicatorKind::Aroon(p) => Box::new(Aroon::new(p)),
+            IndicatorKind::Vortex(p) => Box::new(Vortex::new(p)),
+            IndicatorKind::Choppiness(p) => Box::new(Choppiness::new(p)),
+            IndicatorKind::Trix { periods: p, source } => {
+                Box::new(Trix::new(p).with_source(source))
+            }
+            IndicatorKind::Ppo {
+                fast,
+                slow,
+                signal,
+                source,
+            } => Box::new(Ppo::new(fast, slow, signal).with_source(source)),
+            IndicatorKind::Apo { fast, slow, source } => {
+                Box::new(Apo::new(fast, slow).with_source(source))
+            }
+            IndicatorKind::AwesomeOscillator { fast, slow } => {
+                Box::new(AwesomeOscillator::new(fast, slow))
+            }
+            IndicatorKind::AcceleratorOscillator { fast, slow, signal } => {
+                Box::new(AcceleratorOscillator::new(fast, slow, signal))
+            }
+            IndicatorKind::UltimateOscillator { short, mid, long } => {
+                Box::new(UltimateOscillator::new(short, mid, long))
+            }
+            IndicatorKind::ElderRay(p) => Box::new(ElderRay::new(p)),
+            IndicatorKind::ForceIndex(p) => Box::new(ForceIndex::new(p)),
+            IndicatorKind::EaseOfMovement(p) => Box::new(EaseOfMovement::new(p)),
+            IndicatorKind::Klinger { fast, slow, signal } => {
+                Box::new(Klinger::new(fast, slow, signal))
+            }
+            IndicatorKind::VolumeOscillator { fast, slow } => {
+                Box::new(VolumeOscillator::new(fast, slow))
+            }
+            IndicatorKind::Parkinson {
+                periods,
+                calendar,
+                interval_ms,
+            } => Box::new(
+                Parkinson::new(periods).with_annualization(annualization(calendar, interval_ms)),
+            ),
+            IndicatorKind::GarmanKlass {
+                periods,
+                calendar,
+                interval_ms,
+            } => Box::new(
+                GarmanKlass::new(periods).with_annualization(annualization(calendar, interval_ms)),
+            ),
+            IndicatorKind::RogersSatchell {
+                periods,
+                calendar,
+                interval_ms,
+            } => Box::new(
+                RogersSatchell::new(periods)
+                    .with_annualization(annualization(calendar, interval_ms)),
+            ),
+            IndicatorKind::YangZhang {
+                periods,
+                calendar,
+                interval_ms,
+            } => Box::new(
+                YangZhang::new(periods).with_annualization(annualization(calendar, interval_ms)),
+            ),
+            IndicatorKind::UlcerIndex { periods: p, source } => {
+                Box::new(UlcerIndex::new(p).with_source(source))
+            }
+            IndicatorKind::MassIndex { ema, sum } => Box::new(MassIndex::new(ema, sum)),
+            IndicatorKind::ChaikinVolatility { ema, roc } => {
+                Box::new(ChaikinVolatility::new(ema, roc))
+            }
+            IndicatorKind::ChandelierExit {
+                periods,
+                multiplier_x100,
+            } => Box::new(ChandelierExit::new(periods, multiplier_x100 as f64 / 100.0)),
+            IndicatorKind::AtrTrailingStop {
+                periods,
+                multiplier_x100,
+            } => Box::new(AtrTrailingStop::new(
+                periods,
+                multiplier_x100 as f64 / 100.0,
+            )),
+            IndicatorKind::PivotPoints { anchor, method } => {
+                Box::new(PivotPoints::new(anchor, method))
+            }
+            IndicatorKind::Swing { left, right } => Box::new(SwingDetector::new(left, right)),
+            IndicatorKind::MarketStructure { left, right } => {
+                Box::new(MarketStructure::new(left, right))
+            }
+            IndicatorKind::ZigZagPercent { percent_x100 } => Box::new(ZigZag::new(
+                ZigZagThreshold::Percent(percent_x100 as f64 / 100.0),
+            )),
+            IndicatorKind::ZigZagAtr {
+                periods,
+                multiplier_x100,
+            } => Box::new(ZigZag::new(ZigZagThreshold::Atr {
+                periods,
+                multiplier: multiplier_x100 as f64 / 100.0,
+            })),
+        }
+    }
+
+    /// Closed candles to fetch before the indicator this kind describes is
+    /// ready; see [`Indicator::warmup_bars`]. Computed from the parameters,
+    /// so it agrees with the built indicator without allocating one.
+    pub fn warmup_bars(&self) -> u32 {
+        match *self {
+            IndicatorKind::Sma { periods: p, .. }
+            | IndicatorKind::Ema { periods: p, .. }
+            | IndicatorKind::Atr(p)
+            | IndicatorKind::VwapDeviation(p)
+            | IndicatorKind::Cci(p)
+            | IndicatorKind::VolMa(p)
+            | IndicatorKind::Choppiness(p)
+            | IndicatorKind::ElderRay(p)
+            | IndicatorKind::Parkinson { periods: p, .. }
+            | IndicatorKind::GarmanKlass { periods: p, .. }
+            | IndicatorKind::RogersSatchell { periods: p, .. }
+            | IndicatorKind::BollingerBands { periods: p, .. }
+            | IndicatorKind::ChandelierExit { periods: p, .. }
+            | IndicatorKind::AtrTrailingStop { periods: p, .. } => p,
+            IndicatorKind::Rsi { periods: p, .. }
+            | IndicatorKind::Roc { periods: p, .. }
+            | IndicatorKind::HistVolatility { periods: p, .. }
+            | IndicatorKind::YangZhang { periods: p, .. }
+            | IndicatorKind::Aroon(p)
+            | IndicatorKind::Vortex(p)
+            | IndicatorKind::ForceIndex(p)
+            | IndicatorKind::EaseOfMovement(p) => p + 1,
+            IndicatorKind::SmaOnRsi {
+                periods,
+                smoothing_length,
+                ..
+            } => periods + smoothing_length,
+            IndicatorKind::StochRsi {
+                periods,
+                k_smoothing,
+                d_smoothing,
+                ..
+            } => 2 * periods + k_smoothing.unwrap_or(3) + d_smoothing.unwrap_or(3) - 2,
+            IndicatorKind::Adx { periods, .. } => periods + 2,
+            IndicatorKind::Dema { periods: p, .. }
+            | IndicatorKind::UlcerIndex { periods: p, .. } => 2 * p - 1,
+            IndicatorKind::Tema { periods: p, .. } => 3 * p - 2,
+            IndicatorKind::Trix { periods: p, .. } => 3 * p - 1,
+            IndicatorKind::Obv | IndicatorKind::Cvd | IndicatorKind::VolumeDelta => 1,
+            IndicatorKind::Ichimoku {
+                tenkan,
+                kijun,
+                senkou_b,
+            } => tenkan.max(kijun).max(senkou_b),
+            IndicatorKind::EmaCross { short, long, .. } => short.max(long),
+            IndicatorKind::Apo { fast, slow, .. }
+            | IndicatorKind::AwesomeOscillator { fast, slow }
+            | IndicatorKind::VolumeOscillator { fast, slow } => fast.max(slow),
+            IndicatorKind::Macd {
+                fast, slow, signal, ..
+            }
+            | IndicatorKind::Ppo {
+                fast, slow, signal, ..
+            }
+            | IndicatorKind::AcceleratorOscillator { fast, slow, signal } => {
+                fast.max(slow) + signal - 1
+            }
+            IndicatorKind::Klinger { fast, slow, signal } => fast.max(slow) + signal,
+            IndicatorKind::UltimateOscillator { short, mid, long } => short.max(mid).max(long) + 1,
+            IndicatorKind::MassIndex { ema, sum } => 2 * ema + sum - 2,
+            IndicatorKind::ChaikinVolatility { ema, roc } => ema + roc,
+            IndicatorKind::Swing { left, right }
+            | IndicatorKind::MarketStructure { left, right } => left + right + 1,
+            // Lower bounds: these also wait on the data itself.
+            IndicatorKind::PivotPoints { .. } | IndicatorKind::ZigZagPercent { .. } => 2,
+            IndicatorKind::ZigZagAtr { periods, .. } => periods.max(2),
+        }
+    }
+}
+
+fn annualization(calendar: Calendar, interval_ms: Option<u64>) -> Annualization {
+    let annualization = Annualization::new(calendar);
+    match interval_ms {
+        Some(ms) => annualization.with_interval_ms(ms),
+        None => annualization,
+    }
+}
+
+/// Key suffix for the annualization of the volatility kinds: `_{calendar}`
+/// then the interval or `auto`. The default crypto calendar with an inferred
+/// interval adds nothing.
+fn annualization_suffix(calendar: Calendar, interval_ms: Option<u64>) -> String {
+    match interval_ms {
+        None if calendar == Calendar::default() => String::new(),
+        Some(ms) => format!("_{}_{}", calendar.key(), ms),
+        None => format!("_{}_auto", calendar.key()),
+    }
+}
+
+/// Key suffix for a price source. `close` adds nothing, so keys read as they
+/// did before sources were configurable.
+fn source_suffix(source: Source) -> String {
+    match source {
+        Source::Close => String::new(),
+        source => format!("_{}", source.key()),
+    }
+}
+
+fn is_close(source: &Source) -> bool {
+    *source == Source::Close
 }
 
 fn format_multiplier_x100(std_multiplier_x100: u32) -> String {
@@ -171,3 +1501,470 @@ fn format_multiplier_x100(std_multiplier_x100: u32) -> String {
         format!("{}.{:02}", whole, fraction)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(n: usize) -> Vec<Price> {
+        (0..n)
+            .map(|i| {
+                // A slow wave plus alternating noise, so short oscillators
+                // never sit flat at an extreme.
+                let wave = |x: f64| 100.0 + 10.0 * (x * 0.3).sin() + x * 0.05;
+                let noise = if i % 2 == 0 { 0.8 } else { -0.8 };
+                let close = wave(i as f64) + noise;
+                let open = wave(i as f64 - 1.0) - noise;
+                Price {
+                    open,
+                    high: close.max(open) + 1.0 + (i % 3) as f64,
+                    low: close.min(open) - 1.0 - (i % 2) as f64,
+                    close,
+                    open_time: i as u64 * 60_000,
+                    close_time: i as u64 * 60_000 + 59_999,
+                    vlm: 100.0 + (i % 7) as f64 * 10.0,
+                }
+            })
+            .collect()
+    }
+
+    fn ready_at(kind: &IndicatorKind, prices: &[Price]) -> Option<u32> {
+        let mut indicator = kind.build();
+        prices
+            .iter()
+            .position(|price| {
+                indicator.update_after_close(*price);
+                indicator.is_ready()
+            })
+            .map(|i| i as u32 + 1)
+    }
+
+    fn kinds(p: u32) -> Vec<IndicatorKind> {
+        let source = Source::Close;
+        vec![
+            IndicatorKind::Rsi { periods: p, source },
+            IndicatorKind::SmaOnRsi {
+                periods: p,
+                smoothing_length: 3,
+                source,
+            },
+            IndicatorKind::StochRsi {
+                periods: p,
+                k_smoothing: Some(4),
+                d_smoothing: None,
+                source,
+            },
+            IndicatorKind::Adx {
+                periods: p,
+                di_length: p + 3,
+            },
+            IndicatorKind::Atr(p),
+            IndicatorKind::Ema { periods: p, source },
+            IndicatorKind::Dema { periods: p, source },
+            IndicatorKind::Tema { periods: p, source },
+            IndicatorKind::Obv,
+            IndicatorKind::VwapDeviation(p),
+            IndicatorKind::Cci(p),
+            IndicatorKind::Ichimoku {
+                tenkan: p,
+                kijun: 2 * p,
+                senkou_b: p + 1,
+            },
+            IndicatorKind::EmaCross {
+                short: p,
+                long: 2 * p,
+                source,
+            },
+            IndicatorKind::Macd {
+                fast: p,
+                slow: 2 * p,
+                signal: 3,
+                source,
+            },
+            IndicatorKind::Sma { periods: p, source },
+            IndicatorKind::Roc { periods: p, source },
+            IndicatorKind::BollingerBands {
+                periods: p,
+                std_multiplier_x100: 200,
+                source,
+            },
+            IndicatorKind::VolMa(p),
+            IndicatorKind::HistVolatility {
+                periods: p,
+                calendar: Calendar::Crypto,
+                interval_ms: None,
+                source,
+            },
+            IndicatorKind::Cvd,
+            IndicatorKind::VolumeDelta,
+            IndicatorKind::Aroon(p),
+            IndicatorKind::Vortex(p),
+            IndicatorKind::Choppiness(p),
+            IndicatorKind::Trix { periods: p, source },
+            IndicatorKind::Ppo {
+                fast: p,
+                slow: 2 * p,
+                signal: 3,
+                source,
+            },
+            IndicatorKind::Apo {
+                fast: p,
+                slow: 2 * p,
+                source,
+            },
+            IndicatorKind::AwesomeOscillator {
+                fast: p,
+                slow: 2 * p,
+            },
+            IndicatorKind::AcceleratorOscillator {
+                fast: p,
+                slow: 2 * p,
+                signal: 3,
+            },
+            IndicatorKind::UltimateOscillator {
+                short: p,
+                mid: 2 * p,
+                long: 3 * p,
+            },
+            IndicatorKind::ElderRay(p),
+            IndicatorKind::ForceIndex(p),
+            IndicatorKind::EaseOfMovement(p),
+            IndicatorKind::Klinger {
+                fast: p,
+                slow: 2 * p,
+                signal: 3,
+            },
+            IndicatorKind::VolumeOscillator {
+                fast: p,
+                slow: 2 * p,
+            },
+            IndicatorKind::Parkinson {
+                periods: p,
+                calendar: Calendar::Crypto,
+                interval_ms: None,
+            },
+            IndicatorKind::GarmanKlass {
+                periods: p,
+                calendar: Calendar::Crypto,
+                interval_ms: None,
+            },
+            IndicatorKind::RogersSatchell {
+                periods: p,
+                calendar: Calendar::Crypto,
+                interval_ms: None,
+            },
+            IndicatorKind::YangZhang {
+                periods: p,
+                calendar: Calendar::Crypto,
+                interval_ms: None,
+            },
+            IndicatorKind::UlcerIndex { periods: p, source },
+            IndicatorKind::MassIndex { ema: p, sum: p + 2 },
+            IndicatorKind::ChaikinVolatility { ema: p, roc: p + 2 },
+            IndicatorKind::ChandelierExit {
+                periods: p,
+                multiplier_x100: 300,
+            },
+            IndicatorKind::AtrTrailingStop {
+                periods: p,
+                multiplier_x100: 300,
+            },
+            IndicatorKind::Swing { left: p, right: 2 },
+            IndicatorKind::MarketStructure { left: p, right: 2 },
+        ]
+    }
+
+    #[test]
+    fn warmup_bars_is_the_bar_that_turns_ready() {
+        let prices = series(300);
+
+        for p in [3, 5, 14] {
+            for kind in kinds(p) {
+                assert_eq!(
+                    kind.warmup_bars(),
+                    kind.build().warmup_bars(),
+                    "{}",
+                    kind.key()
+                );
+                assert_eq!(
+                    ready_at(&kind, &prices),
+                    Some(kind.warmup_bars()),
+                    "{}",
+                    kind.key()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn validate_rejects_what_build_would_panic_on() {
+        for kind in kinds(2) {
+            assert_eq!(kind.validate(), Ok(()), "{}", kind.key());
+        }
+        for p in [0, 1] {
+            for kind in kinds(p) {
+                if kind.validate().is_ok() {
+                    kind.build();
+                }
+            }
+        }
+
+        let ema: IndicatorKind = serde_json::from_str(r#"{"ema":{"periods":0}}"#).unwrap();
+        let err = ema.validate().unwrap_err();
+        assert_eq!(err.key(), "ema_0");
+        assert_eq!(err.to_string(), "ema_0: periods must be >= 2, got 0");
+
+        let stoch = IndicatorKind::StochRsi {
+            periods: 14,
+            k_smoothing: Some(0),
+            d_smoothing: None,
+            source: Source::Close,
+        };
+        assert!(stoch.validate().is_err());
+        assert!(
+            IndicatorKind::ZigZagAtr {
+                periods: 14,
+                multiplier_x100: 0,
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn event_driven_warmup_is_a_lower_bound() {
+        let prices = series(3000);
+
+        for kind in [
+            IndicatorKind::PivotPoints {
+                anchor: PivotAnchor::Daily,
+                method: PivotMethod::Classic,
+            },
+            IndicatorKind::ZigZagPercent { percent_x100: 500 },
+            IndicatorKind::ZigZagAtr {
+                periods: 5,
+                multiplier_x100: 200,
+            },
+        ] {
+            let ready = ready_at(&kind, &prices).unwrap();
+            assert!(ready >= kind.warmup_bars(), "{}", kind.key());
+        }
+    }
+
+    #[test]
+    fn hist_volatility_reads_the_bare_period_form() {
+        let default = IndicatorKind::HistVolatility {
+            periods: 20,
+            calendar: Calendar::Crypto,
+            interval_ms: None,
+            source: Source::Close,
+        };
+
+        for json in [
+            r#"{"histVolatility":20}"#,
+            r#"{"histVolatility":{"periods":20}}"#,
+        ] {
+            assert_eq!(
+                serde_json::from_str::<IndicatorKind>(json).unwrap(),
+                default
+            );
+        }
+        assert_eq!(default.key(), "histVol_20");
+
+        let hourly = IndicatorKind::HistVolatility {
+            periods: 20,
+            calendar: Calendar::Equity,
+            interval_ms: Some(3_600_000),
+            source: Source::Close,
+        };
+        let json = serde_json::to_string(&hourly).unwrap();
+        assert_eq!(
+            serde_json::from_str::<IndicatorKind>(&json).unwrap(),
+            hourly
+        );
+        assert_eq!(hourly.key(), "histVol_20_equity_3600000");
+        assert_eq!(
+            serde_json::from_str::<IndicatorKind>(r#""obv""#).unwrap(),
+            IndicatorKind::Obv
+        );
+    }
+
+    #[test]
+    fn bare_period_configs_read_with_close_source() {
+        let cases = [
+            (
+                r#"{"rsi":14}"#,
+                IndicatorKind::Rsi {
+                    periods: 14,
+                    source: Source::Close,
+                },
+            ),
+            (
+                r#"{"ema":20}"#,
+                IndicatorKind::Ema {
+                    periods: 20,
+                    source: Source::Close,
+                },
+            ),
+            (
+                r#"{"dema":20}"#,
+                IndicatorKind::Dema {
+                    periods: 20,
+                    source: Source::Close,
+                },
+            ),
+            (
+                r#"{"tema":20}"#,
+                IndicatorKind::Tema {
+                    periods: 20,
+                    source: Source::Close,
+                },
+            ),
+            (
+                r#"{"sma":50}"#,
+                IndicatorKind::Sma {
+                    periods: 50,
+                    source: Source::Close,
+                },
+            ),
+            (
+                r#"{"roc":10}"#,
+                IndicatorKind::Roc {
+                    periods: 10,
+                    source: Source::Close,
+                },
+            ),
+            (
+                r#"{"trix":15}"#,
+                IndicatorKind::Trix {
+                    periods: 15,
+                    source: Source::Close,
+                },
+            ),
+            (
+                r#"{"ulcerIndex":14}"#,
+                IndicatorKind::UlcerIndex {
+                    periods: 14,
+                    source: Source::Close,
+                },
+            ),
+        ];
+
+        for (json, expected) in cases {
+            let kind: IndicatorKind = serde_json::from_str(json).unwrap();
+            assert_eq!(kind, expected, "{}", json);
+
+            let round_trip = serde_json::to_string(&kind).unwrap();
+            assert_eq!(
+                serde_json::from_str::<IndicatorKind>(&round_trip).unwrap(),
+                kind
+            );
+        }
+
+        let hl2 = IndicatorKind::Ema {
+            periods: 20,
+            source: Source::Hl2,
+        };
+        assert_eq!(
+            serde_json::from_str::<IndicatorKind>(r#"{"ema":{"periods":20,"source":"hl2"}}"#)
+                .unwrap(),
+            hl2
+        );
+        assert!(serde_json::from_str::<IndicatorKind>(r#"{"ema":"20"}"#).is_err());
+    }
+
+    #[test]
+    fn range_estimators_take_calendar_and_interval() {
+        let parkinson = IndicatorKind::Parkinson {
+            periods: 20,
+            calendar: Calendar::Crypto,
+            interval_ms: None,
+        };
+        assert_eq!(
+            serde_json::from_str::<IndicatorKind>(r#"{"parkinson":20}"#).unwrap(),
+            parkinson
+        );
+        assert_eq!(
+            serde_json::to_string(&parkinson).unwrap(),
+            r#"{"parkinson":20}"#
+        );
+        assert_eq!(parkinson.key(), "parkinson_20");
+
+        let hourly = IndicatorKind::YangZhang {
+            periods: 20,
+            calendar: Calendar::Equity,
+            interval_ms: Some(3_600_000),
+        };
+        let json = serde_json::to_string(&hourly).unwrap();
+        assert_eq!(
+            serde_json::from_str::<IndicatorKind>(&json).unwrap(),
+            hourly
+        );
+        assert_eq!(hourly.key(), "yangZhang_20_equity_3600000");
+        assert_eq!(
+            IndicatorKind::GarmanKlass {
+                periods: 20,
+                calendar: Calendar::Equity,
+                interval_ms: None,
+            }
+            .key(),
+            "garmanKlass_20_equity_auto"
+        );
+
+        // Equity annualization on hourly bars differs from the crypto default.
+        let prices = series(30);
+        let last = |kind: IndicatorKind| {
+            let mut indicator = kind.build();
+            indicator.load(&prices);
+            indicator.get_last()
+        };
+        let rogers_satchell = |calendar| IndicatorKind::RogersSatchell {
+            periods: 10,
+            calendar,
+            interval_ms: Some(3_600_000),
+        };
+        assert_ne!(
+            last(rogers_satchell(Calendar::Crypto)),
+            last(rogers_satchell(Calendar::Equity))
+        );
+    }
+
+    #[test]
+    fn close_source_keeps_legacy_keys_and_shape() {
+        let ema = |source| IndicatorKind::Ema {
+            periods: 20,
+            source,
+        };
+        let macd = |source| IndicatorKind::Macd {
+            fast: 12,
+            slow: 26,
+            signal: 9,
+            source,
+        };
+
+        assert_eq!(ema(Source::Close).key(), "ema_20");
+        assert_eq!(ema(Source::Hl2).key(), "ema_20_hl2");
+        assert_eq!(macd(Source::Close).key(), "macd_12_26_9");
+        assert_eq!(macd(Source::Hlc3).key(), "macd_12_26_9_hlc3");
+
+        let json = |kind: IndicatorKind| serde_json::to_string(&kind).unwrap();
+        assert_eq!(json(ema(Source::Close)), r#"{"ema":20}"#);
+        assert_eq!(
+            json(ema(Source::Hl2)),
+            r#"{"ema":{"periods":20,"source":"hl2"}}"#
+        );
+        assert_eq!(
+            json(macd(Source::Close)),
+            r#"{"macd":{"fast":12,"slow":26,"signal":9}}"#
+        );
+        assert_eq!(
+            json(IndicatorKind::HistVolatility {
+                periods: 20,
+                calendar: Calendar::Crypto,
+                interval_ms: None,
+                source: Source::Close,
+            }),
+            r#"{"histVolatility":20}"#
+        );
+    }
+}
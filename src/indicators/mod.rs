@@ -1,13 +1,23 @@
+mod batch;
+mod chain;
+mod divergence;
+mod history;
 mod indicator;
 pub mod momentum;
+mod set;
 pub mod trend;
 mod types;
 pub mod volatility;
 pub mod volume;
 
-pub use indicator::{Indicator, IndicatorKind, Value};
+pub use batch::{Candles, CandlesIter};
+pub use chain::Chain;
+pub use divergence::{Divergence, DivergenceEvent, DivergenceKind, DivergencePivot};
+pub use history::{History, HistoryEntry};
+pub use indicator::{Indicator, IndicatorKind, KindError, Value};
 pub use momentum::*;
+pub use set::IndicatorSet;
 pub use trend::*;
-pub use types::Price;
+pub use types::{Price, Source, Trade, TradeFlow, TradeSide};
 pub use volatility::*;
 pub use volume::*;
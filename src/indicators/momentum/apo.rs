@@ -0,0 +1,156 @@
+use crate::ExpMean;
+use crate::indicators::{Indicator, Price, Source, Value};
+
+#[derive(Clone, Debug)]
+pub struct Apo {
+    slow_period: u32,
+    fast_ema: ExpMean,
+    slow_ema: ExpMean,
+    value: Option<f64>,
+    source: Source,
+}
+
+impl Apo {
+    pub fn new(fast_period: u32, slow_period: u32) -> Self {
+        assert!(
+            fast_period > 1,
+            "APO fast period must be > 1, got {}",
+            fast_period
+        );
+        assert!(
+            slow_period > 1,
+            "APO slow period must be > 1, got {}",
+            slow_period
+        );
+
+        let fast = fast_period.min(slow_period);
+        let slow = fast_period.max(slow_period);
+
+        Self {
+            slow_period: slow,
+            fast_ema: ExpMean::new(fast),
+            slow_ema: ExpMean::new(slow),
+            value: None,
+            source: Source::default(),
+        }
+    }
+
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
+    fn update_value(&mut self) {
+        self.value = match (self.fast_ema.get_last(), self.slow_ema.get_last()) {
+            (Some(fast), Some(slow)) => Some(fast - slow),
+            _ => None,
+        };
+    }
+}
+
+impl Indicator for Apo {
+    fn update_after_close(&mut self, price: Price) {
+        self.fast_ema.update_after_close(self.source.get(&price));
+        self.slow_ema.update_after_close(self.source.get(&price));
+        self.update_value();
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.fast_ema.update_before_close(self.source.get(&price));
+        self.slow_ema.update_before_close(self.source.get(&price));
+        self.update_value();
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::ApoValue)
+    }
+
+    fn reset(&mut self) {
+        self.fast_ema.reset();
+        self.slow_ema.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.slow_period
+    }
+}
+
+impl Default for Apo {
+    fn default() -> Self {
+        Self::new(12, 26)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(close: f64) -> Price {
+        Price {
+            open: close,
+            high: close,
+            low: close,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn apo_is_difference_of_emas() {
+        let mut apo = Apo::new(2, 3);
+
+        apo.update_after_close(p(1.0));
+        apo.update_after_close(p(2.0));
+        assert!(!apo.is_ready());
+
+        apo.update_after_close(p(3.0));
+
+        // fast EMA(2): seed 1.5 -> 2.5, slow EMA(3): seed 2.0
+        match apo.get_last() {
+            Some(Value::ApoValue(value)) => approx_eq(value, 0.5),
+            _ => panic!("missing apo"),
+        }
+    }
+
+    #[test]
+    fn apo_before_close_is_provisional() {
+        let mut apo = Apo::new(2, 3);
+
+        apo.load(&[p(1.0), p(2.0), p(3.0), p(4.0)]);
+
+        let after_close = apo.get_last();
+        apo.update_before_close(p(10.0));
+
+        assert_ne!(after_close, apo.get_last());
+    }
+
+    #[test]
+    fn apo_reset_clears_state() {
+        let mut apo = Apo::new(2, 3);
+
+        apo.load(&[p(1.0), p(2.0), p(3.0)]);
+        assert!(apo.is_ready());
+
+        apo.reset();
+
+        assert!(!apo.is_ready());
+        assert_eq!(apo.get_last(), None);
+    }
+}
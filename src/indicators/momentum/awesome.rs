@@ -0,0 +1,245 @@
+use crate::Mean;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct AwesomeOscillator {
+    fast: Mean,
+    slow: Mean,
+    value: Option<f64>,
+}
+
+impl AwesomeOscillator {
+    pub fn new(fast_period: u32, slow_period: u32) -> Self {
+        assert!(
+            fast_period > 1,
+            "AO fast period must be > 1, got {}",
+            fast_period
+        );
+        assert!(
+            slow_period > 1,
+            "AO slow period must be > 1, got {}",
+            slow_period
+        );
+
+        Self {
+            fast: Mean::new(fast_period.min(slow_period)),
+            slow: Mean::new(fast_period.max(slow_period)),
+            value: None,
+        }
+    }
+
+    #[inline]
+    fn median_price(price: Price) -> f64 {
+        (price.high + price.low) / 2.0
+    }
+
+    fn update_value(&mut self) {
+        self.value = match (self.fast.get_last(), self.slow.get_last()) {
+            (Some(fast), Some(slow)) => Some(fast - slow),
+            _ => None,
+        };
+    }
+}
+
+impl Indicator for AwesomeOscillator {
+    fn update_after_close(&mut self, price: Price) {
+        let median = Self::median_price(price);
+        self.fast.update_after_close(median);
+        self.slow.update_after_close(median);
+        self.update_value();
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        let median = Self::median_price(price);
+        self.fast.update_before_close(median);
+        self.slow.update_before_close(median);
+        self.update_value();
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::AwesomeOscillatorValue)
+    }
+
+    fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.slow.period()
+    }
+}
+
+impl Default for AwesomeOscillator {
+    fn default() -> Self {
+        Self::new(5, 34)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AcceleratorOscillator {
+    ao: AwesomeOscillator,
+    signal: Mean,
+    value: Option<f64>,
+}
+
+impl AcceleratorOscillator {
+    pub fn new(fast_period: u32, slow_period: u32, signal_period: u32) -> Self {
+        assert!(
+            signal_period > 1,
+            "AC signal period must be > 1, got {}",
+            signal_period
+        );
+
+        Self {
+            ao: AwesomeOscillator::new(fast_period, slow_period),
+            signal: Mean::new(signal_period),
+            value: None,
+        }
+    }
+
+    fn update_value(&mut self, ao: f64) {
+        self.value = self.signal.get_last().map(|signal| ao - signal);
+    }
+}
+
+impl Indicator for AcceleratorOscillator {
+    fn update_after_close(&mut self, price: Price) {
+        self.ao.update_after_close(price);
+        if let Some(ao) = self.ao.value {
+            self.signal.update_after_close(ao);
+            self.update_value(ao);
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.ao.update_before_close(price);
+        if let Some(ao) = self.ao.value {
+            self.signal.update_before_close(ao);
+            self.update_value(ao);
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::AcceleratorOscillatorValue)
+    }
+
+    fn reset(&mut self) {
+        self.ao.reset();
+        self.signal.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.ao.period() + self.signal.period() - 1
+    }
+}
+
+impl Default for AcceleratorOscillator {
+    fn default() -> Self {
+        Self::new(5, 34, 5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64) -> Price {
+        Price {
+            open: low,
+            high,
+            low,
+            close: high,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn ao_is_difference_of_median_price_smas() {
+        let mut ao = AwesomeOscillator::new(2, 3);
+
+        ao.update_after_close(p(11.0, 9.0));
+        ao.update_after_close(p(13.0, 11.0));
+        assert!(!ao.is_ready());
+
+        ao.update_after_close(p(15.0, 13.0));
+
+        // medians 10, 12, 14: SMA2 = 13, SMA3 = 12
+        match ao.get_last() {
+            Some(Value::AwesomeOscillatorValue(value)) => approx_eq(value, 1.0),
+            _ => panic!("missing ao"),
+        }
+    }
+
+    #[test]
+    fn ao_before_close_is_provisional() {
+        let mut ao = AwesomeOscillator::new(2, 3);
+
+        ao.load(&[p(11.0, 9.0), p(13.0, 11.0), p(15.0, 13.0)]);
+
+        let after_close = ao.get_last();
+        ao.update_before_close(p(25.0, 23.0));
+
+        assert_ne!(after_close, ao.get_last());
+    }
+
+    #[test]
+    fn ac_subtracts_sma_of_ao() {
+        let mut ac = AcceleratorOscillator::new(2, 3, 2);
+
+        ac.load(&[p(11.0, 9.0), p(13.0, 11.0), p(15.0, 13.0)]);
+        assert!(!ac.is_ready());
+
+        ac.update_after_close(p(19.0, 17.0));
+
+        // medians 12, 14, 18: AO = 16 - 14.666.. = 1.333.., previous AO = 1
+        match ac.get_last() {
+            Some(Value::AcceleratorOscillatorValue(value)) => {
+                approx_eq(value, (4.0 / 3.0) - ((4.0 / 3.0) + 1.0) / 2.0)
+            }
+            _ => panic!("missing ac"),
+        }
+        assert_eq!(ac.period(), 4);
+    }
+
+    #[test]
+    fn ac_reset_clears_state() {
+        let mut ac = AcceleratorOscillator::new(2, 3, 2);
+
+        ac.load(&[p(11.0, 9.0), p(13.0, 11.0), p(15.0, 13.0), p(19.0, 17.0)]);
+        assert!(ac.is_ready());
+
+        ac.reset();
+
+        assert!(!ac.is_ready());
+        assert_eq!(ac.get_last(), None);
+    }
+}
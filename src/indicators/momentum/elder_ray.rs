@@ -0,0 +1,136 @@
+use crate::ExpMean;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct ElderRay {
+    ema: ExpMean,
+    value: Option<(f64, f64)>,
+}
+
+impl ElderRay {
+    pub fn new(periods: u32) -> Self {
+        assert!(
+            periods > 1,
+            "Elder Ray periods must be > 1, got {}",
+            periods
+        );
+        Self {
+            ema: ExpMean::new(periods),
+            value: None,
+        }
+    }
+
+    fn update_value(&mut self, price: Price) {
+        self.value = self
+            .ema
+            .get_last()
+            .map(|ema| (price.high - ema, price.low - ema));
+    }
+}
+
+impl Indicator for ElderRay {
+    fn update_after_close(&mut self, price: Price) {
+        self.ema.update_after_close(price.close);
+        self.update_value(price);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.ema.update_before_close(price.close);
+        self.update_value(price);
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value
+            .map(|(bull, bear)| Value::ElderRayValue { bull, bear })
+    }
+
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.ema.period()
+    }
+}
+
+impl Default for ElderRay {
+    fn default() -> Self {
+        Self::new(13)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open: close,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn elder_ray_measures_extremes_against_ema() {
+        let mut elder = ElderRay::new(3);
+
+        elder.update_after_close(p(11.0, 9.0, 10.0));
+        elder.update_after_close(p(12.0, 10.0, 11.0));
+        assert!(!elder.is_ready());
+
+        elder.update_after_close(p(14.0, 11.0, 12.0));
+
+        match elder.get_last() {
+            Some(Value::ElderRayValue { bull, bear }) => {
+                approx_eq(bull, 3.0);
+                approx_eq(bear, 0.0);
+            }
+            _ => panic!("missing elder ray"),
+        }
+    }
+
+    #[test]
+    fn elder_ray_before_close_is_provisional() {
+        let mut elder = ElderRay::new(3);
+
+        elder.load(&[p(11.0, 9.0, 10.0), p(12.0, 10.0, 11.0), p(14.0, 11.0, 12.0)]);
+
+        let after_close = elder.get_last();
+        elder.update_before_close(p(13.0, 8.0, 9.0));
+
+        assert_ne!(after_close, elder.get_last());
+    }
+
+    #[test]
+    fn elder_ray_reset_clears_state() {
+        let mut elder = ElderRay::new(3);
+
+        elder.load(&[p(11.0, 9.0, 10.0), p(12.0, 10.0, 11.0), p(14.0, 11.0, 12.0)]);
+        assert!(elder.is_ready());
+
+        elder.reset();
+
+        assert!(!elder.is_ready());
+        assert_eq!(elder.get_last(), None);
+    }
+}
@@ -1,32 +1,43 @@
 use crate::ExpMean;
-use crate::indicators::{Indicator, Price, Value};
+use crate::indicators::{Indicator, Price, Source, Value};
 
+/// Fast/slow EMA line with an EMA signal line and histogram, shared by MACD,
+/// PPO and Klinger. `line` turns the fast and slow EMAs into the main line.
 #[derive(Clone, Debug)]
-pub struct Macd {
+pub(crate) struct EmaSpread {
     slow_period: u32,
     fast_ema: ExpMean,
     slow_ema: ExpMean,
     signal_ema: ExpMean,
-    macd: Option<f64>,
-    signal: Option<f64>,
-    histogram: Option<f64>,
+    line: fn(f64, f64) -> Option<f64>,
+    value: Option<(f64, f64, f64)>,
+    source: Source,
 }
 
-impl Macd {
-    pub fn new(fast_period: u32, slow_period: u32, signal_period: u32) -> Self {
+impl EmaSpread {
+    pub fn new(
+        name: &str,
+        fast_period: u32,
+        slow_period: u32,
+        signal_period: u32,
+        line: fn(f64, f64) -> Option<f64>,
+    ) -> Self {
         assert!(
             fast_period > 1,
-            "MACD fast period must be > 1, got {}",
+            "{} fast period must be > 1, got {}",
+            name,
             fast_period
         );
         assert!(
             slow_period > 1,
-            "MACD slow period must be > 1, got {}",
+            "{} slow period must be > 1, got {}",
+            name,
             slow_period
         );
         assert!(
             signal_period > 1,
-            "MACD signal period must be > 1, got {}",
+            "{} signal period must be > 1, got {}",
+            name,
             signal_period
         );
 
@@ -38,54 +49,113 @@ impl Macd {
             fast_ema: ExpMean::new(fast),
             slow_ema: ExpMean::new(slow),
             signal_ema: ExpMean::new(signal_period),
-            macd: None,
-            signal: None,
-            histogram: None,
+            line,
+            value: None,
+            source: Source::default(),
         }
     }
 
-    fn clear_value(&mut self) {
-        self.macd = None;
-        self.signal = None;
-        self.histogram = None;
+    pub fn set_source(&mut self, source: Source) {
+        self.source = source;
     }
 
-    fn update_value(&mut self, macd: f64) {
-        if let Some(signal) = self.signal_ema.get_last() {
-            self.macd = Some(macd);
-            self.signal = Some(signal);
-            self.histogram = Some(macd - signal);
-        } else {
-            self.clear_value();
+    /// `(line, signal, histogram)` once the signal EMA is ready.
+    #[inline]
+    pub fn value(&self) -> Option<(f64, f64, f64)> {
+        self.value
+    }
+
+    fn line(&self) -> Option<f64> {
+        match (self.fast_ema.get_last(), self.slow_ema.get_last()) {
+            (Some(fast), Some(slow)) => (self.line)(fast, slow),
+            _ => None,
         }
     }
-}
 
-impl Indicator for Macd {
-    fn update_after_close(&mut self, price: Price) {
-        self.fast_ema.update_after_close(price.close);
-        self.slow_ema.update_after_close(price.close);
+    fn update_value(&mut self, line: f64) {
+        self.value = self
+            .signal_ema
+            .get_last()
+            .map(|signal| (line, signal, line - signal));
+    }
+
+    pub fn update_after_close(&mut self, price: Price) {
+        self.update_after_close_value(self.source.get(&price));
+    }
 
-        if let (Some(fast), Some(slow)) = (self.fast_ema.get_last(), self.slow_ema.get_last()) {
-            let macd = fast - slow;
-            self.signal_ema.update_after_close(macd);
-            self.update_value(macd);
+    pub fn update_before_close(&mut self, price: Price) {
+        self.update_before_close_value(self.source.get(&price));
+    }
+
+    /// Feeds a closed input that is not read from a price, such as
+    /// Klinger's volume force.
+    pub fn update_after_close_value(&mut self, input: f64) {
+        self.fast_ema.update_after_close(input);
+        self.slow_ema.update_after_close(input);
+
+        if let Some(line) = self.line() {
+            self.signal_ema.update_after_close(line);
+            self.update_value(line);
         } else {
-            self.clear_value();
+            self.value = None;
         }
     }
 
-    fn update_before_close(&mut self, price: Price) {
-        self.fast_ema.update_before_close(price.close);
-        self.slow_ema.update_before_close(price.close);
+    pub fn update_before_close_value(&mut self, input: f64) {
+        self.fast_ema.update_before_close(input);
+        self.slow_ema.update_before_close(input);
+
+        if let Some(line) = self.line() {
+            self.signal_ema.update_before_close(line);
+            self.update_value(line);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.fast_ema.reset();
+        self.slow_ema.reset();
+        self.signal_ema.reset();
+        self.value = None;
+    }
+
+    pub fn period(&self) -> u32 {
+        self.slow_period + self.signal_ema.period() - 1
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Macd {
+    spread: EmaSpread,
+}
 
-        if let (Some(fast), Some(slow)) = (self.fast_ema.get_last(), self.slow_ema.get_last()) {
-            let macd = fast - slow;
-            self.signal_ema.update_before_close(macd);
-            self.update_value(macd);
+impl Macd {
+    pub fn new(fast_period: u32, slow_period: u32, signal_period: u32) -> Self {
+        Self {
+            spread: EmaSpread::new(
+                "MACD",
+                fast_period,
+                slow_period,
+                signal_period,
+                |fast, slow| Some(fast - slow),
+            ),
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.spread.set_source(source);
+        self
+    }
+}
+
+impl Indicator for Macd {
+    fn update_after_close(&mut self, price: Price) {
+        self.spread.update_after_close(price);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.spread.update_before_close(price);
+    }
+
     fn load(&mut self, price_data: &[Price]) {
         for price in price_data {
             self.update_after_close(*price);
@@ -93,29 +163,25 @@ impl Indicator for Macd {
     }
 
     fn is_ready(&self) -> bool {
-        self.macd.is_some() && self.signal.is_some() && self.histogram.is_some()
+        self.spread.value().is_some()
     }
 
     fn get_last(&self) -> Option<Value> {
-        match (self.macd, self.signal, self.histogram) {
-            (Some(macd), Some(signal), Some(histogram)) => Some(Value::MacdValue {
+        self.spread
+            .value()
+            .map(|(macd, signal, histogram)| Value::MacdValue {
                 macd,
                 signal,
                 histogram,
-            }),
-            _ => None,
-        }
+            })
     }
 
     fn reset(&mut self) {
-        self.fast_ema.reset();
-        self.slow_ema.reset();
-        self.signal_ema.reset();
-        self.clear_value();
+        self.spread.reset();
     }
 
     fn period(&self) -> u32 {
-        self.slow_period + self.signal_ema.period() - 1
+        self.spread.period()
     }
 }
 
@@ -217,9 +283,7 @@ mod tests {
 
         assert!(!macd.is_ready());
         assert_eq!(macd.get_last(), None);
-        assert_eq!(macd.macd, None);
-        assert_eq!(macd.signal, None);
-        assert_eq!(macd.histogram, None);
+        assert_eq!(macd.spread.value(), None);
     }
 
     #[test]
@@ -1,11 +1,24 @@
+mod apo;
+mod awesome;
 mod cci;
+mod elder_ray;
 mod macd;
+mod ppo;
 mod roc;
 mod rsi;
 mod stoch_rsi;
+mod trix;
+mod ultimate;
 
+pub use apo::Apo;
+pub use awesome::{AcceleratorOscillator, AwesomeOscillator};
 pub use cci::Cci;
+pub use elder_ray::ElderRay;
+pub(crate) use macd::EmaSpread;
 pub use macd::Macd;
+pub use ppo::Ppo;
 pub use roc::Roc;
 pub use rsi::{Rsi, SmaRsi};
 pub use stoch_rsi::StochasticRsi;
+pub use trix::Trix;
+pub use ultimate::UltimateOscillator;
@@ -0,0 +1,170 @@
+use super::macd::EmaSpread;
+use crate::indicators::{Indicator, Price, Source, Value};
+
+#[derive(Clone, Debug)]
+pub struct Ppo {
+    spread: EmaSpread,
+}
+
+impl Ppo {
+    pub fn new(fast_period: u32, slow_period: u32, signal_period: u32) -> Self {
+        Self {
+            spread: EmaSpread::new(
+                "PPO",
+                fast_period,
+                slow_period,
+                signal_period,
+                |fast, slow| (slow.abs() > f64::EPSILON).then(|| (fast - slow) / slow * 100.0),
+            ),
+        }
+    }
+
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.spread.set_source(source);
+        self
+    }
+}
+
+impl Indicator for Ppo {
+    fn update_after_close(&mut self, price: Price) {
+        self.spread.update_after_close(price);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.spread.update_before_close(price);
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.spread.value().is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.spread
+            .value()
+            .map(|(ppo, signal, histogram)| Value::PpoValue {
+                ppo,
+                signal,
+                histogram,
+            })
+    }
+
+    fn reset(&mut self) {
+        self.spread.reset();
+    }
+
+    fn period(&self) -> u32 {
+        self.spread.period()
+    }
+}
+
+impl Default for Ppo {
+    fn default() -> Self {
+        Self::new(12, 26, 9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Macd;
+
+    fn p(close: f64) -> Price {
+        Price {
+            open: close,
+            high: close,
+            low: close,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn ppo_is_scale_invariant() {
+        let mut small = Ppo::new(3, 5, 3);
+        let mut large = Ppo::new(3, 5, 3);
+
+        for close in [1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 21.0, 34.0] {
+            small.update_after_close(p(close));
+            large.update_after_close(p(close * 1000.0));
+        }
+
+        match (small.get_last(), large.get_last()) {
+            (
+                Some(Value::PpoValue {
+                    ppo: a,
+                    signal: sa,
+                    histogram: ha,
+                }),
+                Some(Value::PpoValue {
+                    ppo: b,
+                    signal: sb,
+                    histogram: hb,
+                }),
+            ) => {
+                approx_eq(a, b);
+                approx_eq(sa, sb);
+                approx_eq(ha, hb);
+            }
+            _ => panic!("missing ppo"),
+        }
+    }
+
+    #[test]
+    fn ppo_is_ready_with_macd() {
+        let mut ppo = Ppo::new(3, 5, 3);
+        let mut macd = Macd::new(3, 5, 3);
+
+        for close in [1.0, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            ppo.update_after_close(p(close));
+            macd.update_after_close(p(close));
+            assert_eq!(ppo.is_ready(), macd.is_ready());
+        }
+
+        ppo.update_after_close(p(7.0));
+        macd.update_after_close(p(7.0));
+
+        assert!(ppo.is_ready());
+        assert_eq!(ppo.period(), macd.period());
+    }
+
+    #[test]
+    fn ppo_before_close_is_provisional() {
+        let mut ppo = Ppo::new(3, 5, 3);
+
+        for close in [1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 21.0, 34.0] {
+            ppo.update_after_close(p(close));
+        }
+
+        let after_close = ppo.get_last();
+        ppo.update_before_close(p(55.0));
+
+        assert_ne!(after_close, ppo.get_last());
+    }
+
+    #[test]
+    fn ppo_reset_clears_state() {
+        let mut ppo = Ppo::new(3, 5, 3);
+
+        for close in [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0] {
+            ppo.update_after_close(p(close));
+        }
+        assert!(ppo.is_ready());
+
+        ppo.reset();
+
+        assert!(!ppo.is_ready());
+        assert_eq!(ppo.get_last(), None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::indicators::{Indicator, Price, Value};
+use crate::indicators::{Indicator, Price, Source, Value};
 use std::collections::VecDeque;
 
 #[derive(Clone, Debug)]
@@ -7,6 +7,7 @@ pub struct Roc {
     closes: VecDeque<f64>,
     value: Option<f64>,
     in_candle: bool,
+    source: Source,
 }
 
 impl Roc {
@@ -17,9 +18,15 @@ impl Roc {
             closes: VecDeque::with_capacity(periods as usize + 1),
             value: None,
             in_candle: true,
+            source: Source::default(),
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
     fn compute(&mut self) {
         if self.closes.len() != self.periods as usize + 1 {
             self.value = None;
@@ -47,7 +54,7 @@ impl Indicator for Roc {
             }
         }
 
-        self.closes.push_back(price.close);
+        self.closes.push_back(self.source.get(&price));
         self.compute();
         self.in_candle = true;
     }
@@ -64,7 +71,7 @@ impl Indicator for Roc {
             self.closes.pop_back();
         }
 
-        self.closes.push_back(price.close);
+        self.closes.push_back(self.source.get(&price));
         self.compute();
     }
 
@@ -91,6 +98,10 @@ impl Indicator for Roc {
     fn period(&self) -> u32 {
         self.periods
     }
+
+    fn warmup_bars(&self) -> u32 {
+        self.periods + 1
+    }
 }
 
 impl Default for Roc {
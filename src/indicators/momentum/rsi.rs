@@ -1,6 +1,8 @@
 use super::stoch_rsi::StochBuffer;
 use crate::indicators::Price;
-use crate::indicators::{Indicator, Value};
+use crate::indicators::batch::{ColumnReader, fill_columns};
+use crate::indicators::{Indicator, Source, Value};
+use std::borrow::Borrow;
 use std::collections::VecDeque;
 
 #[derive(Clone, Debug)]
@@ -11,6 +13,7 @@ pub struct Rsi {
     value: Option<f64>,
     sma: Option<SmaOnRsi>,
     stoch: StochBuffer,
+    source: Source,
 }
 
 #[derive(Clone, Debug)]
@@ -97,9 +100,15 @@ impl Rsi {
                 k_smoothing.unwrap_or(3),
                 d_smoothing.unwrap_or(3),
             ),
+            source: Source::default(),
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
     fn calc_rsi(
         &mut self,
         change: f64,
@@ -152,6 +161,16 @@ impl Rsi {
         false
     }
 
+    /// Bars until the smoothed RSI is ready, if smoothing is configured.
+    pub fn sma_warmup_bars(&self) -> Option<u32> {
+        self.sma.as_ref().map(|sma| self.periods + sma.length)
+    }
+
+    /// Bars until both stochastic lines are ready.
+    pub fn stoch_warmup_bars(&self) -> u32 {
+        self.periods + self.stoch.warmup_values()
+    }
+
     pub fn stoch_is_ready(&self) -> bool {
         self.stoch.is_ready()
     }
@@ -167,7 +186,7 @@ impl Rsi {
 
 impl Indicator for Rsi {
     fn update_before_close(&mut self, price: Price) {
-        let price = price.close;
+        let price = self.source.get(&price);
 
         let change = match self.last_price {
             Some(prev_price) => price - prev_price,
@@ -188,7 +207,7 @@ impl Indicator for Rsi {
     }
 
     fn update_after_close(&mut self, price: Price) {
-        let price = price.close;
+        let price = self.source.get(&price);
         let change = match self.last_price {
             Some(prev_price) => price - prev_price,
             None => {
@@ -214,6 +233,23 @@ impl Indicator for Rsi {
         None
     }
 
+    fn compute_columns<P>(&mut self, price_data: P, fields: &[&str]) -> Vec<Vec<f64>>
+    where
+        P: IntoIterator,
+        P::Item: Borrow<Price>,
+    {
+        let readers: Vec<ColumnReader<Self>> = fields
+            .iter()
+            .map(|field| -> ColumnReader<Self> {
+                match *field {
+                    "value" => |rsi| rsi.value,
+                    _ => |_| None,
+                }
+            })
+            .collect();
+        fill_columns(self, price_data, &readers)
+    }
+
     fn is_ready(&self) -> bool {
         self.buff.is_full() && self.value.is_some()
     }
@@ -237,6 +273,10 @@ impl Indicator for Rsi {
     fn period(&self) -> u32 {
         self.periods
     }
+
+    fn warmup_bars(&self) -> u32 {
+        self.periods + 1
+    }
 }
 
 impl RsiBuffer {
@@ -330,6 +370,7 @@ impl Default for Rsi {
             value: None,
             sma: Some(SmaOnRsi::new(10)),
             stoch: StochBuffer::new(14, 3, 3),
+            source: Source::default(),
         }
     }
 }
@@ -347,6 +388,11 @@ impl SmaRsi {
             rsi: Rsi::new(periods, 4, None, None, Some(smoothing)),
         }
     }
+
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.rsi = self.rsi.with_source(source);
+        self
+    }
 }
 
 impl Indicator for SmaRsi {
@@ -379,4 +425,8 @@ impl Indicator for SmaRsi {
     fn period(&self) -> u32 {
         self.periods
     }
+
+    fn warmup_bars(&self) -> u32 {
+        self.rsi.sma_warmup_bars().unwrap_or(self.periods + 1)
+    }
 }
@@ -1,5 +1,5 @@
 use super::rsi::Rsi;
-use crate::indicators::{Indicator, Price, Value};
+use crate::indicators::{Indicator, Price, Source, Value};
 use std::collections::VecDeque;
 fn is_same(a: f64, b: f64) -> bool {
     (a - b).abs() < f64::EPSILON
@@ -18,6 +18,11 @@ impl StochasticRsi {
             rsi: Rsi::new(periods, periods, k_smoothing, d_smoothing, None),
         }
     }
+
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.rsi = self.rsi.with_source(source);
+        self
+    }
 }
 
 impl Indicator for StochasticRsi {
@@ -50,6 +55,10 @@ impl Indicator for StochasticRsi {
     fn period(&self) -> u32 {
         self.periods
     }
+
+    fn warmup_bars(&self) -> u32 {
+        self.rsi.stoch_warmup_bars()
+    }
 }
 
 impl Default for StochasticRsi {
@@ -296,6 +305,12 @@ impl StochBuffer {
         self.k_value.is_some() && self.d_value.is_some()
     }
 
+    /// RSI values needed before `is_ready`.
+    pub fn warmup_values(&self) -> u32 {
+        self.length + self.k_smoothing_buffer.capacity() as u32 + self.d_buffer.capacity() as u32
+            - 2
+    }
+
     pub fn reset(&mut self) {
         self.buffer.clear();
         self.min_buffer.clear();
@@ -0,0 +1,171 @@
+use crate::ExpMean;
+use crate::indicators::{Indicator, Price, Source, Value};
+
+#[derive(Clone, Debug)]
+pub struct Trix {
+    periods: u32,
+    ema1: ExpMean,
+    ema2: ExpMean,
+    ema3: ExpMean,
+    value: Option<f64>,
+    source: Source,
+}
+
+impl Trix {
+    pub fn new(periods: u32) -> Self {
+        assert!(periods > 1, "TRIX period must be > 1, got {}", periods);
+        Self {
+            periods,
+            ema1: ExpMean::new(periods),
+            ema2: ExpMean::new(periods),
+            ema3: ExpMean::new(periods),
+            value: None,
+            source: Source::default(),
+        }
+    }
+
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
+    fn update_value(&mut self, prev_ema3: Option<f64>) {
+        self.value = match (prev_ema3, self.ema3.get_last()) {
+            (Some(prev), Some(ema3)) if prev.abs() > f64::EPSILON => {
+                Some(((ema3 / prev) - 1.0) * 100.0)
+            }
+            _ => None,
+        };
+    }
+}
+
+impl Indicator for Trix {
+    fn update_after_close(&mut self, price: Price) {
+        let prev_ema3 = self.ema3.get_confirmed();
+
+        self.ema1.update_after_close(self.source.get(&price));
+        if let Some(ema1) = self.ema1.get_last() {
+            self.ema2.update_after_close(ema1);
+        }
+        if let Some(ema2) = self.ema2.get_last() {
+            self.ema3.update_after_close(ema2);
+        }
+        self.update_value(prev_ema3);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        let prev_ema3 = self.ema3.get_confirmed();
+
+        self.ema1.update_before_close(self.source.get(&price));
+        if let Some(ema1) = self.ema1.get_last() {
+            self.ema2.update_before_close(ema1);
+        }
+        if let Some(ema2) = self.ema2.get_last() {
+            self.ema3.update_before_close(ema2);
+        }
+        self.update_value(prev_ema3);
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::TrixValue)
+    }
+
+    fn reset(&mut self) {
+        self.ema1.reset();
+        self.ema2.reset();
+        self.ema3.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        3 * self.periods - 1
+    }
+}
+
+impl Default for Trix {
+    fn default() -> Self {
+        Self::new(15)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(close: f64) -> Price {
+        Price {
+            open: close,
+            high: close,
+            low: close,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn trix_needs_one_bar_past_triple_ema_seed() {
+        let mut trix = Trix::new(3);
+
+        for close in [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0] {
+            trix.update_after_close(p(close));
+        }
+        assert!(!trix.is_ready());
+
+        trix.update_after_close(p(8.0));
+
+        // A linear series lags by 3 bars through the triple EMA: 4.0 -> 5.0
+        match trix.get_last() {
+            Some(Value::TrixValue(value)) => approx_eq(value, 25.0),
+            _ => panic!("missing trix"),
+        }
+    }
+
+    #[test]
+    fn trix_before_close_is_provisional() {
+        let mut trix = Trix::new(3);
+
+        for close in [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0] {
+            trix.update_after_close(p(close));
+        }
+
+        let after_close = trix.get_last();
+        trix.update_before_close(p(15.0));
+
+        assert_ne!(after_close, trix.get_last());
+    }
+
+    #[test]
+    fn trix_reset_clears_state() {
+        let mut trix = Trix::new(3);
+
+        for close in [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0] {
+            trix.update_after_close(p(close));
+        }
+        assert!(trix.is_ready());
+
+        trix.reset();
+
+        assert!(!trix.is_ready());
+        assert_eq!(trix.get_last(), None);
+    }
+}
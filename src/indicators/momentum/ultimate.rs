@@ -0,0 +1,252 @@
+use crate::Mean;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+struct PressureWindow {
+    buying_pressure: Mean,
+    true_range: Mean,
+}
+
+impl PressureWindow {
+    fn new(periods: u32) -> Self {
+        Self {
+            buying_pressure: Mean::new(periods),
+            true_range: Mean::new(periods),
+        }
+    }
+
+    fn update_after_close(&mut self, bp: f64, tr: f64) {
+        self.buying_pressure.update_after_close(bp);
+        self.true_range.update_after_close(tr);
+    }
+
+    fn update_before_close(&mut self, bp: f64, tr: f64) {
+        self.buying_pressure.update_before_close(bp);
+        self.true_range.update_before_close(tr);
+    }
+
+    fn average(&self) -> Option<f64> {
+        if !self.true_range.is_ready() {
+            return None;
+        }
+
+        let tr = self.true_range.sum();
+        if tr <= f64::EPSILON {
+            Some(0.0)
+        } else {
+            Some(self.buying_pressure.sum() / tr)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.buying_pressure.reset();
+        self.true_range.reset();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UltimateOscillator {
+    long_period: u32,
+    short: PressureWindow,
+    mid: PressureWindow,
+    long: PressureWindow,
+    prev_close: Option<f64>,
+    value: Option<f64>,
+}
+
+impl UltimateOscillator {
+    pub fn new(short_period: u32, mid_period: u32, long_period: u32) -> Self {
+        assert!(
+            short_period > 0 && mid_period > 0 && long_period > 0,
+            "UO periods must be > 0, got {}/{}/{}",
+            short_period,
+            mid_period,
+            long_period
+        );
+
+        Self {
+            long_period: short_period.max(mid_period).max(long_period),
+            short: PressureWindow::new(short_period),
+            mid: PressureWindow::new(mid_period),
+            long: PressureWindow::new(long_period),
+            prev_close: None,
+            value: None,
+        }
+    }
+
+    #[inline]
+    fn pressure(price: Price, prev_close: f64) -> (f64, f64) {
+        let true_low = price.low.min(prev_close);
+        let true_high = price.high.max(prev_close);
+        (price.close - true_low, true_high - true_low)
+    }
+
+    fn update_value(&mut self) {
+        self.value = match (
+            self.short.average(),
+            self.mid.average(),
+            self.long.average(),
+        ) {
+            (Some(short), Some(mid), Some(long)) => {
+                Some(100.0 * (4.0 * short + 2.0 * mid + long) / 7.0)
+            }
+            _ => None,
+        };
+    }
+}
+
+impl Indicator for UltimateOscillator {
+    fn update_after_close(&mut self, price: Price) {
+        if let Some(prev_close) = self.prev_close {
+            let (bp, tr) = Self::pressure(price, prev_close);
+            self.short.update_after_close(bp, tr);
+            self.mid.update_after_close(bp, tr);
+            self.long.update_after_close(bp, tr);
+            self.update_value();
+        }
+        self.prev_close = Some(price.close);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(prev_close) = self.prev_close {
+            let (bp, tr) = Self::pressure(price, prev_close);
+            self.short.update_before_close(bp, tr);
+            self.mid.update_before_close(bp, tr);
+            self.long.update_before_close(bp, tr);
+            self.update_value();
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::UltimateOscillatorValue)
+    }
+
+    fn reset(&mut self) {
+        self.short.reset();
+        self.mid.reset();
+        self.long.reset();
+        self.prev_close = None;
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.long_period
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.long_period + 1
+    }
+}
+
+impl Default for UltimateOscillator {
+    fn default() -> Self {
+        Self::new(7, 14, 28)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open: close,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn uo_closing_on_highs_is_100() {
+        let mut uo = UltimateOscillator::new(1, 2, 3);
+
+        uo.update_after_close(p(10.0, 9.0, 10.0));
+        uo.update_after_close(p(11.0, 10.0, 11.0));
+        uo.update_after_close(p(12.0, 11.0, 12.0));
+        assert!(!uo.is_ready());
+
+        uo.update_after_close(p(13.0, 12.0, 13.0));
+
+        match uo.get_last() {
+            Some(Value::UltimateOscillatorValue(value)) => approx_eq(value, 100.0),
+            _ => panic!("missing uo"),
+        }
+    }
+
+    #[test]
+    fn uo_weights_recent_windows() {
+        let mut uo = UltimateOscillator::new(1, 2, 3);
+
+        uo.load(&[
+            p(10.0, 9.0, 10.0),
+            p(11.0, 10.0, 11.0),
+            p(12.0, 11.0, 12.0),
+            p(13.0, 12.0, 13.0),
+            p(13.0, 11.0, 11.0),
+        ]);
+
+        // earlier bars close at their high (BP 1, TR 1), the last bar at its true low (BP 0, TR 2)
+        let short = 0.0;
+        let mid = 1.0 / 3.0;
+        let long = 2.0 / 4.0;
+        match uo.get_last() {
+            Some(Value::UltimateOscillatorValue(value)) => {
+                approx_eq(value, 100.0 * (4.0 * short + 2.0 * mid + long) / 7.0)
+            }
+            _ => panic!("missing uo"),
+        }
+    }
+
+    #[test]
+    fn uo_before_close_is_provisional() {
+        let mut uo = UltimateOscillator::new(1, 2, 3);
+
+        uo.load(&[
+            p(10.0, 9.0, 10.0),
+            p(11.0, 10.0, 11.0),
+            p(12.0, 11.0, 12.0),
+            p(13.0, 12.0, 13.0),
+        ]);
+
+        let after_close = uo.get_last();
+        uo.update_before_close(p(13.0, 11.0, 11.5));
+
+        assert_ne!(after_close, uo.get_last());
+    }
+
+    #[test]
+    fn uo_reset_clears_state() {
+        let mut uo = UltimateOscillator::new(1, 2, 3);
+
+        uo.load(&[
+            p(10.0, 9.0, 10.0),
+            p(11.0, 10.0, 11.0),
+            p(12.0, 11.0, 12.0),
+            p(13.0, 12.0, 13.0),
+        ]);
+        assert!(uo.is_ready());
+
+        uo.reset();
+
+        assert!(!uo.is_ready());
+        assert_eq!(uo.get_last(), None);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::indicators::{Indicator, IndicatorKind, KindError, Price, Value};
+use std::collections::HashMap;
+
+/// Owns one live indicator per distinct [`IndicatorKind`], keyed by
+/// [`IndicatorKind::key`], and feeds every candle to all of them.
+#[derive(Debug, Default)]
+pub struct IndicatorSet {
+    indicators: HashMap<String, (IndicatorKind, Box<dyn Indicator>)>,
+}
+
+impl IndicatorSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `kind` unless an indicator with the same key is already present,
+    /// and returns its key. Kinds that fail [`IndicatorKind::validate`] are
+    /// not added.
+    pub fn insert(&mut self, kind: IndicatorKind) -> Result<String, KindError> {
+        kind.validate()?;
+        let key = kind.key();
+        self.indicators
+            .entry(key.clone())
+            .or_insert_with(|| (kind, kind.build()));
+        Ok(key)
+    }
+
+    pub fn get(&self, kind: &IndicatorKind) -> Option<&dyn Indicator> {
+        self.get_by_key(&kind.key())
+    }
+
+    /// Looks an indicator up by the key [`IndicatorSet::insert`] returned.
+    pub fn get_by_key(&self, key: &str) -> Option<&dyn Indicator> {
+        self.indicators
+            .get(key)
+            .map(|(_, indicator)| indicator.as_ref())
+    }
+
+    pub fn value(&self, kind: &IndicatorKind) -> Option<Value> {
+        self.get(kind).and_then(|indicator| indicator.get_last())
+    }
+
+    /// Latest value of every indicator, by key. Indicators that are not
+    /// ready are left out.
+    pub fn values(&self) -> HashMap<String, Value> {
+        self.indicators
+            .iter()
+            .filter_map(|(key, (_, indicator))| Some((key.clone(), indicator.get_last()?)))
+            .collect()
+    }
+
+    pub fn kinds(&self) -> impl Iterator<Item = &IndicatorKind> {
+        self.indicators.values().map(|(kind, _)| kind)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indicators.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indicators.is_empty()
+    }
+
+    pub fn update_after_close(&mut self, price: Price) {
+        for (_, indicator) in self.indicators.values_mut() {
+            indicator.update_after_close(price);
+        }
+    }
+
+    pub fn update_before_close(&mut self, price: Price) {
+        for (_, indicator) in self.indicators.values_mut() {
+            indicator.update_before_close(price);
+        }
+    }
+
+    pub fn load(&mut self, price_data: &[Price]) {
+        for (_, indicator) in self.indicators.values_mut() {
+            indicator.load(price_data);
+        }
+    }
+
+    /// `true` once every indicator in the set is ready.
+    pub fn is_ready(&self) -> bool {
+        self.indicators
+            .values()
+            .all(|(_, indicator)| indicator.is_ready())
+    }
+
+    pub fn reset(&mut self) {
+        for (_, indicator) in self.indicators.values_mut() {
+            indicator.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Source;
+
+    fn p(close: f64) -> Price {
+        Price {
+            open: close,
+            high: close,
+            low: close,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 1.0,
+        }
+    }
+
+    #[test]
+    fn insert_deduplicates_by_key() {
+        let mut set = IndicatorSet::new();
+
+        assert_eq!(
+            set.insert(IndicatorKind::Sma {
+                periods: 3,
+                source: Source::Close,
+            })
+            .unwrap(),
+            "sma_3"
+        );
+        set.insert(IndicatorKind::Sma {
+            periods: 3,
+            source: Source::Close,
+        })
+        .unwrap();
+        set.insert(IndicatorKind::Ema {
+            periods: 3,
+            source: Source::Close,
+        })
+        .unwrap();
+
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn sources_are_part_of_the_key() {
+        let mut set = IndicatorSet::new();
+
+        set.insert(IndicatorKind::Ema {
+            periods: 20,
+            source: Source::Close,
+        })
+        .unwrap();
+        assert_eq!(
+            set.insert(IndicatorKind::Ema {
+                periods: 20,
+                source: Source::Hl2,
+            })
+            .unwrap(),
+            "ema_20_hl2"
+        );
+
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn insert_rejects_invalid_kinds() {
+        let mut set = IndicatorSet::new();
+
+        let err = set
+            .insert(IndicatorKind::Sma {
+                periods: 0,
+                source: Source::Close,
+            })
+            .unwrap_err();
+
+        assert_eq!(err.key(), "sma_0");
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn feeds_every_indicator() {
+        let mut set = IndicatorSet::new();
+        set.insert(IndicatorKind::Sma {
+            periods: 2,
+            source: Source::Close,
+        })
+        .unwrap();
+        set.insert(IndicatorKind::Roc {
+            periods: 1,
+            source: Source::Close,
+        })
+        .unwrap();
+
+        set.load(&[p(10.0), p(12.0)]);
+
+        assert!(set.is_ready());
+        assert_eq!(
+            set.value(&IndicatorKind::Sma {
+                periods: 2,
+                source: Source::Close,
+            }),
+            Some(Value::SmaValue(11.0))
+        );
+        assert_eq!(set.values().len(), 2);
+    }
+
+    #[test]
+    fn reset_clears_every_indicator() {
+        let mut set = IndicatorSet::new();
+        set.insert(IndicatorKind::Sma {
+            periods: 2,
+            source: Source::Close,
+        })
+        .unwrap();
+        set.load(&[p(10.0), p(12.0)]);
+
+        set.reset();
+
+        assert!(!set.is_ready());
+        assert!(set.values().is_empty());
+    }
+}
@@ -60,19 +60,10 @@ impl Adx {
 
 impl Indicator for Adx {
     fn update_after_close(&mut self, price: Price) {
-        let h = price.high;
-        let l = price.low;
-        let close = price.close;
-        let h_l = h - l;
+        let tr = price.true_range(self.prev_close);
 
-        let tr = if let Some(prev_close) = self.prev_close {
-            h_l.max((h - prev_close).abs().max((l - prev_close).abs()))
-        } else {
-            h_l
-        };
-
-        self.buff.update_after_close(h, l, tr);
-        self.prev_close = Some(close);
+        self.buff.update_after_close(price.high, price.low, tr);
+        self.prev_close = Some(price.close);
 
         if let Some(dx) = self.buff.dx {
             self.calc_adx(dx, true);
@@ -81,13 +72,9 @@ impl Indicator for Adx {
 
     fn update_before_close(&mut self, price: Price) {
         if self.is_ready() {
-            let h = price.high;
-            let l = price.low;
-            let h_l = h - l;
-
             if let Some(prev_close) = self.prev_close {
-                let tr = h_l.max((h - prev_close).abs().max((l - prev_close).abs()));
-                self.buff.update_before_close(h, l, tr);
+                let tr = price.true_range(Some(prev_close));
+                self.buff.update_before_close(price.high, price.low, tr);
             }
 
             if let Some(dx) = self.buff.dx {
@@ -116,6 +103,11 @@ impl Indicator for Adx {
         self.periods
     }
 
+    fn warmup_bars(&self) -> u32 {
+        // True range, then directional movement, then one DX per bar.
+        self.periods + 2
+    }
+
     fn reset(&mut self) {
         self.value = None;
         self.prev_close = None;
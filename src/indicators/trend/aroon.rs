@@ -0,0 +1,190 @@
+use crate::RollingExtremum;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct Aroon {
+    periods: u32,
+    highs: RollingExtremum,
+    lows: RollingExtremum,
+    value: Option<(f64, f64)>,
+}
+
+impl Aroon {
+    pub fn new(periods: u32) -> Self {
+        assert!(periods > 0, "Aroon periods must be > 0, got {}", periods);
+        Self {
+            periods,
+            highs: RollingExtremum::max(periods + 1),
+            lows: RollingExtremum::min(periods + 1),
+            value: None,
+        }
+    }
+
+    fn update_value(&mut self) {
+        let n = self.periods as f64;
+        self.value = match (self.highs.bars_since(), self.lows.bars_since()) {
+            (Some(since_high), Some(since_low)) => Some((
+                100.0 * (n - since_high as f64) / n,
+                100.0 * (n - since_low as f64) / n,
+            )),
+            _ => None,
+        };
+    }
+}
+
+impl Indicator for Aroon {
+    fn update_after_close(&mut self, price: Price) {
+        self.highs.update_after_close(price.high);
+        self.lows.update_after_close(price.low);
+        self.update_value();
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.highs.update_before_close(price.high);
+        self.lows.update_before_close(price.low);
+        self.update_value();
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(|(up, down)| Value::AroonValue {
+            up,
+            down,
+            oscillator: up - down,
+        })
+    }
+
+    fn reset(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.periods + 1
+    }
+}
+
+impl Default for Aroon {
+    fn default() -> Self {
+        Self::new(14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64) -> Price {
+        Price {
+            open: low,
+            high,
+            low,
+            close: high,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    fn lines(aroon: &Aroon) -> (f64, f64, f64) {
+        match aroon.get_last() {
+            Some(Value::AroonValue {
+                up,
+                down,
+                oscillator,
+            }) => (up, down, oscillator),
+            _ => panic!("missing aroon"),
+        }
+    }
+
+    #[test]
+    fn aroon_needs_period_plus_one_bars() {
+        let mut aroon = Aroon::new(4);
+
+        aroon.load(&[p(10.0, 5.0), p(12.0, 4.0), p(11.0, 6.0), p(9.0, 7.0)]);
+        assert!(!aroon.is_ready());
+
+        aroon.update_after_close(p(10.0, 8.0));
+
+        let (up, down, oscillator) = lines(&aroon);
+        approx_eq(up, 25.0);
+        approx_eq(down, 25.0);
+        approx_eq(oscillator, 0.0);
+    }
+
+    #[test]
+    fn aroon_up_is_full_on_new_high() {
+        let mut aroon = Aroon::new(4);
+
+        aroon.load(&[
+            p(10.0, 5.0),
+            p(12.0, 4.0),
+            p(11.0, 6.0),
+            p(9.0, 7.0),
+            p(10.0, 8.0),
+        ]);
+        aroon.update_after_close(p(15.0, 3.0));
+
+        let (up, down, oscillator) = lines(&aroon);
+        approx_eq(up, 100.0);
+        approx_eq(down, 100.0);
+        approx_eq(oscillator, 0.0);
+    }
+
+    #[test]
+    fn aroon_before_close_is_provisional() {
+        let mut aroon = Aroon::new(4);
+        let candles = [
+            p(10.0, 5.0),
+            p(12.0, 4.0),
+            p(11.0, 6.0),
+            p(9.0, 7.0),
+            p(10.0, 8.0),
+        ];
+        aroon.load(&candles);
+
+        let after_close = aroon.get_last();
+
+        aroon.update_before_close(p(20.0, 9.0));
+        let (up, _, _) = lines(&aroon);
+        approx_eq(up, 100.0);
+
+        aroon.update_before_close(p(9.5, 9.0));
+        let (up, down, _) = lines(&aroon);
+        approx_eq(up, 0.0);
+        approx_eq(down, 0.0);
+
+        assert_ne!(after_close, aroon.get_last());
+    }
+
+    #[test]
+    fn aroon_reset_clears_state() {
+        let mut aroon = Aroon::new(2);
+
+        aroon.load(&[p(10.0, 5.0), p(12.0, 4.0), p(11.0, 6.0)]);
+        assert!(aroon.is_ready());
+
+        aroon.reset();
+
+        assert!(!aroon.is_ready());
+        assert_eq!(aroon.get_last(), None);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::indicators::{Indicator, Price, Value};
+use crate::{Mean, RollingExtremum};
+
+#[derive(Clone, Debug)]
+pub struct Choppiness {
+    periods: u32,
+    tr: Mean,
+    highs: RollingExtremum,
+    lows: RollingExtremum,
+    prev_close: Option<f64>,
+    value: Option<f64>,
+}
+
+impl Choppiness {
+    pub fn new(periods: u32) -> Self {
+        assert!(
+            periods > 1,
+            "Choppiness periods must be > 1, got {}",
+            periods
+        );
+        Self {
+            periods,
+            tr: Mean::new(periods),
+            highs: RollingExtremum::max(periods),
+            lows: RollingExtremum::min(periods),
+            prev_close: None,
+            value: None,
+        }
+    }
+
+    fn update_value(&mut self) {
+        self.value = match (self.highs.get_last(), self.lows.get_last()) {
+            (Some(high), Some(low)) if self.tr.is_ready() => {
+                let range = high - low;
+                if range <= f64::EPSILON {
+                    Some(100.0)
+                } else {
+                    Some(100.0 * (self.tr.sum() / range).log10() / (self.periods as f64).log10())
+                }
+            }
+            _ => None,
+        };
+    }
+}
+
+impl Indicator for Choppiness {
+    fn update_after_close(&mut self, price: Price) {
+        self.tr
+            .update_after_close(price.true_range(self.prev_close));
+        self.highs.update_after_close(price.high);
+        self.lows.update_after_close(price.low);
+        self.prev_close = Some(price.close);
+        self.update_value();
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if !self.tr.is_ready() {
+            return;
+        }
+
+        self.tr
+            .update_before_close(price.true_range(self.prev_close));
+        self.highs.update_before_close(price.high);
+        self.lows.update_before_close(price.low);
+        self.update_value();
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::ChoppinessValue)
+    }
+
+    fn reset(&mut self) {
+        self.tr.reset();
+        self.highs.reset();
+        self.lows.reset();
+        self.prev_close = None;
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+}
+
+impl Default for Choppiness {
+    fn default() -> Self {
+        Self::new(14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open: close,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn choppiness_computes_expected_value() {
+        let mut chop = Choppiness::new(2);
+
+        chop.update_after_close(p(10.0, 8.0, 9.0));
+        assert!(!chop.is_ready());
+
+        chop.update_after_close(p(12.0, 9.0, 11.0));
+
+        // TR = 2 + 3 = 5, range = 12 - 8 = 4
+        match chop.get_last() {
+            Some(Value::ChoppinessValue(value)) => {
+                approx_eq(value, 100.0 * (5.0_f64 / 4.0).log10() / 2.0_f64.log10())
+            }
+            _ => panic!("missing choppiness"),
+        }
+    }
+
+    #[test]
+    fn choppiness_trending_market_is_low() {
+        let mut chop = Choppiness::new(4);
+
+        for i in 0..8 {
+            let base = 100.0 + i as f64 * 5.0;
+            chop.update_after_close(p(base + 5.0, base, base + 5.0));
+        }
+
+        match chop.get_last() {
+            Some(Value::ChoppinessValue(value)) => approx_eq(value, 0.0),
+            _ => panic!("missing choppiness"),
+        }
+    }
+
+    #[test]
+    fn choppiness_before_close_is_provisional() {
+        let mut chop = Choppiness::new(2);
+
+        chop.load(&[p(10.0, 8.0, 9.0), p(12.0, 9.0, 11.0), p(13.0, 10.0, 12.0)]);
+
+        let after_close = chop.get_last();
+        chop.update_before_close(p(16.0, 11.0, 15.0));
+
+        assert_ne!(after_close, chop.get_last());
+    }
+
+    #[test]
+    fn choppiness_reset_clears_state() {
+        let mut chop = Choppiness::new(2);
+
+        chop.load(&[p(10.0, 8.0, 9.0), p(12.0, 9.0, 11.0)]);
+        assert!(chop.is_ready());
+
+        chop.reset();
+
+        assert!(!chop.is_ready());
+        assert_eq!(chop.get_last(), None);
+    }
+}
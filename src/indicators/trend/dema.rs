@@ -1,5 +1,5 @@
 use crate::ExpMean;
-use crate::indicators::{Indicator, Price, Value};
+use crate::indicators::{Indicator, Price, Source, Value};
 
 #[derive(Clone, Debug)]
 pub struct Dema {
@@ -7,6 +7,7 @@ pub struct Dema {
     ema1: ExpMean,
     ema2: ExpMean,
     value: Option<f64>,
+    source: Source,
 }
 
 impl Dema {
@@ -17,9 +18,15 @@ impl Dema {
             ema1: ExpMean::new(periods),
             ema2: ExpMean::new(periods),
             value: None,
+            source: Source::default(),
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
     fn update_value(&mut self) {
         self.value = match (self.ema1.get_last(), self.ema2.get_last()) {
             (Some(ema1), Some(ema2)) => Some(2.0 * ema1 - ema2),
@@ -30,7 +37,7 @@ impl Dema {
 
 impl Indicator for Dema {
     fn update_after_close(&mut self, price: Price) {
-        self.ema1.update_after_close(price.close);
+        self.ema1.update_after_close(self.source.get(&price));
         if let Some(ema1) = self.ema1.get_last() {
             self.ema2.update_after_close(ema1);
         }
@@ -38,7 +45,7 @@ impl Indicator for Dema {
     }
 
     fn update_before_close(&mut self, price: Price) {
-        self.ema1.update_before_close(price.close);
+        self.ema1.update_before_close(self.source.get(&price));
         if let Some(ema1) = self.ema1.get_last() {
             self.ema2.update_before_close(ema1);
         }
@@ -68,6 +75,10 @@ impl Indicator for Dema {
     fn period(&self) -> u32 {
         self.periods
     }
+
+    fn warmup_bars(&self) -> u32 {
+        2 * self.periods - 1
+    }
 }
 
 impl Default for Dema {
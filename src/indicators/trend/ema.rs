@@ -1,11 +1,14 @@
 use crate::ExpMean;
-use crate::indicators::{Indicator, Price, Value};
+use crate::indicators::batch::{ColumnReader, fill_columns};
+use crate::indicators::{Indicator, Price, Source, Value};
+use std::borrow::Borrow;
 
 #[derive(Clone, Debug)]
 pub struct Ema {
     core: ExpMean,
     pub value: Option<f64>,
     slope: Option<f64>,
+    source: Source,
 }
 
 #[derive(Clone, Debug)]
@@ -24,6 +27,12 @@ impl EmaCross {
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.short = self.short.with_source(source);
+        self.long = self.long.with_source(source);
+        self
+    }
+
     pub fn check_for_cross(&mut self) -> Option<bool> {
         if !self.is_ready() {
             None
@@ -132,9 +141,15 @@ impl Ema {
             core: ExpMean::new(periods),
             value: None,
             slope: None,
+            source: Source::default(),
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
     pub fn get_slope(&self) -> Option<f64> {
         self.slope
     }
@@ -154,14 +169,14 @@ impl Ema {
 impl Indicator for Ema {
     fn update_after_close(&mut self, price: Price) {
         let last_confirmed = self.core.get_confirmed();
-        self.core.update_after_close(price.close);
+        self.core.update_after_close(self.source.get(&price));
         self.sync_value();
         self.update_slope(last_confirmed);
     }
 
     fn update_before_close(&mut self, price: Price) {
         let last_confirmed = self.core.get_confirmed();
-        self.core.update_before_close(price.close);
+        self.core.update_before_close(self.source.get(&price));
         self.sync_value();
         self.update_slope(last_confirmed);
     }
@@ -170,6 +185,23 @@ impl Indicator for Ema {
         self.value.map(Value::EmaValue)
     }
 
+    fn compute_columns<P>(&mut self, price_data: P, fields: &[&str]) -> Vec<Vec<f64>>
+    where
+        P: IntoIterator,
+        P::Item: Borrow<Price>,
+    {
+        let readers: Vec<ColumnReader<Self>> = fields
+            .iter()
+            .map(|field| -> ColumnReader<Self> {
+                match *field {
+                    "value" => |ema| ema.value,
+                    _ => |_| None,
+                }
+            })
+            .collect();
+        fill_columns(self, price_data, &readers)
+    }
+
     fn is_ready(&self) -> bool {
         self.value.is_some()
     }
@@ -197,6 +229,7 @@ impl Default for Ema {
             core: ExpMean::new(9),
             value: None,
             slope: None,
+            source: Source::default(),
         }
     }
 }
@@ -251,4 +284,18 @@ mod tests {
         approx_eq(ema.value.unwrap(), 12.5);
         approx_eq(ema.get_slope().unwrap(), ((12.5 - 11.0) / 11.0) * 100.0);
     }
+
+    #[test]
+    fn ema_reads_configured_source() {
+        let mut ema = Ema::new(2).with_source(Source::Hl2);
+        let candle = |high: f64, low: f64| Price {
+            high,
+            low,
+            ..p(0.0)
+        };
+
+        ema.load(&[candle(12.0, 8.0), candle(14.0, 10.0)]);
+
+        approx_eq(ema.value.unwrap(), 11.0);
+    }
 }
@@ -240,6 +240,13 @@ impl Indicator for Ichimoku {
     fn period(&self) -> u32 {
         self.senkou_b_period
     }
+
+    fn warmup_bars(&self) -> u32 {
+        self.tenkan_window
+            .period
+            .max(self.kijun_window.period)
+            .max(self.senkou_b_period)
+    }
 }
 
 impl Default for Ichimoku {
@@ -1,13 +1,26 @@
 mod adx;
+mod aroon;
+mod choppiness;
 mod dema;
 mod ema;
 mod ichimoku;
+mod pivots;
 mod sma;
+mod swing;
 mod tema;
+mod vortex;
+mod zigzag;
 
 pub use adx::Adx;
+pub use aroon::Aroon;
+pub use choppiness::Choppiness;
 pub use dema::Dema;
 pub use ema::{Ema, EmaCross};
 pub use ichimoku::Ichimoku;
+pub use pivots::{PivotAnchor, PivotMethod, PivotPoints};
 pub use sma::Sma;
+pub use swing::{MarketStructure, StructureEvent, SwingDetector, SwingLabel, SwingPoint};
+pub(crate) use swing::{PivotHits, PivotWindow};
 pub use tema::Tema;
+pub use vortex::Vortex;
+pub use zigzag::{ZigZag, ZigZagDirection, ZigZagPivot, ZigZagThreshold};
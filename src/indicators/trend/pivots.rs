@@ -0,0 +1,445 @@
+use crate::indicators::{Indicator, Price, Value};
+use serde::{Deserialize, Serialize};
+
+const MS_PER_DAY: u64 = 86_400_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PivotAnchor {
+    #[default]
+    Daily,
+    /// Weeks start on Monday.
+    Weekly,
+    Monthly,
+}
+
+impl PivotAnchor {
+    pub fn key(&self) -> &'static str {
+        match self {
+            PivotAnchor::Daily => "daily",
+            PivotAnchor::Weekly => "weekly",
+            PivotAnchor::Monthly => "monthly",
+        }
+    }
+
+    /// Index of the UTC period containing `open_time` (ms since epoch).
+    fn period_index(&self, open_time: u64) -> u64 {
+        let days = open_time / MS_PER_DAY;
+        match self {
+            PivotAnchor::Daily => days,
+            // 1970-01-01 was a Thursday.
+            PivotAnchor::Weekly => (days + 3) / 7,
+            PivotAnchor::Monthly => month_index(days),
+        }
+    }
+}
+
+/// Months since year 0 for a day count since 1970-01-01 (proleptic Gregorian).
+fn month_index(days: u64) -> u64 {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    year * 12 + (month - 1)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PivotMethod {
+    #[default]
+    Classic,
+    Fibonacci,
+    Camarilla,
+    /// Uses the current period's open, as TradingView does.
+    Woodie,
+    /// Only defines P, R1 and S1; the other levels are `None`.
+    DeMark,
+}
+
+impl PivotMethod {
+    pub fn key(&self) -> &'static str {
+        match self {
+            PivotMethod::Classic => "classic",
+            PivotMethod::Fibonacci => "fibonacci",
+            PivotMethod::Camarilla => "camarilla",
+            PivotMethod::Woodie => "woodie",
+            PivotMethod::DeMark => "demark",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PeriodBar {
+    index: u64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+}
+
+impl PeriodBar {
+    fn new(index: u64, price: Price) -> Self {
+        Self {
+            index,
+            open: price.open,
+            high: price.high,
+            low: price.low,
+            close: price.close,
+        }
+    }
+
+    fn merge(&mut self, price: Price) {
+        self.high = self.high.max(price.high);
+        self.low = self.low.min(price.low);
+        self.close = price.close;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PivotLevels {
+    p: f64,
+    r1: f64,
+    r2: Option<f64>,
+    r3: Option<f64>,
+    s1: f64,
+    s2: Option<f64>,
+    s3: Option<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PivotPoints {
+    anchor: PivotAnchor,
+    method: PivotMethod,
+    current: Option<PeriodBar>,
+    previous: Option<PeriodBar>,
+    value: Option<PivotLevels>,
+}
+
+impl PivotPoints {
+    pub fn new(anchor: PivotAnchor, method: PivotMethod) -> Self {
+        Self {
+            anchor,
+            method,
+            current: None,
+            previous: None,
+            value: None,
+        }
+    }
+
+    fn levels(&self, bar: &PeriodBar, current_open: f64) -> PivotLevels {
+        let (h, l, c) = (bar.high, bar.low, bar.close);
+        let range = h - l;
+
+        match self.method {
+            PivotMethod::Classic => {
+                let p = (h + l + c) / 3.0;
+                PivotLevels {
+                    p,
+                    r1: 2.0 * p - l,
+                    r2: Some(p + range),
+                    r3: Some(h + 2.0 * (p - l)),
+                    s1: 2.0 * p - h,
+                    s2: Some(p - range),
+                    s3: Some(l - 2.0 * (h - p)),
+                }
+            }
+            PivotMethod::Fibonacci => {
+                let p = (h + l + c) / 3.0;
+                PivotLevels {
+                    p,
+                    r1: p + 0.382 * range,
+                    r2: Some(p + 0.618 * range),
+                    r3: Some(p + range),
+                    s1: p - 0.382 * range,
+                    s2: Some(p - 0.618 * range),
+                    s3: Some(p - range),
+                }
+            }
+            PivotMethod::Camarilla => PivotLevels {
+                p: (h + l + c) / 3.0,
+                r1: c + range * 1.1 / 12.0,
+                r2: Some(c + range * 1.1 / 6.0),
+                r3: Some(c + range * 1.1 / 4.0),
+                s1: c - range * 1.1 / 12.0,
+                s2: Some(c - range * 1.1 / 6.0),
+                s3: Some(c - range * 1.1 / 4.0),
+            },
+            PivotMethod::Woodie => {
+                let p = (h + l + 2.0 * current_open) / 4.0;
+                PivotLevels {
+                    p,
+                    r1: 2.0 * p - l,
+                    r2: Some(p + range),
+                    r3: Some(h + 2.0 * (p - l)),
+                    s1: 2.0 * p - h,
+                    s2: Some(p - range),
+                    s3: Some(l - 2.0 * (h - p)),
+                }
+            }
+            PivotMethod::DeMark => {
+                let x = if c < bar.open {
+                    h + 2.0 * l + c
+                } else if c > bar.open {
+                    2.0 * h + l + c
+                } else {
+                    h + l + 2.0 * c
+                };
+                PivotLevels {
+                    p: x / 4.0,
+                    r1: x / 2.0 - l,
+                    r2: None,
+                    r3: None,
+                    s1: x / 2.0 - h,
+                    s2: None,
+                    s3: None,
+                }
+            }
+        }
+    }
+}
+
+impl Indicator for PivotPoints {
+    fn update_after_close(&mut self, price: Price) {
+        let index = self.anchor.period_index(price.open_time);
+
+        match self.current.as_mut() {
+            Some(current) if current.index == index => current.merge(price),
+            _ => {
+                self.previous = self.current.take();
+                self.current = Some(PeriodBar::new(index, price));
+            }
+        }
+
+        self.value = match (self.previous, self.current) {
+            (Some(previous), Some(current)) => Some(self.levels(&previous, current.open)),
+            _ => None,
+        };
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        let index = self.anchor.period_index(price.open_time);
+
+        // A bar opening a new period promotes the running period, without
+        // committing it until the bar closes.
+        let (completed, current_open) = match self.current {
+            Some(current) if current.index != index => (Some(current), price.open),
+            Some(current) => (self.previous, current.open),
+            None => (None, price.open),
+        };
+
+        self.value = completed.map(|bar| self.levels(&bar, current_open));
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(|levels| Value::PivotValue {
+            p: levels.p,
+            r1: levels.r1,
+            r2: levels.r2,
+            r3: levels.r3,
+            s1: levels.s1,
+            s2: levels.s2,
+            s3: levels.s3,
+        })
+    }
+
+    fn reset(&mut self) {
+        self.current = None;
+        self.previous = None;
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        1
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        // At least one completed anchor period; the real count depends on
+        // where the data starts.
+        2
+    }
+}
+
+impl Default for PivotPoints {
+    fn default() -> Self {
+        Self::new(PivotAnchor::Daily, PivotMethod::Classic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3_600_000;
+    // 2024-01-01T00:00:00Z, a Monday
+    const JAN_1: u64 = 19_723 * MS_PER_DAY;
+
+    fn p(open_time: u64, open: f64, high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open,
+            high,
+            low,
+            close,
+            open_time,
+            close_time: open_time + HOUR - 1,
+            vlm: 0.0,
+        }
+    }
+
+    fn levels(pivots: &PivotPoints) -> [f64; 7] {
+        match pivots.get_last() {
+            Some(Value::PivotValue {
+                p,
+                r1,
+                r2: Some(r2),
+                r3: Some(r3),
+                s1,
+                s2: Some(s2),
+                s3: Some(s3),
+            }) => [p, r1, r2, r3, s1, s2, s3],
+            _ => panic!("missing pivots"),
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    fn first_day() -> [Price; 2] {
+        [
+            p(JAN_1, 10.0, 12.0, 8.0, 11.0),
+            p(JAN_1 + HOUR, 11.0, 13.0, 9.0, 12.0),
+        ]
+    }
+
+    #[test]
+    fn classic_uses_previous_day() {
+        let mut pivots = PivotPoints::default();
+
+        pivots.load(&first_day());
+        assert!(!pivots.is_ready());
+
+        pivots.update_after_close(p(JAN_1 + 24 * HOUR, 12.0, 14.0, 11.0, 13.0));
+
+        // previous day: H 13, L 8, C 12
+        let expected = [11.0, 14.0, 16.0, 19.0, 9.0, 6.0, 4.0];
+        for (level, expected) in levels(&pivots).into_iter().zip(expected) {
+            approx_eq(level, expected);
+        }
+    }
+
+    #[test]
+    fn methods_compute_expected_levels() {
+        let next = p(JAN_1 + 24 * HOUR, 12.0, 14.0, 11.0, 13.0);
+        let run = |method| {
+            let mut pivots = PivotPoints::new(PivotAnchor::Daily, method);
+            pivots.load(&first_day());
+            pivots.update_after_close(next);
+            levels(&pivots)
+        };
+
+        let fib = run(PivotMethod::Fibonacci);
+        approx_eq(fib[1], 11.0 + 0.382 * 5.0);
+        approx_eq(fib[6], 6.0);
+
+        let camarilla = run(PivotMethod::Camarilla);
+        approx_eq(camarilla[3], 12.0 + 5.0 * 1.1 / 4.0);
+        approx_eq(camarilla[4], 12.0 - 5.0 * 1.1 / 12.0);
+
+        let woodie = run(PivotMethod::Woodie);
+        approx_eq(woodie[0], (13.0 + 8.0 + 2.0 * 12.0) / 4.0);
+    }
+
+    #[test]
+    fn demark_leaves_outer_levels_empty() {
+        let mut pivots = PivotPoints::new(PivotAnchor::Daily, PivotMethod::DeMark);
+        pivots.load(&first_day());
+        pivots.update_after_close(p(JAN_1 + 24 * HOUR, 12.0, 14.0, 11.0, 13.0));
+
+        // close 12 above open 10: X = 2H + L + C
+        let value = pivots.get_last().unwrap();
+        assert_eq!(
+            value,
+            Value::PivotValue {
+                p: 46.0 / 4.0,
+                r1: 23.0 - 8.0,
+                r2: None,
+                r3: None,
+                s1: 23.0 - 13.0,
+                s2: None,
+                s3: None,
+            }
+        );
+        assert_eq!(pivots.get_last(), Some(value));
+        assert_eq!(value.field("r1"), Some(15.0));
+        assert_eq!(value.field("r2"), None);
+    }
+
+    #[test]
+    fn weekly_and_monthly_anchors_roll_on_utc_boundaries() {
+        let mut weekly = PivotPoints::new(PivotAnchor::Weekly, PivotMethod::Classic);
+        let sunday = JAN_1 - 24 * HOUR;
+        weekly.load(&[
+            p(sunday, 10.0, 12.0, 8.0, 11.0),
+            p(JAN_1, 11.0, 13.0, 9.0, 12.0),
+        ]);
+        approx_eq(levels(&weekly)[0], (12.0 + 8.0 + 11.0) / 3.0);
+
+        weekly.update_after_close(p(JAN_1 + 6 * 24 * HOUR, 12.0, 20.0, 5.0, 15.0));
+        approx_eq(levels(&weekly)[0], (12.0 + 8.0 + 11.0) / 3.0);
+
+        let mut monthly = PivotPoints::new(PivotAnchor::Monthly, PivotMethod::Classic);
+        let jan_31 = JAN_1 + 30 * 24 * HOUR;
+        monthly.load(&[
+            p(JAN_1, 10.0, 12.0, 8.0, 11.0),
+            p(jan_31, 11.0, 13.0, 9.0, 12.0),
+        ]);
+        assert!(!monthly.is_ready());
+
+        monthly.update_after_close(p(jan_31 + 24 * HOUR, 12.0, 14.0, 11.0, 13.0));
+        approx_eq(levels(&monthly)[0], 11.0);
+    }
+
+    #[test]
+    fn before_close_previews_new_period_without_committing() {
+        let mut pivots = PivotPoints::default();
+
+        pivots.load(&first_day());
+        pivots.update_before_close(p(JAN_1 + 24 * HOUR, 12.0, 14.0, 11.0, 13.0));
+        approx_eq(levels(&pivots)[0], 11.0);
+
+        pivots.update_before_close(p(JAN_1 + 2 * HOUR, 12.0, 14.0, 11.0, 13.0));
+        assert!(!pivots.is_ready());
+
+        pivots.update_after_close(p(JAN_1 + 2 * HOUR, 12.0, 14.0, 11.0, 13.0));
+        pivots.update_after_close(p(JAN_1 + 24 * HOUR, 13.0, 14.0, 12.0, 13.0));
+        approx_eq(levels(&pivots)[0], (14.0 + 8.0 + 13.0) / 3.0);
+    }
+
+    #[test]
+    fn pivots_reset_clears_state() {
+        let mut pivots = PivotPoints::default();
+
+        pivots.load(&first_day());
+        pivots.update_after_close(p(JAN_1 + 24 * HOUR, 12.0, 14.0, 11.0, 13.0));
+        assert!(pivots.is_ready());
+
+        pivots.reset();
+
+        assert!(!pivots.is_ready());
+        assert_eq!(pivots.get_last(), None);
+    }
+}
@@ -1,10 +1,13 @@
 use crate::Mean;
-use crate::indicators::{Indicator, Price, Value};
+use crate::indicators::batch::{ColumnReader, fill_columns};
+use crate::indicators::{Indicator, Price, Source, Value};
+use std::borrow::Borrow;
 
 #[derive(Clone, Debug)]
 pub struct Sma {
     periods: u32,
     mean: Mean,
+    source: Source,
 }
 
 impl Sma {
@@ -17,22 +20,28 @@ impl Sma {
         Sma {
             periods,
             mean: Mean::new(periods),
+            source: Source::default(),
         }
     }
+
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
 }
 
 impl Indicator for Sma {
     fn update_after_close(&mut self, price: Price) {
-        self.mean.update_after_close(price.close);
+        self.mean.update_after_close(self.source.get(&price));
     }
 
     fn update_before_close(&mut self, price: Price) {
-        self.mean.update_before_close(price.close);
+        self.mean.update_before_close(self.source.get(&price));
     }
 
     fn load(&mut self, price_data: &[Price]) {
         for p in price_data {
-            self.mean.update_after_close(p.close);
+            self.mean.update_after_close(self.source.get(p));
         }
     }
 
@@ -44,6 +53,23 @@ impl Indicator for Sma {
         self.mean.get_last().map(Value::SmaValue)
     }
 
+    fn compute_columns<P>(&mut self, price_data: P, fields: &[&str]) -> Vec<Vec<f64>>
+    where
+        P: IntoIterator,
+        P::Item: Borrow<Price>,
+    {
+        let readers: Vec<ColumnReader<Self>> = fields
+            .iter()
+            .map(|field| -> ColumnReader<Self> {
+                match *field {
+                    "value" => |sma| sma.mean.get_last(),
+                    _ => |_| None,
+                }
+            })
+            .collect();
+        fill_columns(self, price_data, &readers)
+    }
+
     fn reset(&mut self) {
         self.mean.reset();
     }
@@ -58,6 +84,7 @@ impl Default for Sma {
         Sma {
             periods: 9,
             mean: Mean::new(9),
+            source: Source::default(),
         }
     }
 }
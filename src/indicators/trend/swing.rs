@@ -0,0 +1,549 @@
+use crate::indicators::{Indicator, Price, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SwingPoint {
+    /// `open_time` of the pivot bar.
+    pub time: u64,
+    pub price: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct PivotHits {
+    pub high: Option<SwingPoint>,
+    pub low: Option<SwingPoint>,
+}
+
+/// Fractal pivot detection over `left + 1 + right` bars. A pivot high is
+/// strictly above the `left` bars before it and at least as high as the
+/// `right` bars after it, so it is only known `right` bars later.
+#[derive(Clone, Debug)]
+pub(crate) struct PivotWindow {
+    left: usize,
+    right: usize,
+    bars: VecDeque<(u64, f64, f64)>,
+}
+
+impl PivotWindow {
+    pub fn new(left: u32, right: u32) -> Self {
+        assert!(
+            left > 0 && right > 0,
+            "Pivot left/right bars must be > 0, got {}/{}",
+            left,
+            right
+        );
+        Self {
+            left: left as usize,
+            right: right as usize,
+            bars: VecDeque::with_capacity((left + right + 1) as usize),
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.left + self.right + 1
+    }
+
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.bars.len() == self.size()
+    }
+
+    /// Commits a closed bar and returns the pivots it confirms.
+    pub fn push(&mut self, time: u64, high: f64, low: f64) -> PivotHits {
+        if self.is_ready() {
+            self.bars.pop_front();
+        }
+        self.bars.push_back((time, high, low));
+
+        if self.is_ready() {
+            self.detect(self.bars.iter().copied())
+        } else {
+            PivotHits::default()
+        }
+    }
+
+    /// Evaluates an in-progress bar without committing it. Returns `None`
+    /// while the window would still be short with that bar included.
+    pub fn peek(&self, time: u64, high: f64, low: f64) -> Option<PivotHits> {
+        if self.bars.len() + 1 < self.size() {
+            return None;
+        }
+
+        let skip = usize::from(self.is_ready());
+        let bars = self
+            .bars
+            .iter()
+            .copied()
+            .skip(skip)
+            .chain(std::iter::once((time, high, low)));
+        Some(self.detect(bars))
+    }
+
+    fn detect(&self, bars: impl Iterator<Item = (u64, f64, f64)> + Clone) -> PivotHits {
+        let Some((time, high, low)) = bars.clone().nth(self.left) else {
+            return PivotHits::default();
+        };
+
+        let mut is_high = true;
+        let mut is_low = true;
+        for (i, (_, h, l)) in bars.enumerate() {
+            if i < self.left {
+                is_high &= high > h;
+                is_low &= low < l;
+            } else if i > self.left {
+                is_high &= high >= h;
+                is_low &= low <= l;
+            }
+        }
+
+        PivotHits {
+            high: is_high.then_some(SwingPoint { time, price: high }),
+            low: is_low.then_some(SwingPoint { time, price: low }),
+        }
+    }
+
+    pub fn period(&self) -> u32 {
+        self.size() as u32
+    }
+
+    pub fn reset(&mut self) {
+        self.bars.clear();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SwingDetector {
+    window: PivotWindow,
+    last_high: Option<SwingPoint>,
+    last_low: Option<SwingPoint>,
+    value: Option<(PivotHits, Option<f64>, Option<f64>)>,
+}
+
+impl SwingDetector {
+    pub fn new(left: u32, right: u32) -> Self {
+        Self {
+            window: PivotWindow::new(left, right),
+            last_high: None,
+            last_low: None,
+            value: None,
+        }
+    }
+
+    /// Last confirmed swing high; provisional pivots are not included.
+    #[inline]
+    pub fn last_high(&self) -> Option<SwingPoint> {
+        self.last_high
+    }
+
+    /// Last confirmed swing low; provisional pivots are not included.
+    #[inline]
+    pub fn last_low(&self) -> Option<SwingPoint> {
+        self.last_low
+    }
+
+    fn update_value(&mut self, hits: PivotHits) {
+        let high = hits.high.or(self.last_high).map(|point| point.price);
+        let low = hits.low.or(self.last_low).map(|point| point.price);
+        self.value = Some((hits, high, low));
+    }
+}
+
+impl Indicator for SwingDetector {
+    fn update_after_close(&mut self, price: Price) {
+        let hits = self.window.push(price.open_time, price.high, price.low);
+        self.last_high = hits.high.or(self.last_high);
+        self.last_low = hits.low.or(self.last_low);
+        if self.window.is_ready() {
+            self.update_value(hits);
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(hits) = self.window.peek(price.open_time, price.high, price.low) {
+            self.update_value(hits);
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(|(hits, high, low)| Value::SwingValue {
+            high,
+            low,
+            new_high: hits.high.is_some(),
+            new_low: hits.low.is_some(),
+        })
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+        self.last_high = None;
+        self.last_low = None;
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.window.period()
+    }
+}
+
+impl Default for SwingDetector {
+    fn default() -> Self {
+        Self::new(2, 2)
+    }
+}
+
+#[derive(PartialEq, PartialOrd, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SwingLabel {
+    HigherHigh,
+    LowerHigh,
+    HigherLow,
+    LowerLow,
+}
+
+#[derive(PartialEq, PartialOrd, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StructureEvent {
+    /// Close above the last swing high in an uptrend, or to start one.
+    BullishBos,
+    /// Close below the last swing low in a downtrend, or to start one.
+    BearishBos,
+    /// Close above the last swing high while in a downtrend.
+    BullishChoch,
+    /// Close below the last swing low while in an uptrend.
+    BearishChoch,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct StructureState {
+    high: Option<SwingPoint>,
+    low: Option<SwingPoint>,
+    high_label: Option<SwingLabel>,
+    low_label: Option<SwingLabel>,
+    high_broken: bool,
+    low_broken: bool,
+    bullish: Option<bool>,
+    event: Option<StructureEvent>,
+}
+
+impl StructureState {
+    fn step(mut self, hits: PivotHits, close: f64) -> Self {
+        self.event = None;
+
+        if let Some(high) = hits.high {
+            self.high_label = self.high.map(|prev| {
+                if high.price > prev.price {
+                    SwingLabel::HigherHigh
+                } else {
+                    SwingLabel::LowerHigh
+                }
+            });
+            self.high = Some(high);
+            self.high_broken = false;
+        }
+
+        if let Some(low) = hits.low {
+            self.low_label = self.low.map(|prev| {
+                if low.price > prev.price {
+                    SwingLabel::HigherLow
+                } else {
+                    SwingLabel::LowerLow
+                }
+            });
+            self.low = Some(low);
+            self.low_broken = false;
+        }
+
+        // Each swing level can be broken once.
+        if let Some(high) = self.high
+            && !self.high_broken
+            && close > high.price
+        {
+            self.event = Some(if self.bullish == Some(false) {
+                StructureEvent::BullishChoch
+            } else {
+                StructureEvent::BullishBos
+            });
+            self.bullish = Some(true);
+            self.high_broken = true;
+        } else if let Some(low) = self.low
+            && !self.low_broken
+            && close < low.price
+        {
+            self.event = Some(if self.bullish == Some(true) {
+                StructureEvent::BearishChoch
+            } else {
+                StructureEvent::BearishBos
+            });
+            self.bullish = Some(false);
+            self.low_broken = true;
+        }
+
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MarketStructure {
+    window: PivotWindow,
+    confirmed: StructureState,
+    last: Option<StructureState>,
+}
+
+impl MarketStructure {
+    pub fn new(left: u32, right: u32) -> Self {
+        Self {
+            window: PivotWindow::new(left, right),
+            confirmed: StructureState::default(),
+            last: None,
+        }
+    }
+}
+
+impl Indicator for MarketStructure {
+    fn update_after_close(&mut self, price: Price) {
+        let hits = self.window.push(price.open_time, price.high, price.low);
+        self.confirmed = self.confirmed.step(hits, price.close);
+        if self.window.is_ready() {
+            self.last = Some(self.confirmed);
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(hits) = self.window.peek(price.open_time, price.high, price.low) {
+            self.last = Some(self.confirmed.step(hits, price.close));
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.last.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.last.map(|state| Value::MarketStructureValue {
+            swing_high: state.high.map(|point| point.price),
+            swing_low: state.low.map(|point| point.price),
+            high_label: state.high_label,
+            low_label: state.low_label,
+            bullish: state.bullish,
+            event: state.event,
+        })
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+        self.confirmed = StructureState::default();
+        self.last = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.window.period()
+    }
+}
+
+impl Default for MarketStructure {
+    fn default() -> Self {
+        Self::new(2, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(time: u64, high: f64, low: f64) -> Price {
+        Price {
+            open: low,
+            high,
+            low,
+            close: (high + low) / 2.0,
+            open_time: time,
+            close_time: time,
+            vlm: 0.0,
+        }
+    }
+
+    fn c(time: u64, high: f64, low: f64, close: f64) -> Price {
+        Price {
+            close,
+            ..p(time, high, low)
+        }
+    }
+
+    fn swing(detector: &SwingDetector) -> (Option<f64>, Option<f64>, bool, bool) {
+        match detector.get_last() {
+            Some(Value::SwingValue {
+                high,
+                low,
+                new_high,
+                new_low,
+            }) => (high, low, new_high, new_low),
+            _ => panic!("missing swing"),
+        }
+    }
+
+    fn structure(
+        ms: &MarketStructure,
+    ) -> (
+        Option<SwingLabel>,
+        Option<SwingLabel>,
+        Option<StructureEvent>,
+    ) {
+        match ms.get_last() {
+            Some(Value::MarketStructureValue {
+                high_label,
+                low_label,
+                event,
+                ..
+            }) => (high_label, low_label, event),
+            _ => panic!("missing market structure"),
+        }
+    }
+
+    #[test]
+    fn swing_high_confirmed_after_right_bars() {
+        let mut swings = SwingDetector::new(2, 2);
+
+        swings.load(&[
+            p(0, 10.0, 9.0),
+            p(1, 11.0, 10.0),
+            p(2, 13.0, 11.0),
+            p(3, 12.0, 11.0),
+        ]);
+        assert!(!swings.is_ready());
+
+        // Equal highs on the right side still confirm the pivot.
+        swings.update_after_close(p(4, 13.0, 10.5));
+
+        assert_eq!(swing(&swings), (Some(13.0), None, true, false));
+        assert_eq!(
+            swings.last_high(),
+            Some(SwingPoint {
+                time: 2,
+                price: 13.0
+            })
+        );
+    }
+
+    #[test]
+    fn swing_requires_strictly_higher_than_left_bars() {
+        let mut swings = SwingDetector::new(2, 2);
+
+        swings.load(&[
+            p(0, 10.0, 9.0),
+            p(1, 13.0, 10.0),
+            p(2, 13.0, 11.0),
+            p(3, 12.0, 11.0),
+            p(4, 11.0, 10.5),
+        ]);
+
+        assert_eq!(swings.last_high(), None);
+    }
+
+    #[test]
+    fn provisional_pivot_can_be_revoked() {
+        let mut swings = SwingDetector::new(2, 2);
+
+        swings.load(&[
+            p(0, 10.0, 9.0),
+            p(1, 11.0, 10.0),
+            p(2, 13.0, 11.0),
+            p(3, 12.0, 11.0),
+        ]);
+
+        swings.update_before_close(p(4, 12.5, 11.0));
+        assert!(swing(&swings).2);
+
+        swings.update_before_close(p(4, 14.0, 11.0));
+        assert!(!swing(&swings).2);
+
+        swings.update_after_close(p(4, 14.0, 11.0));
+        assert_eq!(swings.last_high(), None);
+    }
+
+    #[test]
+    fn market_structure_labels_swings_and_breaks() {
+        let mut ms = MarketStructure::new(1, 1);
+
+        ms.load(&[
+            c(0, 10.0, 8.0, 9.0),
+            c(1, 12.0, 9.0, 11.0),
+            c(2, 11.0, 7.0, 8.0),
+            c(3, 14.0, 10.0, 13.0),
+            c(4, 13.0, 9.0, 10.0),
+        ]);
+
+        // swing highs 12 then 14, swing low 7; bar 3 closed above 12
+        assert_eq!(structure(&ms).0, Some(SwingLabel::HigherHigh));
+
+        ms.update_after_close(c(5, 16.0, 12.0, 15.0));
+        assert_eq!(structure(&ms).2, Some(StructureEvent::BullishBos));
+
+        ms.update_after_close(c(6, 15.0, 11.0, 12.0));
+        ms.update_after_close(c(7, 13.0, 5.0, 6.0));
+        let (_, low_label, event) = structure(&ms);
+        assert_eq!(low_label, Some(SwingLabel::HigherLow));
+        assert_eq!(event, Some(StructureEvent::BearishChoch));
+    }
+
+    #[test]
+    fn market_structure_before_close_does_not_commit_break() {
+        let mut ms = MarketStructure::new(1, 1);
+
+        ms.load(&[
+            c(0, 10.0, 8.0, 9.0),
+            c(1, 12.0, 9.0, 11.0),
+            c(2, 11.0, 7.0, 8.0),
+        ]);
+
+        ms.update_before_close(c(3, 14.0, 10.0, 13.0));
+        assert_eq!(structure(&ms).2, Some(StructureEvent::BullishBos));
+
+        ms.update_before_close(c(3, 11.5, 10.0, 11.0));
+        assert_eq!(structure(&ms).2, None);
+
+        ms.update_after_close(c(3, 14.0, 10.0, 13.0));
+        assert_eq!(structure(&ms).2, Some(StructureEvent::BullishBos));
+
+        ms.reset();
+        assert!(!ms.is_ready());
+        assert_eq!(ms.get_last(), None);
+    }
+
+    #[test]
+    fn swing_reset_clears_state() {
+        let mut swings = SwingDetector::new(2, 2);
+
+        swings.load(&[
+            p(0, 10.0, 9.0),
+            p(1, 11.0, 10.0),
+            p(2, 13.0, 11.0),
+            p(3, 12.0, 11.0),
+            p(4, 13.0, 10.5),
+        ]);
+        assert!(swings.is_ready());
+
+        swings.reset();
+
+        assert!(!swings.is_ready());
+        assert_eq!(swings.get_last(), None);
+        assert_eq!(swings.last_high(), None);
+    }
+}
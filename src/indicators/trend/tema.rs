@@ -1,5 +1,5 @@
 use crate::ExpMean;
-use crate::indicators::{Indicator, Price, Value};
+use crate::indicators::{Indicator, Price, Source, Value};
 
 #[derive(Clone, Debug)]
 pub struct Tema {
@@ -8,6 +8,7 @@ pub struct Tema {
     ema2: ExpMean,
     ema3: ExpMean,
     value: Option<f64>,
+    source: Source,
 }
 
 impl Tema {
@@ -19,9 +20,15 @@ impl Tema {
             ema2: ExpMean::new(periods),
             ema3: ExpMean::new(periods),
             value: None,
+            source: Source::default(),
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
     fn update_value(&mut self) {
         self.value = match (
             self.ema1.get_last(),
@@ -36,7 +43,7 @@ impl Tema {
 
 impl Indicator for Tema {
     fn update_after_close(&mut self, price: Price) {
-        self.ema1.update_after_close(price.close);
+        self.ema1.update_after_close(self.source.get(&price));
         if let Some(ema1) = self.ema1.get_last() {
             self.ema2.update_after_close(ema1);
         }
@@ -47,7 +54,7 @@ impl Indicator for Tema {
     }
 
     fn update_before_close(&mut self, price: Price) {
-        self.ema1.update_before_close(price.close);
+        self.ema1.update_before_close(self.source.get(&price));
         if let Some(ema1) = self.ema1.get_last() {
             self.ema2.update_before_close(ema1);
         }
@@ -81,6 +88,10 @@ impl Indicator for Tema {
     fn period(&self) -> u32 {
         self.periods
     }
+
+    fn warmup_bars(&self) -> u32 {
+        3 * self.periods - 2
+    }
 }
 
 impl Default for Tema {
@@ -0,0 +1,184 @@
+use crate::Mean;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct Vortex {
+    periods: u32,
+    vm_plus: Mean,
+    vm_minus: Mean,
+    tr: Mean,
+    prev: Option<Price>,
+    value: Option<(f64, f64)>,
+}
+
+impl Vortex {
+    pub fn new(periods: u32) -> Self {
+        assert!(periods > 1, "Vortex periods must be > 1, got {}", periods);
+        Self {
+            periods,
+            vm_plus: Mean::new(periods),
+            vm_minus: Mean::new(periods),
+            tr: Mean::new(periods),
+            prev: None,
+            value: None,
+        }
+    }
+
+    #[inline]
+    fn movements(prev: Price, price: Price) -> (f64, f64, f64) {
+        (
+            (price.high - prev.low).abs(),
+            (price.low - prev.high).abs(),
+            price.true_range(Some(prev.close)),
+        )
+    }
+
+    fn update_value(&mut self) {
+        if !self.tr.is_ready() {
+            self.value = None;
+            return;
+        }
+
+        let sum_tr = self.tr.sum();
+        self.value = if sum_tr <= f64::EPSILON {
+            Some((0.0, 0.0))
+        } else {
+            Some((self.vm_plus.sum() / sum_tr, self.vm_minus.sum() / sum_tr))
+        };
+    }
+}
+
+impl Indicator for Vortex {
+    fn update_after_close(&mut self, price: Price) {
+        if let Some(prev) = self.prev {
+            let (plus, minus, tr) = Self::movements(prev, price);
+            self.vm_plus.update_after_close(plus);
+            self.vm_minus.update_after_close(minus);
+            self.tr.update_after_close(tr);
+            self.update_value();
+        }
+        self.prev = Some(price);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(prev) = self.prev
+            && self.tr.is_ready()
+        {
+            let (plus, minus, tr) = Self::movements(prev, price);
+            self.vm_plus.update_before_close(plus);
+            self.vm_minus.update_before_close(minus);
+            self.tr.update_before_close(tr);
+            self.update_value();
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value
+            .map(|(plus, minus)| Value::VortexValue { plus, minus })
+    }
+
+    fn reset(&mut self) {
+        self.vm_plus.reset();
+        self.vm_minus.reset();
+        self.tr.reset();
+        self.prev = None;
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.periods + 1
+    }
+}
+
+impl Default for Vortex {
+    fn default() -> Self {
+        Self::new(14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open: close,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn vortex_warms_up_and_computes_expected_lines() {
+        let mut vortex = Vortex::new(2);
+
+        vortex.update_after_close(p(10.0, 8.0, 9.0));
+        vortex.update_after_close(p(12.0, 9.0, 11.0));
+        assert!(!vortex.is_ready());
+
+        vortex.update_after_close(p(13.0, 10.0, 12.0));
+
+        // VM+ = |12-8| + |13-9| = 8, VM- = |9-10| + |10-12| = 3, TR = 3 + 3 = 6
+        match vortex.get_last() {
+            Some(Value::VortexValue { plus, minus }) => {
+                approx_eq(plus, 8.0 / 6.0);
+                approx_eq(minus, 3.0 / 6.0);
+            }
+            _ => panic!("missing vortex"),
+        }
+    }
+
+    #[test]
+    fn vortex_before_close_is_provisional() {
+        let mut vortex = Vortex::new(2);
+        let mut close_only = Vortex::new(2);
+        let candles = [p(10.0, 8.0, 9.0), p(12.0, 9.0, 11.0), p(13.0, 10.0, 12.0)];
+        vortex.load(&candles);
+        close_only.load(&candles);
+
+        let after_close = vortex.get_last();
+        vortex.update_before_close(p(11.0, 7.0, 8.0));
+        assert_ne!(after_close, vortex.get_last());
+
+        vortex.update_before_close(p(14.0, 11.0, 13.0));
+        vortex.update_after_close(p(15.0, 12.0, 14.0));
+        close_only.update_after_close(p(15.0, 12.0, 14.0));
+
+        assert_eq!(vortex.get_last(), close_only.get_last());
+    }
+
+    #[test]
+    fn vortex_reset_clears_state() {
+        let mut vortex = Vortex::new(2);
+
+        vortex.load(&[p(10.0, 8.0, 9.0), p(12.0, 9.0, 11.0), p(13.0, 10.0, 12.0)]);
+        assert!(vortex.is_ready());
+
+        vortex.reset();
+
+        assert!(!vortex.is_ready());
+        assert_eq!(vortex.get_last(), None);
+    }
+}
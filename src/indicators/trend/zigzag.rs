@@ -0,0 +1,506 @@
+use super::SwingPoint;
+use crate::indicators::{Atr, Indicator, Price, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ZigZagThreshold {
+    /// Reversal of `percent` % from the leg's extreme.
+    Percent(f64),
+    /// Reversal of `multiplier * ATR(periods)` from the leg's extreme.
+    Atr { periods: u32, multiplier: f64 },
+}
+
+#[derive(PartialEq, PartialOrd, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ZigZagDirection {
+    Up,
+    Down,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ZigZagPivot {
+    /// `open_time` of the pivot bar.
+    pub time: u64,
+    pub price: f64,
+    /// Direction of the leg this pivot ends: `Up` marks a swing high.
+    pub direction: ZigZagDirection,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LegState {
+    Empty,
+    /// No reversal yet; tracks both extremes since the start.
+    Ranging {
+        high: SwingPoint,
+        low: SwingPoint,
+    },
+    Trending {
+        direction: ZigZagDirection,
+        extreme: SwingPoint,
+    },
+}
+
+impl LegState {
+    fn step(
+        self,
+        price: Price,
+        reversal: impl Fn(f64) -> Option<f64>,
+    ) -> (LegState, Option<ZigZagPivot>) {
+        let high = SwingPoint {
+            time: price.open_time,
+            price: price.high,
+        };
+        let low = SwingPoint {
+            time: price.open_time,
+            price: price.low,
+        };
+        let reversed_down = |extreme: SwingPoint| {
+            reversal(extreme.price).is_some_and(|amount| price.low <= extreme.price - amount)
+        };
+        let reversed_up = |extreme: SwingPoint| {
+            reversal(extreme.price).is_some_and(|amount| price.high >= extreme.price + amount)
+        };
+
+        match self {
+            LegState::Empty => (LegState::Ranging { high, low }, None),
+            LegState::Ranging {
+                high: prev_high,
+                low: prev_low,
+            } => {
+                // The new leg keeps any extreme made after the confirmed pivot.
+                if reversed_down(prev_high) {
+                    let extreme = if prev_low.time > prev_high.time && prev_low.price < low.price {
+                        prev_low
+                    } else {
+                        low
+                    };
+                    (
+                        LegState::Trending {
+                            direction: ZigZagDirection::Down,
+                            extreme,
+                        },
+                        Some(Self::pivot(prev_high, ZigZagDirection::Up)),
+                    )
+                } else if reversed_up(prev_low) {
+                    let extreme = if prev_high.time > prev_low.time && prev_high.price > high.price
+                    {
+                        prev_high
+                    } else {
+                        high
+                    };
+                    (
+                        LegState::Trending {
+                            direction: ZigZagDirection::Up,
+                            extreme,
+                        },
+                        Some(Self::pivot(prev_low, ZigZagDirection::Down)),
+                    )
+                } else {
+                    (
+                        LegState::Ranging {
+                            high: if high.price > prev_high.price {
+                                high
+                            } else {
+                                prev_high
+                            },
+                            low: if low.price < prev_low.price {
+                                low
+                            } else {
+                                prev_low
+                            },
+                        },
+                        None,
+                    )
+                }
+            }
+            // A bar extending the leg is never also counted as its reversal.
+            LegState::Trending {
+                direction: ZigZagDirection::Up,
+                extreme,
+            } => {
+                if high.price > extreme.price {
+                    (
+                        LegState::Trending {
+                            direction: ZigZagDirection::Up,
+                            extreme: high,
+                        },
+                        None,
+                    )
+                } else if reversed_down(extreme) {
+                    (
+                        LegState::Trending {
+                            direction: ZigZagDirection::Down,
+                            extreme: low,
+                        },
+                        Some(Self::pivot(extreme, ZigZagDirection::Up)),
+                    )
+                } else {
+                    (self, None)
+                }
+            }
+            LegState::Trending {
+                direction: ZigZagDirection::Down,
+                extreme,
+            } => {
+                if low.price < extreme.price {
+                    (
+                        LegState::Trending {
+                            direction: ZigZagDirection::Down,
+                            extreme: low,
+                        },
+                        None,
+                    )
+                } else if reversed_up(extreme) {
+                    (
+                        LegState::Trending {
+                            direction: ZigZagDirection::Up,
+                            extreme: high,
+                        },
+                        Some(Self::pivot(extreme, ZigZagDirection::Down)),
+                    )
+                } else {
+                    (self, None)
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn pivot(point: SwingPoint, direction: ZigZagDirection) -> ZigZagPivot {
+        ZigZagPivot {
+            time: point.time,
+            price: point.price,
+            direction,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ZigZag {
+    threshold: ZigZagThreshold,
+    atr: Option<Atr>,
+    confirmed: LegState,
+    last: LegState,
+    pivots: VecDeque<ZigZagPivot>,
+    /// Unbounded unless set with [`ZigZag::with_capacity`].
+    capacity: Option<usize>,
+    pending: Option<ZigZagPivot>,
+}
+
+impl ZigZag {
+    pub fn new(threshold: ZigZagThreshold) -> Self {
+        let atr = match threshold {
+            ZigZagThreshold::Percent(percent) => {
+                assert!(percent > 0.0, "ZigZag percent must be > 0, got {}", percent);
+                None
+            }
+            ZigZagThreshold::Atr {
+                periods,
+                multiplier,
+            } => {
+                assert!(
+                    multiplier > 0.0,
+                    "ZigZag ATR multiplier must be > 0, got {}",
+                    multiplier
+                );
+                Some(Atr::new(periods))
+            }
+        };
+
+        Self {
+            threshold,
+            atr,
+            confirmed: LegState::Empty,
+            last: LegState::Empty,
+            pivots: VecDeque::new(),
+            capacity: None,
+            pending: None,
+        }
+    }
+
+    /// Keeps at most `capacity` confirmed pivots, dropping the oldest first.
+    /// Without it every pivot is kept.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "ZigZag capacity must be > 0");
+        self.capacity = Some(capacity);
+        while self.pivots.len() > capacity {
+            self.pivots.pop_front();
+        }
+        self
+    }
+
+    /// Confirmed pivots, oldest first; only the last `capacity` when one was
+    /// set with [`ZigZag::with_capacity`].
+    #[inline]
+    pub fn pivots(&self) -> &VecDeque<ZigZagPivot> {
+        &self.pivots
+    }
+
+    /// The extreme of the developing leg, including any in-progress bar.
+    pub fn developing(&self) -> Option<ZigZagPivot> {
+        match self.last {
+            LegState::Trending { direction, extreme } => Some(LegState::pivot(extreme, direction)),
+            _ => None,
+        }
+    }
+
+    fn step(&self, price: Price) -> (LegState, Option<ZigZagPivot>) {
+        let atr = self.atr.as_ref().and_then(Atr::get_last_value);
+        let threshold = self.threshold;
+
+        self.confirmed.step(price, |extreme| match threshold {
+            ZigZagThreshold::Percent(percent) => Some(extreme.abs() * percent / 100.0),
+            ZigZagThreshold::Atr { multiplier, .. } => atr.map(|atr| atr * multiplier),
+        })
+    }
+}
+
+impl Indicator for ZigZag {
+    fn update_after_close(&mut self, price: Price) {
+        if let Some(atr) = self.atr.as_mut() {
+            atr.update_after_close(price);
+        }
+
+        let (state, pivot) = self.step(price);
+        self.confirmed = state;
+        self.last = state;
+        self.pending = pivot;
+        if let Some(pivot) = pivot {
+            if Some(self.pivots.len()) == self.capacity {
+                self.pivots.pop_front();
+            }
+            self.pivots.push_back(pivot);
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(atr) = self.atr.as_mut() {
+            atr.update_before_close(price);
+        }
+
+        let (state, pivot) = self.step(price);
+        self.last = state;
+        self.pending = pivot;
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        matches!(self.last, LegState::Trending { .. })
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        let leg = self.developing()?;
+        let pivot = self.pending.or_else(|| self.pivots.back().copied());
+
+        Some(Value::ZigZagValue {
+            pivot: pivot.map(|pivot| pivot.price),
+            extreme: leg.price,
+            is_up: leg.direction == ZigZagDirection::Up,
+            new_pivot: self.pending.is_some(),
+        })
+    }
+
+    fn reset(&mut self) {
+        if let Some(atr) = self.atr.as_mut() {
+            atr.reset();
+        }
+        self.confirmed = LegState::Empty;
+        self.last = LegState::Empty;
+        self.pivots.clear();
+        self.pending = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.atr.as_ref().map_or(1, Atr::period)
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        // A lower bound: the first leg also waits for the threshold move.
+        self.atr.as_ref().map_or(2, Atr::period).max(2)
+    }
+}
+
+impl Default for ZigZag {
+    fn default() -> Self {
+        Self::new(ZigZagThreshold::Percent(5.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(time: u64, high: f64, low: f64) -> Price {
+        Price {
+            open: low,
+            high,
+            low,
+            close: (high + low) / 2.0,
+            open_time: time,
+            close_time: time,
+            vlm: 0.0,
+        }
+    }
+
+    fn leg(zigzag: &ZigZag) -> (Option<f64>, f64, bool, bool) {
+        match zigzag.get_last() {
+            Some(Value::ZigZagValue {
+                pivot,
+                extreme,
+                is_up,
+                new_pivot,
+            }) => (pivot, extreme, is_up, new_pivot),
+            _ => panic!("missing zigzag"),
+        }
+    }
+
+    #[test]
+    fn zigzag_confirms_pivots_on_percent_reversal() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0));
+
+        zigzag.load(&[p(0, 100.0, 98.0), p(1, 104.0, 101.0)]);
+        assert!(!zigzag.is_ready());
+
+        // 10% above the 98 low confirms it and starts an up leg
+        zigzag.update_after_close(p(2, 110.0, 105.0));
+        assert_eq!(leg(&zigzag), (Some(98.0), 110.0, true, true));
+
+        // 10% below the 110 high
+        zigzag.update_after_close(p(3, 105.0, 99.0));
+        assert_eq!(leg(&zigzag), (Some(110.0), 99.0, false, true));
+
+        zigzag.update_after_close(p(4, 100.0, 95.0));
+        assert_eq!(leg(&zigzag), (Some(110.0), 95.0, false, false));
+
+        zigzag.update_after_close(p(5, 106.0, 100.0));
+        assert_eq!(
+            zigzag.pivots(),
+            &[
+                ZigZagPivot {
+                    time: 0,
+                    price: 98.0,
+                    direction: ZigZagDirection::Down
+                },
+                ZigZagPivot {
+                    time: 2,
+                    price: 110.0,
+                    direction: ZigZagDirection::Up
+                },
+                ZigZagPivot {
+                    time: 4,
+                    price: 95.0,
+                    direction: ZigZagDirection::Down
+                },
+            ]
+        );
+        assert_eq!(
+            zigzag.developing(),
+            Some(ZigZagPivot {
+                time: 5,
+                price: 106.0,
+                direction: ZigZagDirection::Up
+            })
+        );
+    }
+
+    #[test]
+    fn zigzag_atr_threshold_waits_for_atr() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Atr {
+            periods: 3,
+            multiplier: 1.0,
+        });
+
+        // A large move is ignored while ATR(3) warms up.
+        zigzag.update_after_close(p(0, 101.0, 99.0));
+        zigzag.update_after_close(p(1, 120.0, 100.0));
+        assert!(!zigzag.is_ready());
+
+        // ATR = (2 + 20 + 9) / 3; the up leg keeps the earlier 120 high.
+        zigzag.update_after_close(p(2, 119.0, 117.0));
+        assert_eq!(zigzag.pivots().len(), 1);
+        assert_eq!(zigzag.pivots()[0].price, 99.0);
+        assert_eq!(
+            zigzag.developing(),
+            Some(ZigZagPivot {
+                time: 1,
+                price: 120.0,
+                direction: ZigZagDirection::Up
+            })
+        );
+    }
+
+    #[test]
+    fn zigzag_before_close_does_not_commit_pivot() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0));
+
+        zigzag.load(&[p(0, 100.0, 98.0), p(1, 104.0, 101.0), p(2, 110.0, 105.0)]);
+
+        zigzag.update_before_close(p(3, 105.0, 99.0));
+        assert_eq!(leg(&zigzag), (Some(110.0), 99.0, false, true));
+        assert_eq!(zigzag.pivots().len(), 1);
+
+        zigzag.update_before_close(p(3, 112.0, 105.0));
+        assert_eq!(zigzag.developing().map(|leg| leg.price), Some(112.0));
+        assert_eq!(zigzag.pivots().len(), 1);
+
+        zigzag.update_after_close(p(3, 108.0, 104.0));
+        assert_eq!(zigzag.pivots().len(), 1);
+        assert_eq!(zigzag.developing().map(|leg| leg.price), Some(110.0));
+    }
+
+    #[test]
+    fn zigzag_capacity_drops_oldest_pivots() {
+        let prices = [
+            p(0, 100.0, 98.0),
+            p(1, 104.0, 101.0),
+            p(2, 110.0, 105.0),
+            p(3, 105.0, 99.0),
+            p(4, 100.0, 95.0),
+            p(5, 106.0, 100.0),
+        ];
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0)).with_capacity(2);
+        let mut unbounded = ZigZag::new(ZigZagThreshold::Percent(10.0));
+
+        zigzag.load(&prices);
+        unbounded.load(&prices);
+
+        let pivots: Vec<f64> = zigzag.pivots().iter().map(|pivot| pivot.price).collect();
+        assert_eq!(pivots, [110.0, 95.0]);
+        assert_eq!(leg(&zigzag).0, Some(95.0));
+        let pivots: Vec<f64> = unbounded.pivots().iter().map(|pivot| pivot.price).collect();
+        assert_eq!(pivots, [98.0, 110.0, 95.0]);
+    }
+
+    #[test]
+    fn zigzag_pivot_round_trips_through_json() {
+        let pivot = ZigZagPivot {
+            time: 2,
+            price: 110.0,
+            direction: ZigZagDirection::Up,
+        };
+
+        let json = serde_json::to_string(&pivot).unwrap();
+        assert_eq!(json, r#"{"time":2,"price":110.0,"direction":"up"}"#);
+        assert_eq!(serde_json::from_str::<ZigZagPivot>(&json).unwrap(), pivot);
+    }
+
+    #[test]
+    fn zigzag_reset_clears_state() {
+        let mut zigzag = ZigZag::new(ZigZagThreshold::Percent(10.0));
+
+        zigzag.load(&[p(0, 100.0, 98.0), p(1, 110.0, 105.0), p(2, 105.0, 99.0)]);
+        assert!(zigzag.is_ready());
+
+        zigzag.reset();
+
+        assert!(!zigzag.is_ready());
+        assert_eq!(zigzag.get_last(), None);
+        assert!(zigzag.pivots().is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Price {
     pub open: f64,
@@ -8,3 +10,151 @@ pub struct Price {
     pub close_time: u64,
     pub vlm: f64,
 }
+
+impl Price {
+    /// True range against the previous close, or the plain candle range for
+    /// the first candle of a series.
+    pub fn true_range(&self, prev_close: Option<f64>) -> f64 {
+        match prev_close {
+            Some(prev_close) => f64::max(
+                self.high - self.low,
+                f64::max(
+                    (self.high - prev_close).abs(),
+                    (self.low - prev_close).abs(),
+                ),
+            ),
+            None => self.high - self.low,
+        }
+    }
+
+    /// A candle carrying `x` as open, high, low and close, keeping this
+    /// candle's times and volume. Used to feed a derived series into an
+    /// indicator that expects prices.
+    #[inline]
+    pub fn with_value(&self, x: f64) -> Price {
+        Price {
+            open: x,
+            high: x,
+            low: x,
+            close: x,
+            ..*self
+        }
+    }
+}
+
+/// Which series a single-series indicator reads from each candle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Source {
+    #[default]
+    Close,
+    Open,
+    High,
+    Low,
+    /// (high + low) / 2
+    Hl2,
+    /// (high + low + close) / 3
+    Hlc3,
+    /// (open + high + low + close) / 4
+    Ohlc4,
+    /// (high + low + 2 * close) / 4
+    Hlcc4,
+    Volume,
+}
+
+impl Source {
+    #[inline]
+    pub fn get(self, price: &Price) -> f64 {
+        match self {
+            Source::Close => price.close,
+            Source::Open => price.open,
+            Source::High => price.high,
+            Source::Low => price.low,
+            Source::Hl2 => (price.high + price.low) / 2.0,
+            Source::Hlc3 => (price.high + price.low + price.close) / 3.0,
+            Source::Ohlc4 => (price.open + price.high + price.low + price.close) / 4.0,
+            Source::Hlcc4 => (price.high + price.low + 2.0 * price.close) / 4.0,
+            Source::Volume => price.vlm,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Source::Close => "close",
+            Source::Open => "open",
+            Source::High => "high",
+            Source::Low => "low",
+            Source::Hl2 => "hl2",
+            Source::Hlc3 => "hlc3",
+            Source::Ohlc4 => "ohlc4",
+            Source::Hlcc4 => "hlcc4",
+            Source::Volume => "volume",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    pub size: f64,
+    pub side: TradeSide,
+}
+
+/// Aggressor-side volume for a single candle.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TradeFlow {
+    pub buy_vlm: f64,
+    pub sell_vlm: f64,
+}
+
+impl TradeFlow {
+    pub fn new(buy_vlm: f64, sell_vlm: f64) -> Self {
+        Self { buy_vlm, sell_vlm }
+    }
+
+    pub fn from_trades(trades: &[Trade]) -> Self {
+        let mut flow = Self::default();
+        for trade in trades {
+            flow.push(*trade);
+        }
+        flow
+    }
+
+    /// Splits `vlm` by where the close sits inside the candle range, for
+    /// feeds that only provide total volume.
+    pub fn estimate(price: &Price) -> Self {
+        let range = price.high - price.low;
+        let buy_ratio = if range <= f64::EPSILON {
+            0.5
+        } else {
+            ((price.close - price.low) / range).clamp(0.0, 1.0)
+        };
+
+        Self {
+            buy_vlm: price.vlm * buy_ratio,
+            sell_vlm: price.vlm * (1.0 - buy_ratio),
+        }
+    }
+
+    pub fn push(&mut self, trade: Trade) {
+        match trade.side {
+            TradeSide::Buy => self.buy_vlm += trade.size,
+            TradeSide::Sell => self.sell_vlm += trade.size,
+        }
+    }
+
+    #[inline]
+    pub fn delta(&self) -> f64 {
+        self.buy_vlm - self.sell_vlm
+    }
+
+    #[inline]
+    pub fn total(&self) -> f64 {
+        self.buy_vlm + self.sell_vlm
+    }
+}
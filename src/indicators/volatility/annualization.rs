@@ -0,0 +1,169 @@
+use crate::indicators::Price;
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const CRYPTO_DAYS_PER_YEAR: f64 = 365.0;
+const EQUITY_DAYS_PER_YEAR: f64 = 252.0;
+const EQUITY_SESSION_SECONDS: f64 = 23_400.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Calendar {
+    /// Trades around the clock, every day of the year.
+    #[default]
+    Crypto,
+    /// 252 sessions of 6.5 hours, no weekends.
+    Equity,
+}
+
+impl Calendar {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Calendar::Crypto => "crypto",
+            Calendar::Equity => "equity",
+        }
+    }
+}
+
+/// Scales per-bar variance to an annualized volatility.
+///
+/// The bar interval is either configured or inferred from the first closed
+/// candle as `close_time - open_time`. Without either, bars are assumed daily.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Annualization {
+    calendar: Calendar,
+    interval_ms: Option<u64>,
+    inferred_ms: Option<u64>,
+}
+
+impl Annualization {
+    pub fn new(calendar: Calendar) -> Self {
+        Self {
+            calendar,
+            interval_ms: None,
+            inferred_ms: None,
+        }
+    }
+
+    pub fn with_interval_ms(mut self, interval_ms: u64) -> Self {
+        self.interval_ms = Some(interval_ms);
+        self
+    }
+
+    #[inline]
+    pub fn calendar(&self) -> Calendar {
+        self.calendar
+    }
+
+    #[inline]
+    pub fn interval_ms(&self) -> Option<u64> {
+        self.interval_ms.or(self.inferred_ms)
+    }
+
+    pub fn periods_per_year(&self) -> f64 {
+        // Exchanges usually stamp close_time one millisecond before the next
+        // open, so round up to whole seconds.
+        let seconds = match self.interval_ms().map(|ms| ms.div_ceil(1000)) {
+            Some(seconds) if seconds > 0 => seconds,
+            _ => SECONDS_PER_DAY,
+        };
+
+        match self.calendar {
+            Calendar::Crypto => CRYPTO_DAYS_PER_YEAR * SECONDS_PER_DAY as f64 / seconds as f64,
+            Calendar::Equity if seconds < SECONDS_PER_DAY => {
+                EQUITY_DAYS_PER_YEAR * EQUITY_SESSION_SECONDS / seconds as f64
+            }
+            Calendar::Equity => {
+                let days = seconds as f64 / SECONDS_PER_DAY as f64;
+                EQUITY_DAYS_PER_YEAR / (days * 5.0 / 7.0).max(1.0)
+            }
+        }
+    }
+
+    /// Converts a per-bar variance into annualized volatility in percent.
+    #[inline]
+    pub fn annualize(&self, variance: f64) -> f64 {
+        (variance.max(0.0) * self.periods_per_year()).sqrt() * 100.0
+    }
+
+    pub(crate) fn observe(&mut self, price: &Price) {
+        if self.interval_ms.is_none() && self.inferred_ms.is_none() {
+            let interval = price.close_time.saturating_sub(price.open_time);
+            if interval > 0 {
+                self.inferred_ms = Some(interval);
+            }
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.inferred_ms = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: u64 = 3_600_000;
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    fn candle(open_time: u64, close_time: u64) -> Price {
+        Price {
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            open_time,
+            close_time,
+            vlm: 0.0,
+        }
+    }
+
+    #[test]
+    fn defaults_to_daily_crypto_bars() {
+        approx_eq(Annualization::default().periods_per_year(), 365.0);
+        approx_eq(
+            Annualization::new(Calendar::Equity).periods_per_year(),
+            252.0,
+        );
+    }
+
+    #[test]
+    fn configured_interval_scales_bars_per_year() {
+        let crypto = Annualization::new(Calendar::Crypto).with_interval_ms(HOUR_MS);
+        approx_eq(crypto.periods_per_year(), 365.0 * 24.0);
+
+        let equity = Annualization::new(Calendar::Equity).with_interval_ms(300_000);
+        approx_eq(equity.periods_per_year(), 252.0 * 78.0);
+
+        let weekly = Annualization::new(Calendar::Equity).with_interval_ms(7 * 24 * HOUR_MS);
+        approx_eq(weekly.periods_per_year(), 252.0 / 5.0);
+    }
+
+    #[test]
+    fn infers_interval_from_first_candle() {
+        let mut annualization = Annualization::default();
+
+        annualization.observe(&candle(0, 0));
+        assert_eq!(annualization.interval_ms(), None);
+
+        annualization.observe(&candle(HOUR_MS, 2 * HOUR_MS - 1));
+        annualization.observe(&candle(0, 24 * HOUR_MS));
+        approx_eq(annualization.periods_per_year(), 365.0 * 24.0);
+
+        annualization.reset();
+        assert_eq!(annualization.interval_ms(), None);
+    }
+
+    #[test]
+    fn configured_interval_wins_over_inference() {
+        let mut annualization = Annualization::default().with_interval_ms(HOUR_MS);
+
+        annualization.observe(&candle(0, 60_000));
+
+        assert_eq!(annualization.interval_ms(), Some(HOUR_MS));
+    }
+}
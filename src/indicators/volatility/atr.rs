@@ -1,4 +1,6 @@
+use crate::indicators::batch::{ColumnReader, fill_columns};
 use crate::indicators::{Indicator, Price, Value};
+use std::borrow::Borrow;
 
 #[derive(Clone, Debug)]
 pub struct Atr {
@@ -25,6 +27,11 @@ impl Atr {
         }
     }
 
+    #[inline]
+    pub fn get_last_value(&self) -> Option<f64> {
+        self.value
+    }
+
     pub fn normalized(&self, price: f64) -> Option<Value> {
         if price.abs() < f64::EPSILON {
             return None;
@@ -40,15 +47,7 @@ impl Indicator for Atr {
     }
 
     fn update_after_close(&mut self, price: Price) {
-        let high = price.high;
-        let low = price.low;
-        let close = price.close;
-
-        let tr = if let Some(prev_close) = self.prev_close {
-            calc_tr(high, low, prev_close)
-        } else {
-            high - low
-        };
+        let tr = price.true_range(self.prev_close);
 
         if self.value.is_none() {
             self.warmup_trs.push(tr);
@@ -58,18 +57,18 @@ impl Indicator for Atr {
                 self.value = Some(initial_atr);
                 self.prev_value = Some(initial_atr);
             }
-        } else if let Some(prev_atr) = self.value {
+        } else if let Some(prev_atr) = self.prev_value {
             let new_atr = (prev_atr * (self.periods as f64 - 1.0) + tr) / self.periods as f64;
             self.value = Some(new_atr);
             self.prev_value = Some(new_atr);
         }
 
-        self.prev_close = Some(close);
+        self.prev_close = Some(price.close);
     }
 
     fn update_before_close(&mut self, price: Price) {
         if let (Some(prev_close), Some(prev_atr)) = (self.prev_close, self.prev_value) {
-            let tr = calc_tr(price.high, price.low, prev_close);
+            let tr = price.true_range(Some(prev_close));
             let provisional_atr =
                 (prev_atr * (self.periods as f64 - 1.0) + tr) / self.periods as f64;
             self.value = Some(provisional_atr);
@@ -79,6 +78,23 @@ impl Indicator for Atr {
     fn get_last(&self) -> Option<Value> {
         self.value.map(Value::AtrValue)
     }
+
+    fn compute_columns<P>(&mut self, price_data: P, fields: &[&str]) -> Vec<Vec<f64>>
+    where
+        P: IntoIterator,
+        P::Item: Borrow<Price>,
+    {
+        let readers: Vec<ColumnReader<Self>> = fields
+            .iter()
+            .map(|field| -> ColumnReader<Self> {
+                match *field {
+                    "value" => Atr::get_last_value,
+                    _ => |_| None,
+                }
+            })
+            .collect();
+        fill_columns(self, price_data, &readers)
+    }
     fn load(&mut self, price_data: &[Price]) {
         for p in price_data {
             self.update_after_close(*p);
@@ -97,13 +113,6 @@ impl Indicator for Atr {
     }
 }
 
-fn calc_tr(high: f64, low: f64, prev_close: f64) -> f64 {
-    f64::max(
-        high - low,
-        f64::max((high - prev_close).abs(), (low - prev_close).abs()),
-    )
-}
-
 impl Default for Atr {
     fn default() -> Self {
         Atr::new(14)
@@ -151,7 +160,7 @@ mod tests {
         atr.update_after_close(p(14.0, 7.0, 11.0));
 
         atr.update_after_close(p(16.0, 8.0, 15.0));
-        let tr = calc_tr(16.0, 8.0, 11.0);
+        let tr = p(16.0, 8.0, 15.0).true_range(Some(11.0));
         let expected = (6.0 * 2.0 + tr) / 3.0;
 
         assert_eq!(atr.get_last(), Some(Value::AtrValue(expected)));
@@ -174,6 +183,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_close_ignores_provisional_value() {
+        let mut atr = Atr::new(3);
+
+        atr.update_after_close(p(10.0, 5.0, 8.0));
+        atr.update_after_close(p(12.0, 6.0, 10.0));
+        atr.update_after_close(p(14.0, 7.0, 11.0));
+
+        atr.update_before_close(p(30.0, 5.0, 20.0));
+        atr.update_after_close(p(16.0, 8.0, 15.0));
+
+        let tr = p(16.0, 8.0, 15.0).true_range(Some(11.0));
+        assert_eq!(atr.get_last_value(), Some((6.0 * 2.0 + tr) / 3.0));
+    }
+
     #[test]
     fn test_reset() {
         let mut atr = Atr::new(3);
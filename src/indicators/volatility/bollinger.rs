@@ -1,11 +1,14 @@
 use crate::Mean;
-use crate::indicators::{Indicator, Price, Value};
+use crate::indicators::batch::{ColumnReader, fill_columns};
+use crate::indicators::{Indicator, Price, Source, Value};
+use std::borrow::Borrow;
 
 #[derive(Clone, Debug)]
 pub struct BollingerBands {
     periods: u32,
     std_multiplier: f64,
     mean: Mean,
+    source: Source,
 }
 
 impl BollingerBands {
@@ -25,9 +28,15 @@ impl BollingerBands {
             periods,
             std_multiplier,
             mean: Mean::new(periods),
+            source: Source::default(),
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
     fn width(upper: f64, lower: f64, mid: f64) -> f64 {
         if mid.abs() <= f64::EPSILON {
             0.0
@@ -54,11 +63,11 @@ impl BollingerBands {
 
 impl Indicator for BollingerBands {
     fn update_after_close(&mut self, price: Price) {
-        self.mean.update_after_close(price.close);
+        self.mean.update_after_close(self.source.get(&price));
     }
 
     fn update_before_close(&mut self, price: Price) {
-        self.mean.update_before_close(price.close);
+        self.mean.update_before_close(self.source.get(&price));
     }
 
     fn load(&mut self, price_data: &[Price]) {
@@ -81,6 +90,26 @@ impl Indicator for BollingerBands {
             })
     }
 
+    fn compute_columns<P>(&mut self, price_data: P, fields: &[&str]) -> Vec<Vec<f64>>
+    where
+        P: IntoIterator,
+        P::Item: Borrow<Price>,
+    {
+        let readers: Vec<ColumnReader<Self>> = fields
+            .iter()
+            .map(|field| -> ColumnReader<Self> {
+                match *field {
+                    "upper" => |bb| bb.get_bands().map(|(upper, ..)| upper),
+                    "mid" => |bb| bb.mean.get_last(),
+                    "lower" => |bb| bb.get_bands().map(|(_, _, lower, _)| lower),
+                    "width" => |bb| bb.get_bands().map(|(.., width)| width),
+                    _ => |_| None,
+                }
+            })
+            .collect();
+        fill_columns(self, price_data, &readers)
+    }
+
     fn reset(&mut self) {
         self.mean.reset();
     }
@@ -0,0 +1,199 @@
+use crate::ExpMean;
+use crate::indicators::{Indicator, Price, Value};
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug)]
+pub struct ChaikinVolatility {
+    roc_period: u32,
+    ema: ExpMean,
+    averages: VecDeque<f64>,
+    value: Option<f64>,
+    in_candle: bool,
+}
+
+impl ChaikinVolatility {
+    pub fn new(ema_period: u32, roc_period: u32) -> Self {
+        assert!(
+            ema_period > 1,
+            "Chaikin Volatility EMA period must be > 1, got {}",
+            ema_period
+        );
+        assert!(
+            roc_period > 0,
+            "Chaikin Volatility ROC period must be > 0, got {}",
+            roc_period
+        );
+        Self {
+            roc_period,
+            ema: ExpMean::new(ema_period),
+            averages: VecDeque::with_capacity(roc_period as usize + 1),
+            value: None,
+            in_candle: true,
+        }
+    }
+
+    #[inline]
+    fn is_full(&self) -> bool {
+        self.averages.len() == self.roc_period as usize + 1
+    }
+
+    fn compute(&mut self) {
+        if !self.is_full() {
+            self.value = None;
+            return;
+        }
+
+        let first = self.averages.front().copied().unwrap();
+        let last = self.averages.back().copied().unwrap();
+
+        // A flat range leaves nothing to compare against.
+        self.value = if first.abs() <= f64::EPSILON {
+            Some(0.0)
+        } else {
+            Some(((last / first) - 1.0) * 100.0)
+        };
+    }
+}
+
+impl Indicator for ChaikinVolatility {
+    fn update_after_close(&mut self, price: Price) {
+        self.ema.update_after_close(price.high - price.low);
+        let Some(average) = self.ema.get_last() else {
+            return;
+        };
+
+        if self.is_full() {
+            if self.in_candle {
+                self.averages.pop_front();
+            } else {
+                self.averages.pop_back();
+            }
+        }
+
+        self.averages.push_back(average);
+        self.compute();
+        self.in_candle = true;
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.ema.update_before_close(price.high - price.low);
+        if !self.is_full() {
+            return;
+        }
+        let Some(average) = self.ema.get_last() else {
+            return;
+        };
+
+        if self.in_candle {
+            self.averages.pop_front();
+            self.in_candle = false;
+        } else {
+            self.averages.pop_back();
+        }
+
+        self.averages.push_back(average);
+        self.compute();
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::ChaikinVolatilityValue)
+    }
+
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.averages.clear();
+        self.value = None;
+        self.in_candle = true;
+    }
+
+    fn period(&self) -> u32 {
+        self.ema.period() + self.roc_period
+    }
+}
+
+impl Default for ChaikinVolatility {
+    fn default() -> Self {
+        Self::new(10, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64) -> Price {
+        Price {
+            open: low,
+            high,
+            low,
+            close: high,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn chaikin_volatility_compares_range_ema() {
+        let mut cv = ChaikinVolatility::new(2, 1);
+
+        cv.update_after_close(p(12.0, 10.0));
+        cv.update_after_close(p(14.0, 10.0));
+        assert!(!cv.is_ready());
+
+        cv.update_after_close(p(15.0, 10.0));
+
+        // EMA seeds at 3, then moves to 2/3 * 5 + 1/3 * 3
+        let seed = 3.0;
+        let next = (2.0 / 3.0) * 5.0 + seed / 3.0;
+        match cv.get_last() {
+            Some(Value::ChaikinVolatilityValue(value)) => {
+                approx_eq(value, (next / seed - 1.0) * 100.0)
+            }
+            _ => panic!("missing chaikin volatility"),
+        }
+        assert_eq!(cv.period(), 3);
+    }
+
+    #[test]
+    fn chaikin_volatility_before_close_is_provisional() {
+        let mut cv = ChaikinVolatility::new(2, 1);
+
+        cv.load(&[p(12.0, 10.0), p(14.0, 10.0), p(15.0, 10.0)]);
+
+        let after_close = cv.get_last();
+        cv.update_before_close(p(11.0, 10.0));
+        let provisional = cv.get_last();
+        assert_ne!(after_close, provisional);
+
+        cv.update_before_close(p(20.0, 10.0));
+        assert_ne!(provisional, cv.get_last());
+    }
+
+    #[test]
+    fn chaikin_volatility_reset_clears_state() {
+        let mut cv = ChaikinVolatility::new(2, 1);
+
+        cv.load(&[p(12.0, 10.0), p(14.0, 10.0), p(15.0, 10.0)]);
+        assert!(cv.is_ready());
+
+        cv.reset();
+
+        assert!(!cv.is_ready());
+        assert_eq!(cv.get_last(), None);
+    }
+}
@@ -0,0 +1,191 @@
+use super::Atr;
+use super::trailing_stop::StopRatchet;
+use crate::RollingExtremum;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct ChandelierExit {
+    multiplier: f64,
+    atr: Atr,
+    highest: RollingExtremum,
+    lowest: RollingExtremum,
+    ratchet: StopRatchet,
+}
+
+impl ChandelierExit {
+    pub fn new(periods: u32, multiplier: f64) -> Self {
+        assert!(
+            periods > 0,
+            "Chandelier periods must be > 0, got {}",
+            periods
+        );
+        assert!(
+            multiplier > 0.0,
+            "Chandelier multiplier must be > 0, got {}",
+            multiplier
+        );
+        Self {
+            multiplier,
+            atr: Atr::new(periods),
+            highest: RollingExtremum::max(periods),
+            lowest: RollingExtremum::min(periods),
+            ratchet: StopRatchet::default(),
+        }
+    }
+
+    fn candidates(&self) -> Option<(f64, f64)> {
+        match (
+            self.atr.get_last_value(),
+            self.highest.get_last(),
+            self.lowest.get_last(),
+        ) {
+            (Some(atr), Some(highest), Some(lowest)) => {
+                let offset = self.multiplier * atr;
+                Some((highest - offset, lowest + offset))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Indicator for ChandelierExit {
+    fn update_after_close(&mut self, price: Price) {
+        self.atr.update_after_close(price);
+        self.highest.update_after_close(price.high);
+        self.lowest.update_after_close(price.low);
+        if let Some((long, short)) = self.candidates() {
+            self.ratchet.update_after_close(long, short, price.close);
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.atr.update_before_close(price);
+        self.highest.update_before_close(price.high);
+        self.lowest.update_before_close(price.low);
+        if let Some((long, short)) = self.candidates() {
+            self.ratchet.update_before_close(long, short, price.close);
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ratchet.get_last().is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.ratchet
+            .get_last()
+            .map(|state| Value::ChandelierExitValue {
+                long_stop: state.long_stop,
+                short_stop: state.short_stop,
+                is_long: state.is_long,
+                stop_hit: state.stop_hit,
+            })
+    }
+
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.highest.reset();
+        self.lowest.reset();
+        self.ratchet.reset();
+    }
+
+    fn period(&self) -> u32 {
+        self.atr.period()
+    }
+}
+
+impl Default for ChandelierExit {
+    fn default() -> Self {
+        Self::new(22, 3.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open: close,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn state(chandelier: &ChandelierExit) -> (f64, f64, bool, bool) {
+        match chandelier.get_last() {
+            Some(Value::ChandelierExitValue {
+                long_stop,
+                short_stop,
+                is_long,
+                stop_hit,
+            }) => (long_stop, short_stop, is_long, stop_hit),
+            _ => panic!("missing chandelier exit"),
+        }
+    }
+
+    #[test]
+    fn chandelier_hangs_from_extremes() {
+        let mut chandelier = ChandelierExit::new(2, 1.0);
+
+        chandelier.update_after_close(p(11.0, 9.0, 10.0));
+        assert!(!chandelier.is_ready());
+
+        chandelier.update_after_close(p(14.0, 10.0, 13.0));
+
+        // ATR = (2 + 4) / 2, highest high 14, lowest low 9
+        assert_eq!(state(&chandelier), (11.0, 12.0, true, false));
+    }
+
+    #[test]
+    fn chandelier_flips_short_on_breakdown() {
+        let mut chandelier = ChandelierExit::new(2, 1.0);
+
+        chandelier.load(&[p(11.0, 9.0, 10.0), p(14.0, 10.0, 13.0)]);
+        chandelier.update_after_close(p(13.0, 8.0, 9.0));
+
+        let (long_stop, _, is_long, stop_hit) = state(&chandelier);
+        assert_eq!(long_stop, 11.0);
+        assert!(!is_long);
+        assert!(stop_hit);
+    }
+
+    #[test]
+    fn chandelier_before_close_holds_ratchet() {
+        let mut chandelier = ChandelierExit::new(2, 1.0);
+
+        chandelier.load(&[p(11.0, 9.0, 10.0), p(14.0, 10.0, 13.0)]);
+        let after_close = chandelier.get_last();
+
+        chandelier.update_before_close(p(13.0, 8.0, 9.0));
+        assert_ne!(after_close, chandelier.get_last());
+        assert!(state(&chandelier).3);
+
+        chandelier.update_before_close(p(14.0, 12.0, 13.5));
+        let (_, _, is_long, stop_hit) = state(&chandelier);
+        assert!(is_long && !stop_hit);
+    }
+
+    #[test]
+    fn chandelier_reset_clears_state() {
+        let mut chandelier = ChandelierExit::new(2, 1.0);
+
+        chandelier.load(&[p(11.0, 9.0, 10.0), p(14.0, 10.0, 13.0)]);
+        assert!(chandelier.is_ready());
+
+        chandelier.reset();
+
+        assert!(!chandelier.is_ready());
+        assert_eq!(chandelier.get_last(), None);
+    }
+}
@@ -0,0 +1,165 @@
+use super::annualization::Annualization;
+use super::log_ratio;
+use crate::Mean;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct GarmanKlass {
+    periods: u32,
+    mean: Mean,
+    annualization: Annualization,
+    value: Option<f64>,
+}
+
+impl GarmanKlass {
+    pub fn new(periods: u32) -> Self {
+        assert!(
+            periods > 1,
+            "Garman-Klass periods must be > 1, got {}",
+            periods
+        );
+        Self {
+            periods,
+            mean: Mean::new(periods),
+            annualization: Annualization::default(),
+            value: None,
+        }
+    }
+
+    pub fn with_annualization(mut self, annualization: Annualization) -> Self {
+        self.annualization = annualization;
+        self.update_value();
+        self
+    }
+
+    #[inline]
+    fn term(price: Price) -> Option<f64> {
+        let hl = log_ratio(price.high, price.low)?;
+        let co = log_ratio(price.close, price.open)?;
+        Some(0.5 * hl * hl - (2.0 * std::f64::consts::LN_2 - 1.0) * co * co)
+    }
+
+    fn update_value(&mut self) {
+        self.value = if self.mean.is_ready() {
+            Some(
+                self.annualization
+                    .annualize(self.mean.sum() / self.periods as f64),
+            )
+        } else {
+            None
+        };
+    }
+}
+
+impl Indicator for GarmanKlass {
+    fn update_after_close(&mut self, price: Price) {
+        self.annualization.observe(&price);
+        if let Some(term) = Self::term(price) {
+            self.mean.update_after_close(term);
+            self.update_value();
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(term) = Self::term(price) {
+            self.mean.update_before_close(term);
+            self.update_value();
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::GarmanKlassValue)
+    }
+
+    fn reset(&mut self) {
+        self.annualization.reset();
+        self.mean.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+}
+
+impl Default for GarmanKlass {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(open: f64, high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn garman_klass_combines_range_and_body() {
+        let mut gk = GarmanKlass::new(2);
+
+        gk.update_after_close(p(100.0, 110.0, 100.0, 105.0));
+        assert!(!gk.is_ready());
+
+        gk.update_after_close(p(105.0, 110.0, 100.0, 105.0));
+
+        let k = 2.0 * std::f64::consts::LN_2 - 1.0;
+        let hl = 1.1f64.ln().powi(2);
+        let variance = (0.5 * hl - k * 1.05f64.ln().powi(2) + 0.5 * hl) / 2.0;
+        match gk.get_last() {
+            Some(Value::GarmanKlassValue(value)) => {
+                approx_eq(value, (variance * 365.0).sqrt() * 100.0)
+            }
+            _ => panic!("missing garman-klass"),
+        }
+    }
+
+    #[test]
+    fn garman_klass_before_close_is_provisional() {
+        let mut gk = GarmanKlass::new(2);
+
+        gk.load(&[p(100.0, 110.0, 100.0, 105.0), p(105.0, 110.0, 100.0, 105.0)]);
+
+        let after_close = gk.get_last();
+        gk.update_before_close(p(105.0, 120.0, 95.0, 118.0));
+
+        assert_ne!(after_close, gk.get_last());
+    }
+
+    #[test]
+    fn garman_klass_reset_clears_state() {
+        let mut gk = GarmanKlass::new(2);
+
+        gk.load(&[p(100.0, 110.0, 100.0, 105.0), p(105.0, 110.0, 100.0, 105.0)]);
+        assert!(gk.is_ready());
+
+        gk.reset();
+
+        assert!(!gk.is_ready());
+        assert_eq!(gk.get_last(), None);
+    }
+}
@@ -1,50 +1,73 @@
-use crate::StdDev;
-use crate::indicators::{Indicator, Price, Value};
+use super::annualization::Annualization;
+use super::log_ratio;
+use crate::indicators::{Indicator, Price, Source, Value};
+use crate::{StdDev, VarianceMode};
 
 #[derive(Clone, Debug)]
 pub struct HistVolatility {
     periods: u32,
     prev_close: Option<f64>,
     stddev: StdDev,
+    annualization: Annualization,
     value: Option<f64>,
+    source: Source,
 }
 
 impl HistVolatility {
-    const ANNUALIZATION_DAYS: f64 = 365.0;
-
     pub fn new(periods: u32) -> Self {
         assert!(periods > 1, "HV periods must be > 1");
         Self {
             periods,
             prev_close: None,
             stddev: StdDev::new(periods),
+            annualization: Annualization::default(),
             value: None,
+            source: Source::default(),
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: VarianceMode) -> Self {
+        self.stddev = self.stddev.with_mode(mode);
+        self.update_value();
+        self
+    }
+
+    pub fn with_annualization(mut self, annualization: Annualization) -> Self {
+        self.annualization = annualization;
+        self.update_value();
+        self
+    }
+
     fn update_value(&mut self) {
         self.value = self
             .stddev
-            .get_last_value()
-            .map(|v| v * Self::ANNUALIZATION_DAYS.sqrt() * 100.0);
+            .get_variance()
+            .map(|v| self.annualization.annualize(v));
     }
 }
 
 impl Indicator for HistVolatility {
     fn update_after_close(&mut self, price: Price) {
-        if let Some(prev) = self.prev_close {
-            let r = (price.close / prev).ln();
+        self.annualization.observe(&price);
+        if let Some(prev) = self.prev_close
+            && let Some(r) = log_ratio(self.source.get(&price), prev)
+        {
             self.stddev.update_after_close_value(r);
             self.update_value();
         }
-        self.prev_close = Some(price.close);
+        self.prev_close = Some(self.source.get(&price));
     }
 
     fn update_before_close(&mut self, price: Price) {
         if let Some(prev) = self.prev_close
             && self.stddev.is_ready()
+            && let Some(provisional) = log_ratio(self.source.get(&price), prev)
         {
-            let provisional = (price.close / prev).ln();
             self.stddev.update_before_close_value(provisional);
             self.update_value();
         }
@@ -59,6 +82,7 @@ impl Indicator for HistVolatility {
     }
 
     fn reset(&mut self) {
+        self.annualization.reset();
         self.prev_close = None;
         self.stddev.reset();
         self.value = None;
@@ -73,6 +97,10 @@ impl Indicator for HistVolatility {
     fn period(&self) -> u32 {
         self.periods
     }
+
+    fn warmup_bars(&self) -> u32 {
+        self.periods + 1
+    }
 }
 
 impl Default for HistVolatility {
@@ -84,6 +112,7 @@ impl Default for HistVolatility {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::volatility::Calendar;
     use crate::indicators::{Indicator, Price, Value};
 
     fn p(c: f64) -> Price {
@@ -180,6 +209,48 @@ mod tests {
         assert_ne!(after_close, provisional);
     }
 
+    #[test]
+    fn hv_annualizes_by_inferred_interval() {
+        let hour = 3_600_000;
+        let closes = [100.0, 101.0, 100.5, 102.0];
+        let mut daily = HistVolatility::new(3);
+        let mut hourly = HistVolatility::new(3);
+
+        for (i, close) in closes.into_iter().enumerate() {
+            daily.update_after_close(p(close));
+            hourly.update_after_close(Price {
+                open_time: i as u64 * hour,
+                close_time: (i as u64 + 1) * hour - 1,
+                ..p(close)
+            });
+        }
+
+        match (daily.get_last(), hourly.get_last()) {
+            (Some(Value::HistVolatilityValue(d)), Some(Value::HistVolatilityValue(h))) => {
+                assert!((h / d - 24f64.sqrt()).abs() < 1e-9)
+            }
+            _ => panic!("missing hv"),
+        }
+    }
+
+    #[test]
+    fn hv_equity_calendar_uses_trading_days() {
+        let closes = [p(100.0), p(101.0), p(100.5), p(102.0)];
+        let mut crypto = HistVolatility::new(3);
+        let mut equity = HistVolatility::new(3)
+            .with_annualization(Annualization::new(Calendar::Equity).with_interval_ms(86_400_000));
+
+        crypto.load(&closes);
+        equity.load(&closes);
+
+        match (crypto.get_last(), equity.get_last()) {
+            (Some(Value::HistVolatilityValue(c)), Some(Value::HistVolatilityValue(e))) => {
+                assert!((e / c - (252.0f64 / 365.0).sqrt()).abs() < 1e-9)
+            }
+            _ => panic!("missing hv"),
+        }
+    }
+
     #[test]
     fn hv_reset_clears_state() {
         let mut hv = HistVolatility::new(3);
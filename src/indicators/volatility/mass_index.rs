@@ -0,0 +1,181 @@
+use crate::indicators::{Indicator, Price, Value};
+use crate::{ExpMean, Mean};
+
+#[derive(Clone, Debug)]
+pub struct MassIndex {
+    single: ExpMean,
+    double: ExpMean,
+    ratios: Mean,
+    value: Option<f64>,
+}
+
+impl MassIndex {
+    pub fn new(ema_period: u32, sum_period: u32) -> Self {
+        assert!(
+            ema_period > 1,
+            "Mass Index EMA period must be > 1, got {}",
+            ema_period
+        );
+        assert!(
+            sum_period > 0,
+            "Mass Index sum period must be > 0, got {}",
+            sum_period
+        );
+        Self {
+            single: ExpMean::new(ema_period),
+            double: ExpMean::new(ema_period),
+            ratios: Mean::new(sum_period),
+            value: None,
+        }
+    }
+
+    #[inline]
+    fn ratio(single: f64, double: f64) -> f64 {
+        // A flat range drives both averages to zero; treat that as no expansion.
+        if double.abs() <= f64::EPSILON {
+            1.0
+        } else {
+            single / double
+        }
+    }
+
+    fn update_value(&mut self) {
+        self.value = if self.ratios.is_ready() {
+            Some(self.ratios.sum())
+        } else {
+            None
+        };
+    }
+}
+
+impl Indicator for MassIndex {
+    fn update_after_close(&mut self, price: Price) {
+        self.single.update_after_close(price.high - price.low);
+        if let Some(single) = self.single.get_last() {
+            self.double.update_after_close(single);
+            if let Some(double) = self.double.get_last() {
+                self.ratios.update_after_close(Self::ratio(single, double));
+                self.update_value();
+            }
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.single.update_before_close(price.high - price.low);
+        if let Some(single) = self.single.get_last() {
+            self.double.update_before_close(single);
+            if let Some(double) = self.double.get_last() {
+                self.ratios.update_before_close(Self::ratio(single, double));
+                self.update_value();
+            }
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::MassIndexValue)
+    }
+
+    fn reset(&mut self) {
+        self.single.reset();
+        self.double.reset();
+        self.ratios.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        2 * self.single.period() + self.ratios.period() - 2
+    }
+}
+
+impl Default for MassIndex {
+    fn default() -> Self {
+        Self::new(9, 25)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64) -> Price {
+        Price {
+            open: low,
+            high,
+            low,
+            close: high,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn mass_index_constant_range_sums_unit_ratios() {
+        let mut mass = MassIndex::new(2, 3);
+
+        for _ in 0..4 {
+            mass.update_after_close(p(12.0, 10.0));
+        }
+        assert!(!mass.is_ready());
+
+        mass.update_after_close(p(12.0, 10.0));
+
+        match mass.get_last() {
+            Some(Value::MassIndexValue(value)) => approx_eq(value, 3.0),
+            _ => panic!("missing mass index"),
+        }
+        assert_eq!(mass.period(), 5);
+    }
+
+    #[test]
+    fn mass_index_bulges_on_range_expansion() {
+        let mut mass = MassIndex::new(2, 3);
+
+        mass.load(&[p(12.0, 10.0); 6]);
+        mass.update_after_close(p(16.0, 10.0));
+
+        match mass.get_last() {
+            Some(Value::MassIndexValue(value)) => assert!(value > 3.0),
+            _ => panic!("missing mass index"),
+        }
+    }
+
+    #[test]
+    fn mass_index_before_close_is_provisional() {
+        let mut mass = MassIndex::new(2, 3);
+
+        mass.load(&[p(12.0, 10.0); 6]);
+
+        let after_close = mass.get_last();
+        mass.update_before_close(p(16.0, 10.0));
+
+        assert_ne!(after_close, mass.get_last());
+    }
+
+    #[test]
+    fn mass_index_reset_clears_state() {
+        let mut mass = MassIndex::new(2, 3);
+
+        mass.load(&[p(12.0, 10.0); 6]);
+        assert!(mass.is_ready());
+
+        mass.reset();
+
+        assert!(!mass.is_ready());
+        assert_eq!(mass.get_last(), None);
+    }
+}
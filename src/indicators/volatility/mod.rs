@@ -1,7 +1,36 @@
+mod annualization;
 mod atr;
 mod bollinger;
+mod chaikin_volatility;
+mod chandelier;
+mod garman_klass;
 mod hist_vol;
+mod mass_index;
+mod parkinson;
+mod rogers_satchell;
+mod trailing_stop;
+mod ulcer;
+mod yang_zhang;
 
+pub use annualization::{Annualization, Calendar};
 pub use atr::Atr;
 pub use bollinger::BollingerBands;
+pub use chaikin_volatility::ChaikinVolatility;
+pub use chandelier::ChandelierExit;
+pub use garman_klass::GarmanKlass;
 pub use hist_vol::HistVolatility;
+pub use mass_index::MassIndex;
+pub use parkinson::Parkinson;
+pub use rogers_satchell::RogersSatchell;
+pub use trailing_stop::AtrTrailingStop;
+pub use ulcer::UlcerIndex;
+pub use yang_zhang::YangZhang;
+
+/// `ln(a / b)`, or `None` unless both prices are positive and the ratio is
+/// finite.
+#[inline]
+pub(crate) fn log_ratio(a: f64, b: f64) -> Option<f64> {
+    (a > 0.0 && b > 0.0)
+        .then(|| (a / b).ln())
+        .filter(|r| r.is_finite())
+}
@@ -0,0 +1,174 @@
+use super::annualization::Annualization;
+use super::log_ratio;
+use crate::Mean;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct Parkinson {
+    periods: u32,
+    mean: Mean,
+    annualization: Annualization,
+    value: Option<f64>,
+}
+
+impl Parkinson {
+    pub fn new(periods: u32) -> Self {
+        assert!(
+            periods > 1,
+            "Parkinson periods must be > 1, got {}",
+            periods
+        );
+        Self {
+            periods,
+            mean: Mean::new(periods),
+            annualization: Annualization::default(),
+            value: None,
+        }
+    }
+
+    pub fn with_annualization(mut self, annualization: Annualization) -> Self {
+        self.annualization = annualization;
+        self.update_value();
+        self
+    }
+
+    #[inline]
+    fn term(price: Price) -> Option<f64> {
+        log_ratio(price.high, price.low).map(|hl| hl * hl)
+    }
+
+    fn update_value(&mut self) {
+        self.value = if self.mean.is_ready() {
+            let scale = 4.0 * std::f64::consts::LN_2 * self.periods as f64;
+            Some(self.annualization.annualize(self.mean.sum() / scale))
+        } else {
+            None
+        };
+    }
+}
+
+impl Indicator for Parkinson {
+    fn update_after_close(&mut self, price: Price) {
+        self.annualization.observe(&price);
+        if let Some(term) = Self::term(price) {
+            self.mean.update_after_close(term);
+            self.update_value();
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(term) = Self::term(price) {
+            self.mean.update_before_close(term);
+            self.update_value();
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::ParkinsonValue)
+    }
+
+    fn reset(&mut self) {
+        self.annualization.reset();
+        self.mean.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+}
+
+impl Default for Parkinson {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64) -> Price {
+        Price {
+            open: low,
+            high,
+            low,
+            close: high,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn parkinson_uses_high_low_range() {
+        let mut pk = Parkinson::new(2);
+
+        pk.update_after_close(p(110.0, 100.0));
+        assert!(!pk.is_ready());
+
+        pk.update_after_close(p(121.0, 110.0));
+
+        let variance = 1.1f64.ln().powi(2) / (4.0 * std::f64::consts::LN_2);
+        match pk.get_last() {
+            Some(Value::ParkinsonValue(value)) => {
+                approx_eq(value, (variance * 365.0).sqrt() * 100.0)
+            }
+            _ => panic!("missing parkinson"),
+        }
+    }
+
+    #[test]
+    fn parkinson_skips_non_positive_prices() {
+        let mut pk = Parkinson::new(2);
+
+        pk.load(&[p(110.0, 100.0), p(110.0, 0.0), p(121.0, 110.0)]);
+
+        let variance = 1.1f64.ln().powi(2) / (4.0 * std::f64::consts::LN_2);
+        match pk.get_last() {
+            Some(Value::ParkinsonValue(value)) => {
+                approx_eq(value, (variance * 365.0).sqrt() * 100.0)
+            }
+            _ => panic!("missing parkinson"),
+        }
+    }
+
+    #[test]
+    fn parkinson_before_close_is_provisional() {
+        let mut pk = Parkinson::new(2);
+
+        pk.load(&[p(110.0, 100.0), p(121.0, 110.0)]);
+
+        let after_close = pk.get_last();
+        pk.update_before_close(p(130.0, 100.0));
+
+        assert_ne!(after_close, pk.get_last());
+    }
+
+    #[test]
+    fn parkinson_reset_clears_state() {
+        let mut pk = Parkinson::new(2);
+
+        pk.load(&[p(110.0, 100.0), p(121.0, 110.0)]);
+        assert!(pk.is_ready());
+
+        pk.reset();
+
+        assert!(!pk.is_ready());
+        assert_eq!(pk.get_last(), None);
+    }
+}
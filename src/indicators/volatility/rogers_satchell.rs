@@ -0,0 +1,178 @@
+use super::annualization::Annualization;
+use super::log_ratio;
+use crate::Mean;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct RogersSatchell {
+    periods: u32,
+    mean: Mean,
+    annualization: Annualization,
+    value: Option<f64>,
+}
+
+impl RogersSatchell {
+    pub fn new(periods: u32) -> Self {
+        assert!(
+            periods > 1,
+            "Rogers-Satchell periods must be > 1, got {}",
+            periods
+        );
+        Self {
+            periods,
+            mean: Mean::new(periods),
+            annualization: Annualization::default(),
+            value: None,
+        }
+    }
+
+    pub fn with_annualization(mut self, annualization: Annualization) -> Self {
+        self.annualization = annualization;
+        self.update_value();
+        self
+    }
+
+    #[inline]
+    pub(crate) fn term(price: Price) -> Option<f64> {
+        let hc = log_ratio(price.high, price.close)?;
+        let ho = log_ratio(price.high, price.open)?;
+        let lc = log_ratio(price.low, price.close)?;
+        let lo = log_ratio(price.low, price.open)?;
+        Some(hc * ho + lc * lo)
+    }
+
+    fn update_value(&mut self) {
+        self.value = if self.mean.is_ready() {
+            Some(
+                self.annualization
+                    .annualize(self.mean.sum() / self.periods as f64),
+            )
+        } else {
+            None
+        };
+    }
+}
+
+impl Indicator for RogersSatchell {
+    fn update_after_close(&mut self, price: Price) {
+        self.annualization.observe(&price);
+        if let Some(term) = Self::term(price) {
+            self.mean.update_after_close(term);
+            self.update_value();
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(term) = Self::term(price) {
+            self.mean.update_before_close(term);
+            self.update_value();
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::RogersSatchellValue)
+    }
+
+    fn reset(&mut self) {
+        self.annualization.reset();
+        self.mean.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+}
+
+impl Default for RogersSatchell {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(open: f64, high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn rogers_satchell_ignores_pure_trend() {
+        let mut rs = RogersSatchell::new(2);
+
+        // Opening at the low and closing at the high leaves no drift-free variance.
+        rs.load(&[p(100.0, 110.0, 100.0, 110.0), p(110.0, 121.0, 110.0, 121.0)]);
+
+        match rs.get_last() {
+            Some(Value::RogersSatchellValue(value)) => approx_eq(value, 0.0),
+            _ => panic!("missing rogers-satchell"),
+        }
+    }
+
+    #[test]
+    fn rogers_satchell_computes_expected_value() {
+        let mut rs = RogersSatchell::new(2);
+
+        rs.update_after_close(p(100.0, 110.0, 90.0, 100.0));
+        assert!(!rs.is_ready());
+
+        rs.update_after_close(p(100.0, 110.0, 90.0, 100.0));
+
+        let variance = 1.1f64.ln().powi(2) + 0.9f64.ln().powi(2);
+        match rs.get_last() {
+            Some(Value::RogersSatchellValue(value)) => {
+                approx_eq(value, (variance * 365.0).sqrt() * 100.0)
+            }
+            _ => panic!("missing rogers-satchell"),
+        }
+    }
+
+    #[test]
+    fn rogers_satchell_before_close_is_provisional() {
+        let mut rs = RogersSatchell::new(2);
+
+        rs.load(&[p(100.0, 110.0, 90.0, 100.0), p(100.0, 110.0, 90.0, 100.0)]);
+
+        let after_close = rs.get_last();
+        rs.update_before_close(p(100.0, 120.0, 95.0, 105.0));
+
+        assert_ne!(after_close, rs.get_last());
+    }
+
+    #[test]
+    fn rogers_satchell_reset_clears_state() {
+        let mut rs = RogersSatchell::new(2);
+
+        rs.load(&[p(100.0, 110.0, 90.0, 100.0), p(100.0, 110.0, 90.0, 100.0)]);
+        assert!(rs.is_ready());
+
+        rs.reset();
+
+        assert!(!rs.is_ready());
+        assert_eq!(rs.get_last(), None);
+    }
+}
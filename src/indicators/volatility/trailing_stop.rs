@@ -0,0 +1,259 @@
+use super::Atr;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct StopState {
+    pub long_stop: f64,
+    pub short_stop: f64,
+    pub is_long: bool,
+    pub stop_hit: bool,
+    close: f64,
+}
+
+/// Ratchets a pair of long/short stops. Provisional updates are evaluated
+/// against the last confirmed state and never move it; only closes commit.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct StopRatchet {
+    confirmed: Option<StopState>,
+    last: Option<StopState>,
+}
+
+impl StopRatchet {
+    fn step(&self, long_candidate: f64, short_candidate: f64, close: f64) -> StopState {
+        let Some(prev) = self.confirmed else {
+            return StopState {
+                long_stop: long_candidate,
+                short_stop: short_candidate,
+                is_long: true,
+                stop_hit: false,
+                close,
+            };
+        };
+
+        // A stop only trails while the previous close respected it.
+        let long_stop = if prev.close > prev.long_stop {
+            long_candidate.max(prev.long_stop)
+        } else {
+            long_candidate
+        };
+        let short_stop = if prev.close < prev.short_stop {
+            short_candidate.min(prev.short_stop)
+        } else {
+            short_candidate
+        };
+
+        let is_long = if close > prev.short_stop {
+            true
+        } else if close < prev.long_stop {
+            false
+        } else {
+            prev.is_long
+        };
+
+        StopState {
+            long_stop,
+            short_stop,
+            is_long,
+            stop_hit: is_long != prev.is_long,
+            close,
+        }
+    }
+
+    pub fn update_after_close(&mut self, long_candidate: f64, short_candidate: f64, close: f64) {
+        let state = self.step(long_candidate, short_candidate, close);
+        self.confirmed = Some(state);
+        self.last = Some(state);
+    }
+
+    pub fn update_before_close(&mut self, long_candidate: f64, short_candidate: f64, close: f64) {
+        self.last = Some(self.step(long_candidate, short_candidate, close));
+    }
+
+    #[inline]
+    pub fn get_last(&self) -> Option<StopState> {
+        self.last
+    }
+
+    pub fn reset(&mut self) {
+        self.confirmed = None;
+        self.last = None;
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AtrTrailingStop {
+    multiplier: f64,
+    atr: Atr,
+    ratchet: StopRatchet,
+}
+
+impl AtrTrailingStop {
+    pub fn new(periods: u32, multiplier: f64) -> Self {
+        assert!(
+            multiplier > 0.0,
+            "ATR trailing stop multiplier must be > 0, got {}",
+            multiplier
+        );
+        Self {
+            multiplier,
+            atr: Atr::new(periods),
+            ratchet: StopRatchet::default(),
+        }
+    }
+
+    fn candidates(&self, price: Price) -> Option<(f64, f64)> {
+        self.atr.get_last_value().map(|atr| {
+            let offset = self.multiplier * atr;
+            (price.close - offset, price.close + offset)
+        })
+    }
+}
+
+impl Indicator for AtrTrailingStop {
+    fn update_after_close(&mut self, price: Price) {
+        self.atr.update_after_close(price);
+        if let Some((long, short)) = self.candidates(price) {
+            self.ratchet.update_after_close(long, short, price.close);
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.atr.update_before_close(price);
+        if let Some((long, short)) = self.candidates(price) {
+            self.ratchet.update_before_close(long, short, price.close);
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ratchet.get_last().is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.ratchet
+            .get_last()
+            .map(|state| Value::AtrTrailingStopValue {
+                long_stop: state.long_stop,
+                short_stop: state.short_stop,
+                is_long: state.is_long,
+                stop_hit: state.stop_hit,
+            })
+    }
+
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.ratchet.reset();
+    }
+
+    fn period(&self) -> u32 {
+        self.atr.period()
+    }
+}
+
+impl Default for AtrTrailingStop {
+    fn default() -> Self {
+        Self::new(14, 3.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open: close,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn state(stop: &AtrTrailingStop) -> (f64, f64, bool, bool) {
+        match stop.get_last() {
+            Some(Value::AtrTrailingStopValue {
+                long_stop,
+                short_stop,
+                is_long,
+                stop_hit,
+            }) => (long_stop, short_stop, is_long, stop_hit),
+            _ => panic!("missing trailing stop"),
+        }
+    }
+
+    #[test]
+    fn trailing_stop_ratchets_up_in_uptrend() {
+        let mut stop = AtrTrailingStop::new(2, 1.0);
+
+        stop.update_after_close(p(11.0, 9.0, 10.0));
+        assert!(!stop.is_ready());
+
+        stop.update_after_close(p(12.0, 10.0, 11.0));
+        assert_eq!(state(&stop), (9.0, 13.0, true, false));
+
+        // ATR stays 2: the long stop trails the close, the short stop holds.
+        stop.update_after_close(p(13.0, 11.0, 12.0));
+        assert_eq!(state(&stop), (10.0, 13.0, true, false));
+
+        // A pullback lowers the candidate, but the long stop holds.
+        stop.update_after_close(p(12.5, 10.5, 11.0));
+        let (long_stop, _, is_long, stop_hit) = state(&stop);
+        assert_eq!(long_stop, 10.0);
+        assert!(is_long && !stop_hit);
+    }
+
+    #[test]
+    fn trailing_stop_flips_when_close_breaks_long_stop() {
+        let mut stop = AtrTrailingStop::new(2, 1.0);
+
+        stop.load(&[p(11.0, 9.0, 10.0), p(12.0, 10.0, 11.0), p(13.0, 11.0, 12.0)]);
+        stop.update_after_close(p(12.0, 8.0, 9.0));
+
+        let (_, _, is_long, stop_hit) = state(&stop);
+        assert!(!is_long);
+        assert!(stop_hit);
+
+        stop.update_after_close(p(10.0, 8.0, 9.0));
+        assert!(!state(&stop).3);
+    }
+
+    #[test]
+    fn trailing_stop_before_close_does_not_commit_ratchet() {
+        let mut stop = AtrTrailingStop::new(2, 1.0);
+
+        stop.load(&[p(11.0, 9.0, 10.0), p(12.0, 10.0, 11.0), p(13.0, 11.0, 12.0)]);
+
+        stop.update_before_close(p(12.0, 8.0, 9.0));
+        let (_, _, is_long, stop_hit) = state(&stop);
+        assert!(!is_long && stop_hit);
+
+        stop.update_before_close(p(13.0, 11.0, 12.0));
+        assert!(state(&stop).2);
+
+        stop.update_after_close(p(13.0, 11.0, 12.0));
+        let (long_stop, _, is_long, stop_hit) = state(&stop);
+        assert_eq!(long_stop, 10.0);
+        assert!(is_long && !stop_hit);
+    }
+
+    #[test]
+    fn trailing_stop_reset_clears_state() {
+        let mut stop = AtrTrailingStop::new(2, 1.0);
+
+        stop.load(&[p(11.0, 9.0, 10.0), p(12.0, 10.0, 11.0)]);
+        assert!(stop.is_ready());
+
+        stop.reset();
+
+        assert!(!stop.is_ready());
+        assert_eq!(stop.get_last(), None);
+    }
+}
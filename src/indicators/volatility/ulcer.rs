@@ -0,0 +1,184 @@
+use crate::indicators::{Indicator, Price, Source, Value};
+use crate::{Mean, RollingExtremum};
+
+#[derive(Clone, Debug)]
+pub struct UlcerIndex {
+    periods: u32,
+    highest: RollingExtremum,
+    drawdowns: Mean,
+    value: Option<f64>,
+    source: Source,
+}
+
+impl UlcerIndex {
+    pub fn new(periods: u32) -> Self {
+        assert!(
+            periods > 0,
+            "Ulcer Index periods must be > 0, got {}",
+            periods
+        );
+        Self {
+            periods,
+            highest: RollingExtremum::max(periods),
+            drawdowns: Mean::new(periods),
+            value: None,
+            source: Source::default(),
+        }
+    }
+
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
+    #[inline]
+    fn squared_drawdown(close: f64, highest: f64) -> f64 {
+        if highest.abs() <= f64::EPSILON {
+            0.0
+        } else {
+            (100.0 * (close - highest) / highest).powi(2)
+        }
+    }
+
+    fn update_value(&mut self) {
+        self.value = self.drawdowns.get_last().map(f64::sqrt);
+    }
+}
+
+impl Indicator for UlcerIndex {
+    fn update_after_close(&mut self, price: Price) {
+        self.highest.update_after_close(self.source.get(&price));
+        if let Some(highest) = self.highest.get_last() {
+            self.drawdowns
+                .update_after_close(Self::squared_drawdown(self.source.get(&price), highest));
+            self.update_value();
+        }
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.highest.update_before_close(self.source.get(&price));
+        if let Some(highest) = self.highest.get_last() {
+            self.drawdowns
+                .update_before_close(Self::squared_drawdown(self.source.get(&price), highest));
+            self.update_value();
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::UlcerIndexValue)
+    }
+
+    fn reset(&mut self) {
+        self.highest.reset();
+        self.drawdowns.reset();
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        2 * self.periods - 1
+    }
+}
+
+impl Default for UlcerIndex {
+    fn default() -> Self {
+        Self::new(14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(close: f64) -> Price {
+        Price {
+            open: close,
+            high: close,
+            low: close,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn ulcer_is_rms_of_drawdowns() {
+        let mut ulcer = UlcerIndex::new(2);
+
+        ulcer.update_after_close(p(100.0));
+        ulcer.update_after_close(p(90.0));
+        assert!(!ulcer.is_ready());
+
+        ulcer.update_after_close(p(99.0));
+
+        // drawdowns of -10% from 100 and -0% from 99
+        match ulcer.get_last() {
+            Some(Value::UlcerIndexValue(value)) => approx_eq(value, 50.0f64.sqrt()),
+            _ => panic!("missing ulcer index"),
+        }
+    }
+
+    #[test]
+    fn ulcer_rising_market_is_zero() {
+        let mut ulcer = UlcerIndex::new(3);
+
+        ulcer.load(&[p(1.0), p(2.0), p(3.0), p(4.0), p(5.0), p(6.0)]);
+
+        match ulcer.get_last() {
+            Some(Value::UlcerIndexValue(value)) => approx_eq(value, 0.0),
+            _ => panic!("missing ulcer index"),
+        }
+    }
+
+    #[test]
+    fn ulcer_before_close_is_provisional() {
+        let mut ulcer = UlcerIndex::new(2);
+
+        ulcer.load(&[p(100.0), p(90.0), p(99.0)]);
+
+        let after_close = ulcer.get_last();
+        ulcer.update_before_close(p(80.0));
+
+        assert_ne!(after_close, ulcer.get_last());
+
+        ulcer.update_after_close(p(99.0));
+        approx_eq(
+            match ulcer.get_last() {
+                Some(Value::UlcerIndexValue(value)) => value,
+                _ => panic!("missing ulcer index"),
+            },
+            0.0,
+        );
+    }
+
+    #[test]
+    fn ulcer_reset_clears_state() {
+        let mut ulcer = UlcerIndex::new(2);
+
+        ulcer.load(&[p(100.0), p(90.0), p(99.0)]);
+        assert!(ulcer.is_ready());
+
+        ulcer.reset();
+
+        assert!(!ulcer.is_ready());
+        assert_eq!(ulcer.get_last(), None);
+    }
+}
@@ -0,0 +1,235 @@
+use super::annualization::Annualization;
+use super::log_ratio;
+use super::rogers_satchell::RogersSatchell;
+use crate::indicators::{Indicator, Price, Value};
+use crate::{Mean, StdDev, VarianceMode};
+
+#[derive(Clone, Debug)]
+pub struct YangZhang {
+    periods: u32,
+    overnight: StdDev,
+    open_close: StdDev,
+    rogers_satchell: Mean,
+    annualization: Annualization,
+    prev_close: Option<f64>,
+    value: Option<f64>,
+}
+
+impl YangZhang {
+    pub fn new(periods: u32) -> Self {
+        assert!(
+            periods > 1,
+            "Yang-Zhang periods must be > 1, got {}",
+            periods
+        );
+        Self {
+            periods,
+            overnight: StdDev::new(periods),
+            open_close: StdDev::new(periods),
+            rogers_satchell: Mean::new(periods),
+            annualization: Annualization::default(),
+            prev_close: None,
+            value: None,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: VarianceMode) -> Self {
+        self.overnight = self.overnight.with_mode(mode);
+        self.open_close = self.open_close.with_mode(mode);
+        self.update_value();
+        self
+    }
+
+    pub fn with_annualization(mut self, annualization: Annualization) -> Self {
+        self.annualization = annualization;
+        self.update_value();
+        self
+    }
+
+    #[inline]
+    fn weight(&self) -> f64 {
+        let n = self.periods as f64;
+        0.34 / (1.34 + (n + 1.0) / (n - 1.0))
+    }
+
+    /// Overnight, open-to-close and Rogers-Satchell terms; `None` on the
+    /// first bar or with a non-positive price.
+    fn terms(&self, price: Price) -> Option<(f64, f64, f64)> {
+        Some((
+            log_ratio(price.open, self.prev_close?)?,
+            log_ratio(price.close, price.open)?,
+            RogersSatchell::term(price)?,
+        ))
+    }
+
+    fn update_value(&mut self) {
+        self.value = match (
+            self.overnight.get_variance(),
+            self.open_close.get_variance(),
+        ) {
+            (Some(overnight), Some(open_close)) => {
+                let k = self.weight();
+                let rs = self.rogers_satchell.sum() / self.periods as f64;
+                Some(
+                    self.annualization
+                        .annualize(overnight + k * open_close + (1.0 - k) * rs),
+                )
+            }
+            _ => None,
+        };
+    }
+}
+
+impl Indicator for YangZhang {
+    fn update_after_close(&mut self, price: Price) {
+        self.annualization.observe(&price);
+        if let Some((overnight, open_close, rs)) = self.terms(price) {
+            self.overnight.update_after_close_value(overnight);
+            self.open_close.update_after_close_value(open_close);
+            self.rogers_satchell.update_after_close(rs);
+            self.update_value();
+        }
+        self.prev_close = Some(price.close);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some((overnight, open_close, rs)) = self.terms(price) {
+            self.overnight.update_before_close_value(overnight);
+            self.open_close.update_before_close_value(open_close);
+            self.rogers_satchell.update_before_close(rs);
+            self.update_value();
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::YangZhangValue)
+    }
+
+    fn reset(&mut self) {
+        self.annualization.reset();
+        self.overnight.reset();
+        self.open_close.reset();
+        self.rogers_satchell.reset();
+        self.prev_close = None;
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.periods + 1
+    }
+}
+
+impl Default for YangZhang {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(open: f64, high: f64, low: f64, close: f64) -> Price {
+        Price {
+            open,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: 0.0,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn yang_zhang_needs_previous_close() {
+        let mut yz = YangZhang::new(2);
+
+        yz.update_after_close(p(100.0, 110.0, 90.0, 100.0));
+        yz.update_after_close(p(100.0, 110.0, 90.0, 100.0));
+        assert!(!yz.is_ready());
+
+        yz.update_after_close(p(100.0, 110.0, 90.0, 100.0));
+        assert!(yz.is_ready());
+    }
+
+    #[test]
+    fn yang_zhang_blends_overnight_body_and_range() {
+        let mut yz = YangZhang::new(2);
+
+        yz.load(&[
+            p(100.0, 100.0, 100.0, 100.0),
+            p(110.0, 121.0, 100.0, 110.0),
+            p(99.0, 110.0, 90.0, 110.0),
+        ]);
+
+        let overnight = [1.1f64.ln(), 0.9f64.ln()];
+        let body = [0.0, (110.0f64 / 99.0).ln()];
+        let sample_var = |x: [f64; 2]| (x[0] - x[1]).powi(2) / 2.0;
+        let rs = [p(110.0, 121.0, 100.0, 110.0), p(99.0, 110.0, 90.0, 110.0)]
+            .iter()
+            .map(|bar| RogersSatchell::term(*bar).unwrap())
+            .sum::<f64>()
+            / 2.0;
+        let k = 0.34 / (1.34 + 3.0);
+        let variance = sample_var(overnight) + k * sample_var(body) + (1.0 - k) * rs;
+
+        match yz.get_last() {
+            Some(Value::YangZhangValue(value)) => {
+                approx_eq(value, (variance * 365.0).sqrt() * 100.0)
+            }
+            _ => panic!("missing yang-zhang"),
+        }
+    }
+
+    #[test]
+    fn yang_zhang_before_close_is_provisional() {
+        let mut yz = YangZhang::new(2);
+
+        yz.load(&[
+            p(100.0, 100.0, 100.0, 100.0),
+            p(110.0, 121.0, 100.0, 110.0),
+            p(99.0, 110.0, 90.0, 110.0),
+        ]);
+
+        let after_close = yz.get_last();
+        yz.update_before_close(p(115.0, 120.0, 105.0, 108.0));
+
+        assert_ne!(after_close, yz.get_last());
+    }
+
+    #[test]
+    fn yang_zhang_reset_clears_state() {
+        let mut yz = YangZhang::new(2);
+
+        yz.load(&[
+            p(100.0, 100.0, 100.0, 100.0),
+            p(110.0, 121.0, 100.0, 110.0),
+            p(99.0, 110.0, 90.0, 110.0),
+        ]);
+        assert!(yz.is_ready());
+
+        yz.reset();
+
+        assert!(!yz.is_ready());
+        assert_eq!(yz.get_last(), None);
+    }
+}
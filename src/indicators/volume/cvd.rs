@@ -0,0 +1,375 @@
+use crate::indicators::divergence::{DivergenceTracker, divergence_value};
+use crate::indicators::{DivergenceEvent, Indicator, Price, TradeFlow, Value};
+
+#[derive(Clone, Debug)]
+pub struct Cvd {
+    confirmed_value: Option<f64>,
+    value: Option<f64>,
+}
+
+impl Cvd {
+    pub fn new() -> Self {
+        Self {
+            confirmed_value: None,
+            value: None,
+        }
+    }
+
+    pub fn update_after_close_flow(&mut self, flow: TradeFlow) {
+        let next = self.confirmed_value.unwrap_or(0.0) + flow.delta();
+        self.confirmed_value = Some(next);
+        self.value = Some(next);
+    }
+
+    pub fn update_before_close_flow(&mut self, flow: TradeFlow) {
+        if let Some(base) = self.confirmed_value {
+            self.value = Some(base + flow.delta());
+        }
+    }
+}
+
+impl Indicator for Cvd {
+    fn update_after_close(&mut self, price: Price) {
+        self.update_after_close_flow(TradeFlow::estimate(&price));
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.update_before_close_flow(TradeFlow::estimate(&price));
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::CvdValue)
+    }
+
+    fn reset(&mut self) {
+        self.confirmed_value = None;
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        1
+    }
+}
+
+impl Default for Cvd {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Per-bar delta. The absorption flags mark a candle that closed against
+/// its own delta; for swing divergence see [`CvdDivergence`].
+#[derive(Clone, Debug)]
+pub struct VolumeDelta {
+    delta: Option<f64>,
+    bullish_absorption: bool,
+    bearish_absorption: bool,
+}
+
+impl VolumeDelta {
+    pub fn new() -> Self {
+        Self {
+            delta: None,
+            bullish_absorption: false,
+            bearish_absorption: false,
+        }
+    }
+
+    pub fn update_after_close_flow(&mut self, price: Price, flow: TradeFlow) {
+        self.update_value(price, flow);
+    }
+
+    pub fn update_before_close_flow(&mut self, price: Price, flow: TradeFlow) {
+        self.update_value(price, flow);
+    }
+
+    fn update_value(&mut self, price: Price, flow: TradeFlow) {
+        let delta = flow.delta();
+        self.delta = Some(delta);
+        self.bullish_absorption = price.close < price.open && delta > 0.0;
+        self.bearish_absorption = price.close > price.open && delta < 0.0;
+    }
+}
+
+impl Indicator for VolumeDelta {
+    fn update_after_close(&mut self, price: Price) {
+        self.update_after_close_flow(price, TradeFlow::estimate(&price));
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.update_before_close_flow(price, TradeFlow::estimate(&price));
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.delta.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.delta.map(|delta| Value::VolumeDeltaValue {
+            delta,
+            bullish_absorption: self.bullish_absorption,
+            bearish_absorption: self.bearish_absorption,
+        })
+    }
+
+    fn reset(&mut self) {
+        self.delta = None;
+        self.bullish_absorption = false;
+        self.bearish_absorption = false;
+    }
+
+    fn period(&self) -> u32 {
+        1
+    }
+}
+
+impl Default for VolumeDelta {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Divergence between CVD swings and price swings, paired and compared as
+/// in [`Divergence`](crate::indicators::Divergence).
+#[derive(Clone, Debug)]
+pub struct CvdDivergence {
+    cvd: Cvd,
+    tracker: DivergenceTracker,
+    events: Vec<DivergenceEvent>,
+    ready: bool,
+}
+
+impl CvdDivergence {
+    pub fn new(left: u32, right: u32, lookback: u32) -> Self {
+        Self {
+            cvd: Cvd::new(),
+            tracker: DivergenceTracker::new(left, right, 2, lookback),
+            events: Vec::new(),
+            ready: false,
+        }
+    }
+
+    pub fn with_tolerance(mut self, bars: u32) -> Self {
+        self.tracker = self.tracker.with_tolerance(bars);
+        self
+    }
+
+    /// Divergences confirmed on the latest update, including provisional ones.
+    #[inline]
+    pub fn events(&self) -> &[DivergenceEvent] {
+        &self.events
+    }
+
+    pub fn update_after_close_flow(&mut self, price: Price, flow: TradeFlow) {
+        self.cvd.update_after_close_flow(flow);
+        self.events = self.tracker.push(&price, self.cvd.value);
+        self.ready = self.tracker.is_ready();
+    }
+
+    pub fn update_before_close_flow(&mut self, price: Price, flow: TradeFlow) {
+        self.cvd.update_before_close_flow(flow);
+        self.events = self.tracker.preview(&price, self.cvd.value);
+    }
+}
+
+impl Indicator for CvdDivergence {
+    fn update_after_close(&mut self, price: Price) {
+        self.update_after_close_flow(price, TradeFlow::estimate(&price));
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.update_before_close_flow(price, TradeFlow::estimate(&price));
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.ready.then(|| divergence_value(&self.events))
+    }
+
+    fn reset(&mut self) {
+        self.cvd.reset();
+        self.tracker.reset();
+        self.events.clear();
+        self.ready = false;
+    }
+
+    fn period(&self) -> u32 {
+        self.tracker.period()
+    }
+}
+
+impl Default for CvdDivergence {
+    fn default() -> Self {
+        Self::new(5, 5, 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{DivergenceKind, Trade, TradeSide};
+
+    fn p(open: f64, high: f64, low: f64, close: f64, volume: f64) -> Price {
+        Price {
+            open,
+            high,
+            low,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: volume,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn cvd_accumulates_trade_side_delta() {
+        let mut cvd = Cvd::new();
+
+        cvd.update_after_close_flow(TradeFlow::new(10.0, 4.0));
+        assert_eq!(cvd.get_last(), Some(Value::CvdValue(6.0)));
+
+        cvd.update_after_close_flow(TradeFlow::new(3.0, 8.0));
+        assert_eq!(cvd.get_last(), Some(Value::CvdValue(1.0)));
+    }
+
+    #[test]
+    fn cvd_before_close_is_provisional() {
+        let mut cvd = Cvd::new();
+
+        cvd.update_after_close_flow(TradeFlow::new(10.0, 4.0));
+
+        let mut flow = TradeFlow::default();
+        flow.push(Trade {
+            size: 2.0,
+            side: TradeSide::Sell,
+        });
+        cvd.update_before_close_flow(flow);
+        assert_eq!(cvd.get_last(), Some(Value::CvdValue(4.0)));
+
+        flow.push(Trade {
+            size: 5.0,
+            side: TradeSide::Buy,
+        });
+        cvd.update_before_close_flow(flow);
+        assert_eq!(cvd.get_last(), Some(Value::CvdValue(9.0)));
+
+        cvd.update_after_close_flow(flow);
+        assert_eq!(cvd.get_last(), Some(Value::CvdValue(9.0)));
+    }
+
+    #[test]
+    fn cvd_estimates_flow_from_candle_range() {
+        let mut cvd = Cvd::new();
+
+        cvd.update_after_close(p(10.0, 12.0, 8.0, 11.0, 8.0));
+
+        match cvd.get_last() {
+            Some(Value::CvdValue(value)) => approx_eq(value, 4.0),
+            _ => panic!("missing cvd"),
+        }
+    }
+
+    #[test]
+    fn cvd_reset_clears_state() {
+        let mut cvd = Cvd::new();
+
+        cvd.update_after_close_flow(TradeFlow::new(10.0, 4.0));
+        assert!(cvd.is_ready());
+
+        cvd.reset();
+
+        assert!(!cvd.is_ready());
+        assert_eq!(cvd.get_last(), None);
+    }
+
+    #[test]
+    fn volume_delta_flags_absorption_against_candle_direction() {
+        let mut delta = VolumeDelta::new();
+
+        delta.update_after_close_flow(p(10.0, 12.0, 9.0, 11.0, 10.0), TradeFlow::new(3.0, 7.0));
+        assert_eq!(
+            delta.get_last(),
+            Some(Value::VolumeDeltaValue {
+                delta: -4.0,
+                bullish_absorption: false,
+                bearish_absorption: true,
+            })
+        );
+
+        delta.update_before_close_flow(p(11.0, 11.5, 9.0, 9.5, 10.0), TradeFlow::new(6.0, 4.0));
+        assert_eq!(
+            delta.get_last(),
+            Some(Value::VolumeDeltaValue {
+                delta: 2.0,
+                bullish_absorption: true,
+                bearish_absorption: false,
+            })
+        );
+    }
+
+    #[test]
+    fn cvd_divergence_compares_cvd_swings_with_price_swings() {
+        let bar = |time: u64, low: f64| Price {
+            open: low + 1.0,
+            high: low + 2.0,
+            low,
+            close: low + 1.0,
+            open_time: time,
+            close_time: time,
+            vlm: 0.0,
+        };
+        // Price lows at bars 1 and 4 (lower low), CVD lows 30 then 35.
+        let lows = [11.0, 9.0, 10.0, 10.0, 7.0, 8.0];
+        let deltas: [f64; 6] = [50.0, -20.0, 15.0, 0.0, -10.0, 5.0];
+
+        let mut div = CvdDivergence::new(1, 1, 10);
+        for (i, (low, delta)) in lows.iter().zip(deltas).enumerate() {
+            div.update_after_close_flow(
+                bar(i as u64, *low),
+                TradeFlow::new(delta.max(0.0), (-delta).max(0.0)),
+            );
+        }
+
+        assert_eq!(div.events().len(), 1);
+        let event = div.events()[0];
+        assert_eq!(event.kind, DivergenceKind::RegularBullish);
+        assert_eq!((event.from.price, event.from.oscillator), (9.0, 30.0));
+        assert_eq!((event.to.price, event.to.oscillator), (7.0, 35.0));
+        assert!(matches!(
+            div.get_last(),
+            Some(Value::DivergenceValue {
+                regular_bullish: true,
+                ..
+            })
+        ));
+    }
+}
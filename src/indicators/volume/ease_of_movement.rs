@@ -0,0 +1,181 @@
+use crate::Mean;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct EaseOfMovement {
+    periods: u32,
+    mean: Mean,
+    prev_midpoint: Option<f64>,
+}
+
+impl EaseOfMovement {
+    const VOLUME_DIVISOR: f64 = 10_000.0;
+
+    pub fn new(periods: u32) -> Self {
+        assert!(periods > 0, "EoM periods must be > 0, got {}", periods);
+        Self {
+            periods,
+            mean: Mean::new(periods),
+            prev_midpoint: None,
+        }
+    }
+
+    #[inline]
+    fn midpoint(price: Price) -> f64 {
+        (price.high + price.low) / 2.0
+    }
+
+    fn raw_emv(price: Price, prev_midpoint: f64) -> f64 {
+        let range = price.high - price.low;
+
+        // Without volume or range the box ratio is undefined, so the candle
+        // contributes no movement instead of an infinite one.
+        if price.vlm <= f64::EPSILON || range <= f64::EPSILON {
+            return 0.0;
+        }
+
+        let distance = Self::midpoint(price) - prev_midpoint;
+        let box_ratio = (price.vlm / Self::VOLUME_DIVISOR) / range;
+        distance / box_ratio
+    }
+}
+
+impl Indicator for EaseOfMovement {
+    fn update_after_close(&mut self, price: Price) {
+        if let Some(prev_midpoint) = self.prev_midpoint {
+            self.mean
+                .update_after_close(Self::raw_emv(price, prev_midpoint));
+        }
+        self.prev_midpoint = Some(Self::midpoint(price));
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(prev_midpoint) = self.prev_midpoint {
+            self.mean
+                .update_before_close(Self::raw_emv(price, prev_midpoint));
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.mean.is_ready()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.mean.get_last().map(Value::EaseOfMovementValue)
+    }
+
+    fn reset(&mut self) {
+        self.mean.reset();
+        self.prev_midpoint = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.periods
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.periods + 1
+    }
+}
+
+impl Default for EaseOfMovement {
+    fn default() -> Self {
+        Self::new(14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(high: f64, low: f64, volume: f64) -> Price {
+        Price {
+            open: low,
+            high,
+            low,
+            close: high,
+            open_time: 0,
+            close_time: 0,
+            vlm: volume,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn eom_computes_expected_value() {
+        let mut eom = EaseOfMovement::new(2);
+
+        eom.update_after_close(p(11.0, 9.0, 10_000.0));
+        eom.update_after_close(p(13.0, 11.0, 10_000.0));
+        assert!(!eom.is_ready());
+
+        eom.update_after_close(p(14.0, 12.0, 20_000.0));
+
+        // distance 2 and 1, box ratios 0.5 and 1.0
+        match eom.get_last() {
+            Some(Value::EaseOfMovementValue(value)) => approx_eq(value, 2.5),
+            _ => panic!("missing eom"),
+        }
+    }
+
+    #[test]
+    fn eom_zero_volume_candle_contributes_nothing() {
+        let mut eom = EaseOfMovement::new(2);
+
+        eom.load(&[
+            p(11.0, 9.0, 10_000.0),
+            p(13.0, 11.0, 10_000.0),
+            p(20.0, 18.0, 0.0),
+        ]);
+
+        match eom.get_last() {
+            Some(Value::EaseOfMovementValue(value)) => {
+                assert!(value.is_finite());
+                approx_eq(value, 2.0);
+            }
+            _ => panic!("missing eom"),
+        }
+    }
+
+    #[test]
+    fn eom_before_close_is_provisional() {
+        let mut eom = EaseOfMovement::new(2);
+
+        eom.load(&[
+            p(11.0, 9.0, 10_000.0),
+            p(13.0, 11.0, 10_000.0),
+            p(14.0, 12.0, 20_000.0),
+        ]);
+
+        let after_close = eom.get_last();
+        eom.update_before_close(p(10.0, 8.0, 5_000.0));
+
+        assert_ne!(after_close, eom.get_last());
+    }
+
+    #[test]
+    fn eom_reset_clears_state() {
+        let mut eom = EaseOfMovement::new(2);
+
+        eom.load(&[
+            p(11.0, 9.0, 10_000.0),
+            p(13.0, 11.0, 10_000.0),
+            p(14.0, 12.0, 20_000.0),
+        ]);
+        assert!(eom.is_ready());
+
+        eom.reset();
+
+        assert!(!eom.is_ready());
+        assert_eq!(eom.get_last(), None);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::ExpMean;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct ForceIndex {
+    ema: ExpMean,
+    prev_close: Option<f64>,
+    value: Option<f64>,
+}
+
+impl ForceIndex {
+    pub fn new(periods: u32) -> Self {
+        assert!(
+            periods > 1,
+            "Force Index periods must be > 1, got {}",
+            periods
+        );
+        Self {
+            ema: ExpMean::new(periods),
+            prev_close: None,
+            value: None,
+        }
+    }
+
+    #[inline]
+    fn raw_force(price: Price, prev_close: f64) -> f64 {
+        // Zero-volume candles carry no force, whatever the price change.
+        if price.vlm <= f64::EPSILON {
+            0.0
+        } else {
+            (price.close - prev_close) * price.vlm
+        }
+    }
+}
+
+impl Indicator for ForceIndex {
+    fn update_after_close(&mut self, price: Price) {
+        if let Some(prev_close) = self.prev_close {
+            self.ema
+                .update_after_close(Self::raw_force(price, prev_close));
+            self.value = self.ema.get_last();
+        }
+        self.prev_close = Some(price.close);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(prev_close) = self.prev_close {
+            self.ema
+                .update_before_close(Self::raw_force(price, prev_close));
+            self.value = self.ema.get_last();
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.value.map(Value::ForceIndexValue)
+    }
+
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.prev_close = None;
+        self.value = None;
+    }
+
+    fn period(&self) -> u32 {
+        self.ema.period()
+    }
+
+    fn warmup_bars(&self) -> u32 {
+        self.ema.period() + 1
+    }
+}
+
+impl Default for ForceIndex {
+    fn default() -> Self {
+        Self::new(13)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(close: f64, volume: f64) -> Price {
+        Price {
+            open: close,
+            high: close,
+            low: close,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: volume,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn force_index_smooths_signed_volume_force() {
+        let mut force = ForceIndex::new(2);
+
+        force.update_after_close(p(10.0, 5.0));
+        force.update_after_close(p(11.0, 4.0));
+        assert!(!force.is_ready());
+
+        force.update_after_close(p(10.5, 2.0));
+
+        // raw force 4 and -1, seeded with their mean
+        match force.get_last() {
+            Some(Value::ForceIndexValue(value)) => approx_eq(value, 1.5),
+            _ => panic!("missing force index"),
+        }
+    }
+
+    #[test]
+    fn force_index_ignores_price_change_on_zero_volume() {
+        let mut force = ForceIndex::new(2);
+
+        force.load(&[p(10.0, 5.0), p(11.0, 4.0), p(12.0, 4.0)]);
+        force.update_after_close(p(20.0, 0.0));
+
+        // EMA(2) decays towards 0: 4 * 1/3
+        match force.get_last() {
+            Some(Value::ForceIndexValue(value)) => approx_eq(value, 4.0 / 3.0),
+            _ => panic!("missing force index"),
+        }
+    }
+
+    #[test]
+    fn force_index_before_close_is_provisional() {
+        let mut force = ForceIndex::new(2);
+
+        force.load(&[p(10.0, 5.0), p(11.0, 4.0), p(10.5, 2.0)]);
+
+        let after_close = force.get_last();
+        force.update_before_close(p(12.0, 3.0));
+
+        assert_ne!(after_close, force.get_last());
+    }
+
+    #[test]
+    fn force_index_reset_clears_state() {
+        let mut force = ForceIndex::new(2);
+
+        force.load(&[p(10.0, 5.0), p(11.0, 4.0), p(10.5, 2.0)]);
+        assert!(force.is_ready());
+
+        force.reset();
+
+        assert!(!force.is_ready());
+        assert_eq!(force.get_last(), None);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::indicators::momentum::EmaSpread;
+use crate::indicators::{Indicator, Price, Value};
+
+#[derive(Clone, Debug)]
+pub struct Klinger {
+    spread: EmaSpread,
+    prev_typical: Option<f64>,
+}
+
+impl Klinger {
+    pub fn new(fast_period: u32, slow_period: u32, signal_period: u32) -> Self {
+        Self {
+            spread: EmaSpread::new(
+                "Klinger",
+                fast_period,
+                slow_period,
+                signal_period,
+                |fast, slow| Some(fast - slow),
+            ),
+            prev_typical: None,
+        }
+    }
+
+    #[inline]
+    fn typical_price(price: Price) -> f64 {
+        (price.high + price.low + price.close) / 3.0
+    }
+
+    #[inline]
+    fn volume_force(price: Price, prev_typical: f64) -> f64 {
+        if Self::typical_price(price) >= prev_typical {
+            price.vlm
+        } else {
+            -price.vlm
+        }
+    }
+}
+
+impl Indicator for Klinger {
+    fn update_after_close(&mut self, price: Price) {
+        if let Some(prev_typical) = self.prev_typical {
+            self.spread
+                .update_after_close_value(Self::volume_force(price, prev_typical));
+        }
+        self.prev_typical = Some(Self::typical_price(price));
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        if let Some(prev_typical) = self.prev_typical {
+            self.spread
+                .update_before_close_value(Self::volume_force(price, prev_typical));
+        }
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for price in price_data {
+            self.update_after_close(*price);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.spread.value().is_some()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.spread
+            .value()
+            .map(|(kvo, signal, histogram)| Value::KlingerValue {
+                kvo,
+                signal,
+                histogram,
+            })
+    }
+
+    fn reset(&mut self) {
+        self.spread.reset();
+        self.prev_typical = None;
+    }
+
+    fn period(&self) -> u32 {
+        // One extra bar for the first typical price.
+        self.spread.period() + 1
+    }
+}
+
+impl Default for Klinger {
+    fn default() -> Self {
+        Self::new(34, 55, 13)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(close: f64, volume: f64) -> Price {
+        Price {
+            open: close,
+            high: close,
+            low: close,
+            close,
+            open_time: 0,
+            close_time: 0,
+            vlm: volume,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn klinger_not_ready_until_signal_warms_up() {
+        let mut klinger = Klinger::new(2, 3, 2);
+
+        for (close, volume) in [(10.0, 1.0), (11.0, 2.0), (12.0, 3.0), (11.0, 4.0)] {
+            klinger.update_after_close(p(close, volume));
+        }
+        assert!(!klinger.is_ready());
+
+        klinger.update_after_close(p(12.0, 5.0));
+
+        // volume forces 2, 3, -4, 5
+        let fast_3 = (2.0 / 3.0) * -4.0 + (1.0 / 3.0) * 2.5;
+        let slow_3 = 1.0 / 3.0;
+        let fast_4 = (2.0 / 3.0) * 5.0 + (1.0 / 3.0) * fast_3;
+        let slow_4 = 0.5 * 5.0 + 0.5 * slow_3;
+        match klinger.get_last() {
+            Some(Value::KlingerValue {
+                kvo,
+                signal,
+                histogram,
+            }) => {
+                approx_eq(kvo, fast_4 - slow_4);
+                approx_eq(signal, ((fast_3 - slow_3) + (fast_4 - slow_4)) / 2.0);
+                approx_eq(histogram, kvo - signal);
+            }
+            _ => panic!("missing klinger"),
+        }
+        assert_eq!(klinger.period(), 5);
+    }
+
+    #[test]
+    fn klinger_before_close_is_provisional() {
+        let mut klinger = Klinger::new(2, 3, 2);
+
+        klinger.load(&[
+            p(10.0, 1.0),
+            p(11.0, 2.0),
+            p(12.0, 3.0),
+            p(11.0, 4.0),
+            p(12.0, 5.0),
+        ]);
+
+        let after_close = klinger.get_last();
+        klinger.update_before_close(p(9.0, 10.0));
+
+        assert_ne!(after_close, klinger.get_last());
+    }
+
+    #[test]
+    fn klinger_reset_clears_state() {
+        let mut klinger = Klinger::new(2, 3, 2);
+
+        klinger.load(&[
+            p(10.0, 1.0),
+            p(11.0, 2.0),
+            p(12.0, 3.0),
+            p(11.0, 4.0),
+            p(12.0, 5.0),
+        ]);
+        assert!(klinger.is_ready());
+
+        klinger.reset();
+
+        assert!(!klinger.is_ready());
+        assert_eq!(klinger.get_last(), None);
+    }
+}
@@ -1,7 +1,15 @@
+mod cvd;
+mod ease_of_movement;
+mod force_index;
+mod klinger;
 mod obv;
 mod volume_ma;
 mod vwap_deviation;
 
+pub use cvd::{Cvd, CvdDivergence, VolumeDelta};
+pub use ease_of_movement::EaseOfMovement;
+pub use force_index::ForceIndex;
+pub use klinger::Klinger;
 pub use obv::Obv;
-pub use volume_ma::VolumeMa;
+pub use volume_ma::{VolumeMa, VolumeOscillator};
 pub use vwap_deviation::VwapDeviation;
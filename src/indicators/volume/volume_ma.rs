@@ -61,3 +61,168 @@ impl Default for VolumeMa {
         }
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct VolumeOscillator {
+    fast: VolumeMa,
+    slow: VolumeMa,
+}
+
+impl VolumeOscillator {
+    pub fn new(fast_period: u32, slow_period: u32) -> Self {
+        assert!(
+            fast_period > 1,
+            "Volume Oscillator fast period must be > 1, got {}",
+            fast_period
+        );
+        assert!(
+            slow_period > 1,
+            "Volume Oscillator slow period must be > 1, got {}",
+            slow_period
+        );
+
+        VolumeOscillator {
+            fast: VolumeMa::new(fast_period.min(slow_period)),
+            slow: VolumeMa::new(fast_period.max(slow_period)),
+        }
+    }
+
+    #[inline]
+    pub fn fast(&self) -> &VolumeMa {
+        &self.fast
+    }
+
+    #[inline]
+    pub fn slow(&self) -> &VolumeMa {
+        &self.slow
+    }
+
+    fn get_value(&self) -> Option<f64> {
+        let fast = self.fast.mean.get_last()?;
+        let slow = self.slow.mean.get_last()?;
+
+        if slow.abs() <= f64::EPSILON {
+            Some(0.0)
+        } else {
+            Some(((fast - slow) / slow) * 100.0)
+        }
+    }
+}
+
+impl Indicator for VolumeOscillator {
+    fn update_after_close(&mut self, price: Price) {
+        self.fast.update_after_close(price);
+        self.slow.update_after_close(price);
+    }
+
+    fn update_before_close(&mut self, price: Price) {
+        self.fast.update_before_close(price);
+        self.slow.update_before_close(price);
+    }
+
+    fn load(&mut self, price_data: &[Price]) {
+        for p in price_data {
+            self.update_after_close(*p);
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.fast.is_ready() && self.slow.is_ready()
+    }
+
+    fn get_last(&self) -> Option<Value> {
+        self.get_value().map(Value::VolumeOscillatorValue)
+    }
+
+    fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+    }
+
+    fn period(&self) -> u32 {
+        self.slow.period()
+    }
+}
+
+impl Default for VolumeOscillator {
+    fn default() -> Self {
+        VolumeOscillator::new(5, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(volume: f64) -> Price {
+        Price {
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            open_time: 0,
+            close_time: 0,
+            vlm: volume,
+        }
+    }
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn volume_oscillator_compares_fast_and_slow_averages() {
+        let mut osc = VolumeOscillator::new(2, 4);
+
+        osc.load(&[p(10.0), p(10.0), p(20.0)]);
+        assert!(!osc.is_ready());
+
+        osc.update_after_close(p(40.0));
+
+        // fast = 30, slow = 20
+        match osc.get_last() {
+            Some(Value::VolumeOscillatorValue(value)) => approx_eq(value, 50.0),
+            _ => panic!("missing volume oscillator"),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "fast period must be > 1")]
+    fn volume_oscillator_rejects_single_bar_period() {
+        VolumeOscillator::new(1, 4);
+    }
+
+    #[test]
+    fn volume_oscillator_handles_zero_volume_window() {
+        let mut osc = VolumeOscillator::new(2, 3);
+
+        osc.load(&[p(0.0), p(0.0), p(0.0)]);
+
+        assert_eq!(osc.get_last(), Some(Value::VolumeOscillatorValue(0.0)));
+    }
+
+    #[test]
+    fn volume_oscillator_before_close_is_provisional() {
+        let mut osc = VolumeOscillator::new(2, 4);
+
+        osc.load(&[p(10.0), p(10.0), p(20.0), p(40.0)]);
+
+        let after_close = osc.get_last();
+        osc.update_before_close(p(5.0));
+
+        assert_ne!(after_close, osc.get_last());
+    }
+
+    #[test]
+    fn volume_oscillator_reset_clears_state() {
+        let mut osc = VolumeOscillator::new(2, 4);
+
+        osc.load(&[p(10.0), p(10.0), p(20.0), p(40.0)]);
+        assert!(osc.is_ready());
+
+        osc.reset();
+
+        assert!(!osc.is_ready());
+        assert_eq!(osc.get_last(), None);
+    }
+}
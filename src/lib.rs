@@ -1,5 +1,18 @@
+pub mod backtest;
+pub mod expression;
 pub mod indicators;
+pub mod metrics;
+pub mod optimize;
 pub mod primitives;
+pub mod signals;
 
+pub use backtest::{
+    Backtest, BacktestResult, Context, EquityPoint, Fill, Order, OrderId, OrderKind, Strategy,
+    TradeRecord,
+};
+pub use expression::{Expression, ExpressionError};
 pub use indicators::*;
+pub use metrics::{Drawdown, EquityStats, TradeStats};
+pub use optimize::{Evaluation, Fold, Optimizer, Param, Params, Sample, Search, WalkForward};
 pub use primitives::*;
+pub use signals::*;
@@ -0,0 +1,323 @@
+use crate::backtest::{BacktestResult, EquityPoint, TradeRecord};
+use crate::indicators::{Annualization, Calendar};
+
+const MS_PER_YEAR: f64 = 365.25 * 86_400_000.0;
+
+/// The deepest peak-to-trough decline of an equity curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drawdown {
+    pub depth_pct: f64,
+    pub peak_time: u64,
+    pub trough_time: u64,
+    /// First time equity regained the peak, if it did.
+    pub recovery_time: Option<u64>,
+    /// Peak to recovery, or to the last point when still under water.
+    pub duration_ms: u64,
+}
+
+/// Return and risk statistics of an equity curve.
+///
+/// Per-period returns are taken between consecutive points and annualized
+/// with [`Annualization`] from the median spacing of the timestamps, so the
+/// curve should be sampled at a regular bar interval. The risk-free rate is
+/// taken as zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquityStats {
+    pub total_return_pct: f64,
+    /// Compounded over the curve's calendar span.
+    pub cagr_pct: f64,
+    /// `None` when returns have no variance.
+    pub sharpe: Option<f64>,
+    /// `None` when no period lost money.
+    pub sortino: Option<f64>,
+    /// CAGR over max drawdown; `None` without a drawdown.
+    pub calmar: Option<f64>,
+    pub max_drawdown: Drawdown,
+    /// Share of points with an open position.
+    pub exposure_pct: f64,
+    pub periods_per_year: f64,
+}
+
+impl EquityStats {
+    /// `None` for fewer than two points or a non-positive starting equity.
+    pub fn new(equity: &[EquityPoint], calendar: Calendar) -> Option<Self> {
+        let (first, last) = (equity.first()?, equity.last()?);
+        if equity.len() < 2 || first.equity <= 0.0 {
+            return None;
+        }
+
+        let growth = last.equity / first.equity;
+        let years = last.time.saturating_sub(first.time) as f64 / MS_PER_YEAR;
+        let cagr_pct = if years > 0.0 && growth > 0.0 {
+            (growth.powf(1.0 / years) - 1.0) * 100.0
+        } else {
+            (growth - 1.0) * 100.0
+        };
+
+        let periods_per_year = match median_interval_ms(equity) {
+            Some(ms) => Annualization::new(calendar).with_interval_ms(ms),
+            None => Annualization::new(calendar),
+        }
+        .periods_per_year();
+
+        let returns: Vec<f64> = equity
+            .windows(2)
+            .map(|pair| {
+                if pair[0].equity == 0.0 {
+                    0.0
+                } else {
+                    pair[1].equity / pair[0].equity - 1.0
+                }
+            })
+            .collect();
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n;
+
+        let std_dev = if returns.len() > 1 {
+            (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let downside = (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / n).sqrt();
+        let annualize = periods_per_year.sqrt();
+
+        let max_drawdown = max_drawdown(equity);
+
+        Some(Self {
+            total_return_pct: (growth - 1.0) * 100.0,
+            cagr_pct,
+            sharpe: (std_dev > 0.0).then(|| mean / std_dev * annualize),
+            sortino: (downside > 0.0).then(|| mean / downside * annualize),
+            calmar: (max_drawdown.depth_pct > 0.0).then(|| cagr_pct / max_drawdown.depth_pct),
+            max_drawdown,
+            exposure_pct: equity.iter().filter(|point| point.position != 0.0).count() as f64
+                / equity.len() as f64
+                * 100.0,
+            periods_per_year,
+        })
+    }
+}
+
+fn median_interval_ms(equity: &[EquityPoint]) -> Option<u64> {
+    let mut intervals: Vec<u64> = equity
+        .windows(2)
+        .map(|pair| pair[1].time.saturating_sub(pair[0].time))
+        .filter(|ms| *ms > 0)
+        .collect();
+    intervals.sort_unstable();
+    intervals.get(intervals.len() / 2).copied()
+}
+
+fn max_drawdown(equity: &[EquityPoint]) -> Drawdown {
+    let mut peak = equity[0];
+    let mut worst = Drawdown {
+        depth_pct: 0.0,
+        peak_time: peak.time,
+        trough_time: peak.time,
+        recovery_time: Some(peak.time),
+        duration_ms: 0,
+    };
+    let mut worst_peak = peak.equity;
+
+    for point in equity {
+        if point.equity > peak.equity {
+            peak = *point;
+        } else if peak.equity > 0.0 {
+            let depth_pct = (1.0 - point.equity / peak.equity) * 100.0;
+            if depth_pct > worst.depth_pct {
+                worst.depth_pct = depth_pct;
+                worst.peak_time = peak.time;
+                worst.trough_time = point.time;
+                worst_peak = peak.equity;
+            }
+        }
+    }
+
+    if worst.depth_pct > 0.0 {
+        worst.recovery_time = equity
+            .iter()
+            .find(|point| point.time > worst.trough_time && point.equity >= worst_peak)
+            .map(|point| point.time);
+        let end = worst
+            .recovery_time
+            .unwrap_or_else(|| equity[equity.len() - 1].time);
+        worst.duration_ms = end.saturating_sub(worst.peak_time);
+    }
+    worst
+}
+
+/// Win/loss statistics of closed trades.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeStats {
+    pub trades: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub win_rate_pct: f64,
+    /// Gross profit over gross loss; `None` without a losing trade.
+    pub profit_factor: Option<f64>,
+    /// Mean `pnl` per trade.
+    pub expectancy: f64,
+    pub average_win: f64,
+    pub average_loss: f64,
+    /// Mean R multiple over the trades that carry a risk.
+    pub average_r: Option<f64>,
+}
+
+impl TradeStats {
+    /// `None` for an empty trade list.
+    pub fn new(trades: &[TradeRecord]) -> Option<Self> {
+        if trades.is_empty() {
+            return None;
+        }
+
+        let n = trades.len() as f64;
+        let gross_profit: f64 = trades.iter().map(|t| t.pnl.max(0.0)).sum();
+        let gross_loss: f64 = trades.iter().map(|t| (-t.pnl).max(0.0)).sum();
+        let wins = trades.iter().filter(|t| t.pnl > 0.0).count();
+        let losses = trades.iter().filter(|t| t.pnl < 0.0).count();
+
+        let r_multiples: Vec<f64> = trades.iter().filter_map(TradeRecord::r_multiple).collect();
+
+        Some(Self {
+            trades: trades.len(),
+            wins,
+            losses,
+            win_rate_pct: wins as f64 / n * 100.0,
+            profit_factor: (gross_loss > 0.0).then(|| gross_profit / gross_loss),
+            expectancy: (gross_profit - gross_loss) / n,
+            average_win: if wins > 0 {
+                gross_profit / wins as f64
+            } else {
+                0.0
+            },
+            average_loss: if losses > 0 {
+                gross_loss / losses as f64
+            } else {
+                0.0
+            },
+            average_r: (!r_multiples.is_empty())
+                .then(|| r_multiples.iter().sum::<f64>() / r_multiples.len() as f64),
+        })
+    }
+}
+
+impl BacktestResult {
+    pub fn equity_stats(&self, calendar: Calendar) -> Option<EquityStats> {
+        EquityStats::new(&self.equity, calendar)
+    }
+
+    pub fn trade_stats(&self) -> Option<TradeStats> {
+        TradeStats::new(&self.trades)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::TradeSide;
+
+    const DAY_MS: u64 = 86_400_000;
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    fn curve(values: &[f64]) -> Vec<EquityPoint> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, equity)| EquityPoint {
+                time: i as u64 * DAY_MS,
+                equity: *equity,
+                position: if i % 2 == 0 { 1.0 } else { 0.0 },
+            })
+            .collect()
+    }
+
+    fn trade(pnl: f64, risk: Option<f64>) -> TradeRecord {
+        TradeRecord {
+            side: TradeSide::Buy,
+            quantity: 1.0,
+            entry_price: 100.0,
+            exit_price: 100.0 + pnl,
+            entry_bar: 0,
+            exit_bar: 1,
+            entry_time: 0,
+            exit_time: DAY_MS,
+            fees: 0.0,
+            pnl,
+            risk,
+        }
+    }
+
+    #[test]
+    fn drawdown_tracks_depth_and_recovery() {
+        let stats = EquityStats::new(
+            &curve(&[100.0, 120.0, 90.0, 105.0, 125.0, 110.0]),
+            Calendar::Crypto,
+        )
+        .unwrap();
+
+        let drawdown = stats.max_drawdown;
+        approx_eq(drawdown.depth_pct, 25.0);
+        assert_eq!(drawdown.peak_time, DAY_MS);
+        assert_eq!(drawdown.trough_time, 2 * DAY_MS);
+        assert_eq!(drawdown.recovery_time, Some(4 * DAY_MS));
+        assert_eq!(drawdown.duration_ms, 3 * DAY_MS);
+        approx_eq(stats.total_return_pct, 10.0);
+        approx_eq(stats.exposure_pct, 50.0);
+    }
+
+    #[test]
+    fn annualizes_from_point_spacing() {
+        let values = [100.0, 101.0, 100.5, 102.0, 101.0];
+        let stats = EquityStats::new(&curve(&values), Calendar::Crypto).unwrap();
+
+        let returns: Vec<f64> = values.windows(2).map(|w| w[1] / w[0] - 1.0).collect();
+        let mean = returns.iter().sum::<f64>() / 4.0;
+        let std = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 3.0).sqrt();
+        let downside = (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / 4.0).sqrt();
+
+        approx_eq(stats.periods_per_year, 365.0);
+        approx_eq(stats.sharpe.unwrap(), mean / std * 365f64.sqrt());
+        approx_eq(stats.sortino.unwrap(), mean / downside * 365f64.sqrt());
+        approx_eq(
+            stats.cagr_pct,
+            ((1.01f64).powf(MS_PER_YEAR / (4 * DAY_MS) as f64) - 1.0) * 100.0,
+        );
+        approx_eq(
+            stats.calmar.unwrap(),
+            stats.cagr_pct / stats.max_drawdown.depth_pct,
+        );
+    }
+
+    #[test]
+    fn flat_curve_has_no_ratios() {
+        let stats = EquityStats::new(&curve(&[100.0, 100.0, 100.0]), Calendar::Crypto).unwrap();
+
+        assert_eq!(stats.sharpe, None);
+        assert_eq!(stats.sortino, None);
+        assert_eq!(stats.calmar, None);
+        assert!(EquityStats::new(&curve(&[100.0]), Calendar::Crypto).is_none());
+    }
+
+    #[test]
+    fn trade_stats() {
+        let stats = TradeStats::new(&[
+            trade(30.0, Some(10.0)),
+            trade(-10.0, Some(10.0)),
+            trade(20.0, None),
+            trade(-20.0, None),
+        ])
+        .unwrap();
+
+        assert_eq!((stats.trades, stats.wins, stats.losses), (4, 2, 2));
+        approx_eq(stats.win_rate_pct, 50.0);
+        approx_eq(stats.profit_factor.unwrap(), 50.0 / 30.0);
+        approx_eq(stats.expectancy, 5.0);
+        approx_eq(stats.average_win, 25.0);
+        approx_eq(stats.average_loss, 15.0);
+        approx_eq(stats.average_r.unwrap(), 1.0);
+        assert!(TradeStats::new(&[]).is_none());
+    }
+}
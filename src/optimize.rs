@@ -0,0 +1,622 @@
+use crate::indicators::Price;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// A named parameter and the values it is searched over.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    name: String,
+    values: Vec<f64>,
+}
+
+impl Param {
+    pub fn values(name: &str, values: &[f64]) -> Self {
+        assert!(!values.is_empty(), "Param {} has no values", name);
+        assert!(
+            values.iter().all(|value| value.is_finite()),
+            "Param {} values must be finite",
+            name
+        );
+        Self {
+            name: name.to_string(),
+            values: values.to_vec(),
+        }
+    }
+
+    /// `start`, `start + step`, ... up to and including `end`.
+    pub fn range(name: &str, start: f64, end: f64, step: f64) -> Self {
+        assert!(step > 0.0, "Param {} step must be > 0, got {}", name, step);
+        assert!(end >= start, "Param {} range is empty", name);
+
+        let count = ((end - start) / step + 1e-9).floor() as usize + 1;
+        let values: Vec<f64> = (0..count).map(|i| start + i as f64 * step).collect();
+        Self::values(name, &values)
+    }
+
+    /// Integer periods from `start` to `end` inclusive.
+    pub fn periods(name: &str, start: u32, end: u32, step: u32) -> Self {
+        assert!(step > 0, "Param {} step must be > 0", name);
+        assert!(end >= start, "Param {} range is empty", name);
+
+        let values: Vec<f64> = (start..=end)
+            .step_by(step as usize)
+            .map(f64::from)
+            .collect();
+        Self::values(name, &values)
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn candidates(&self) -> &[f64] {
+        &self.values
+    }
+}
+
+/// One point of the search space, handed to the objective.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    values: Vec<(String, f64)>,
+}
+
+impl Params {
+    /// Panics if `name` is not a parameter of the search.
+    pub fn get(&self, name: &str) -> f64 {
+        self.values
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("Unknown parameter {}", name))
+    }
+
+    /// `get` as a period or count for an `IndicatorKind` field. Panics
+    /// unless the value is a whole number that fits in a `u32`.
+    pub fn get_u32(&self, name: &str) -> u32 {
+        let value = self.get(name);
+        assert!(
+            value.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(&value),
+            "Parameter {} must be a whole number >= 0, got {}",
+            name,
+            value
+        );
+        value as u32
+    }
+
+    /// `get` in hundredths, rounded, for the `*_x100` fields of
+    /// `IndicatorKind` such as `std_multiplier_x100`. Panics on negative
+    /// values.
+    pub fn get_x100(&self, name: &str) -> u32 {
+        let value = self.get(name);
+        let scaled = (value * 100.0).round();
+        assert!(
+            (0.0..=u32::MAX as f64).contains(&scaled),
+            "Parameter {} must be >= 0, got {}",
+            name,
+            value
+        );
+        scaled as u32
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+}
+
+/// Prices handed to the objective. Bars before `start` are history for
+/// warming indicators up; only `prices[start..]` should be scored, e.g. with
+/// [`Backtest::run_from`](crate::backtest::Backtest::run_from).
+#[derive(Debug, Clone, Copy)]
+pub struct Sample<'a> {
+    pub prices: &'a [Price],
+    pub start: usize,
+}
+
+impl<'a> Sample<'a> {
+    pub fn scored(&self) -> &'a [Price] {
+        &self.prices[self.start..]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Search {
+    /// Every combination of parameter values.
+    Grid,
+    /// `samples` distinct combinations drawn with a seeded generator; the
+    /// full grid if it is not larger than that.
+    Random { samples: usize, seed: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    pub params: Params,
+    pub score: f64,
+}
+
+/// In-sample and out-of-sample windows, by bar index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkForward {
+    in_sample: usize,
+    out_of_sample: usize,
+    anchored: bool,
+}
+
+impl WalkForward {
+    /// Rolling windows: each fold optimizes on `in_sample` bars and tests
+    /// on the next `out_of_sample`, then moves on by `out_of_sample`.
+    pub fn new(in_sample: usize, out_of_sample: usize) -> Self {
+        assert!(in_sample > 0, "Walk-forward in-sample length must be > 0");
+        assert!(
+            out_of_sample > 0,
+            "Walk-forward out-of-sample length must be > 0"
+        );
+        Self {
+            in_sample,
+            out_of_sample,
+            anchored: false,
+        }
+    }
+
+    /// Anchored windows keep the in-sample start at the first bar and grow.
+    pub fn with_anchored(mut self, anchored: bool) -> Self {
+        self.anchored = anchored;
+        self
+    }
+
+    /// `(in_sample, out_of_sample)` ranges over `len` bars. The last
+    /// out-of-sample window may be shorter.
+    pub fn folds(&self, len: usize) -> Vec<(Range<usize>, Range<usize>)> {
+        let mut folds = Vec::new();
+        let mut offset = 0;
+
+        while offset + self.in_sample < len {
+            let in_sample_end = offset + self.in_sample;
+            let in_sample_start = if self.anchored { 0 } else { offset };
+            let out_of_sample_end = (in_sample_end + self.out_of_sample).min(len);
+
+            folds.push((
+                in_sample_start..in_sample_end,
+                in_sample_end..out_of_sample_end,
+            ));
+            offset += self.out_of_sample;
+        }
+        folds
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fold {
+    pub index: usize,
+    pub in_sample: Range<usize>,
+    pub out_of_sample: Range<usize>,
+    /// Best parameters in sample.
+    pub params: Params,
+    pub in_sample_score: f64,
+    pub out_of_sample_score: f64,
+}
+
+/// Searches a parameter space for the highest score of a user objective.
+///
+/// The objective maps a [`Params`] and a [`Sample`] to a score, typically by
+/// building `IndicatorKind`s from the parameters and backtesting them.
+/// Candidates are spread over threads, but results keep candidate order and
+/// ties go to the earlier candidate, so output does not depend on the thread
+/// count.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Optimizer {
+    params: Vec<Param>,
+    threads: usize,
+}
+
+impl Optimizer {
+    pub fn new(params: Vec<Param>) -> Self {
+        assert!(!params.is_empty(), "Optimizer needs at least one Param");
+        let mut names = HashSet::new();
+        for param in &params {
+            assert!(
+                names.insert(param.name()),
+                "Duplicate parameter {}",
+                param.name()
+            );
+        }
+
+        Self {
+            params,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "Optimizer threads must be > 0");
+        self.threads = threads;
+        self
+    }
+
+    /// Candidates of `search`, in evaluation order.
+    pub fn candidates(&self, search: Search) -> Vec<Params> {
+        if let Search::Random { samples, .. } = search {
+            assert!(samples > 0, "Random search samples must be > 0");
+        }
+
+        let sizes: Vec<usize> = self.params.iter().map(|p| p.values.len()).collect();
+        let total = sizes
+            .iter()
+            .try_fold(1usize, |total, size| total.checked_mul(*size));
+
+        let indices: Vec<Vec<usize>> = match search {
+            Search::Random { samples, seed } if total.is_none_or(|total| samples < total) => {
+                let mut rng = SplitMix64(seed);
+                let mut seen = HashSet::new();
+                let mut drawn = Vec::with_capacity(samples);
+                while drawn.len() < samples {
+                    let combo: Vec<usize> = sizes.iter().map(|size| rng.below(*size)).collect();
+                    if seen.insert(combo.clone()) {
+                        drawn.push(combo);
+                    }
+                }
+                drawn
+            }
+            _ => {
+                let mut grid = vec![Vec::new()];
+                for size in &sizes {
+                    grid = grid
+                        .into_iter()
+                        .flat_map(|prefix| {
+                            (0..*size).map(move |i| {
+                                let mut combo = prefix.clone();
+                                combo.push(i);
+                                combo
+                            })
+                        })
+                        .collect();
+                }
+                grid
+            }
+        };
+
+        indices
+            .into_iter()
+            .map(|combo| Params {
+                values: self
+                    .params
+                    .iter()
+                    .zip(combo)
+                    .map(|(param, i)| (param.name.clone(), param.values[i]))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Scores every candidate on all of `prices`, best first. `NaN` scores
+    /// sort last.
+    pub fn search<F>(&self, search: Search, prices: &[Price], objective: F) -> Vec<Evaluation>
+    where
+        F: Fn(&Params, Sample<'_>) -> f64 + Sync,
+    {
+        self.rank(
+            self.candidates(search),
+            Sample { prices, start: 0 },
+            &objective,
+        )
+    }
+
+    /// Optimizes each in-sample window and scores the winner on the
+    /// following out-of-sample window. Both windows see all earlier bars as
+    /// warm-up history.
+    pub fn walk_forward<F>(
+        &self,
+        search: Search,
+        walk_forward: WalkForward,
+        prices: &[Price],
+        objective: F,
+    ) -> Vec<Fold>
+    where
+        F: Fn(&Params, Sample<'_>) -> f64 + Sync,
+    {
+        let candidates = self.candidates(search);
+
+        walk_forward
+            .folds(prices.len())
+            .into_iter()
+            .enumerate()
+            .map(|(index, (in_sample, out_of_sample))| {
+                let best = self
+                    .rank(
+                        candidates.clone(),
+                        Sample {
+                            prices: &prices[..in_sample.end],
+                            start: in_sample.start,
+                        },
+                        &objective,
+                    )
+                    .swap_remove(0);
+                let out_of_sample_score = objective(
+                    &best.params,
+                    Sample {
+                        prices: &prices[..out_of_sample.end],
+                        start: out_of_sample.start,
+                    },
+                );
+
+                Fold {
+                    index,
+                    in_sample,
+                    out_of_sample,
+                    params: best.params,
+                    in_sample_score: best.score,
+                    out_of_sample_score,
+                }
+            })
+            .collect()
+    }
+
+    fn rank<F>(&self, candidates: Vec<Params>, sample: Sample<'_>, objective: &F) -> Vec<Evaluation>
+    where
+        F: Fn(&Params, Sample<'_>) -> f64 + Sync,
+    {
+        let scores = self.evaluate(&candidates, sample, objective);
+        let mut evaluations: Vec<Evaluation> = candidates
+            .into_iter()
+            .zip(scores)
+            .map(|(params, score)| Evaluation { params, score })
+            .collect();
+
+        // Stable, so equal scores keep candidate order.
+        evaluations.sort_by(|a, b| match (a.score.is_nan(), b.score.is_nan()) {
+            (false, false) => b.score.total_cmp(&a.score),
+            (nan_a, nan_b) => nan_a.cmp(&nan_b),
+        });
+        evaluations
+    }
+
+    fn evaluate<F>(&self, candidates: &[Params], sample: Sample<'_>, objective: &F) -> Vec<f64>
+    where
+        F: Fn(&Params, Sample<'_>) -> f64 + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let workers = self.threads.min(candidates.len()).max(1);
+
+        let mut scored: Vec<(usize, f64)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut scored = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(params) = candidates.get(i) else {
+                                break;
+                            };
+                            scored.push((i, objective(params, sample)));
+                        }
+                        scored
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Optimizer objective panicked"))
+                .collect()
+        });
+
+        scored.sort_by_key(|(i, _)| *i);
+        scored.into_iter().map(|(_, score)| score).collect()
+    }
+}
+
+/// Small seeded generator for reproducible random search.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{IndicatorKind, Source};
+
+    fn prices(n: usize) -> Vec<Price> {
+        (0..n)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.4).sin() * 5.0;
+                Price {
+                    open: close,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    open_time: i as u64 * 60_000,
+                    close_time: i as u64 * 60_000 + 59_999,
+                    vlm: 1.0,
+                }
+            })
+            .collect()
+    }
+
+    fn optimizer() -> Optimizer {
+        Optimizer::new(vec![
+            Param::periods("fast", 2, 10, 2),
+            Param::range("mult", 0.5, 2.0, 0.5),
+        ])
+    }
+
+    /// Peaks at fast = 6, mult = 1.5.
+    fn objective(params: &Params, _: Sample<'_>) -> f64 {
+        -((params.get("fast") - 6.0).powi(2) + (params.get("mult") - 1.5).powi(2))
+    }
+
+    #[test]
+    fn grid_covers_every_combination_best_first() {
+        let results = optimizer().search(Search::Grid, &prices(10), objective);
+
+        assert_eq!(results.len(), 5 * 4);
+        assert_eq!(results[0].params.get_u32("fast"), 6);
+        assert_eq!(results[0].params.get("mult"), 1.5);
+        assert_eq!(results[0].score, 0.0);
+        assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn random_search_is_seeded_and_distinct() {
+        let optimizer = optimizer();
+        let search = Search::Random {
+            samples: 7,
+            seed: 42,
+        };
+
+        let a = optimizer.candidates(search);
+        let b = optimizer.candidates(search);
+        let other = optimizer.candidates(Search::Random {
+            samples: 7,
+            seed: 43,
+        });
+
+        assert_eq!(a.len(), 7);
+        assert_eq!(a, b);
+        assert_ne!(a, other);
+        for (i, params) in a.iter().enumerate() {
+            assert!(!a[..i].contains(params));
+        }
+        assert_eq!(
+            optimizer
+                .candidates(Search::Random {
+                    samples: 100,
+                    seed: 1
+                })
+                .len(),
+            20
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "samples must be > 0")]
+    fn random_search_rejects_zero_samples() {
+        optimizer().walk_forward(
+            Search::Random {
+                samples: 0,
+                seed: 1,
+            },
+            WalkForward::new(5, 5),
+            &prices(20),
+            objective,
+        );
+    }
+
+    #[test]
+    fn params_convert_to_indicator_kind_fields() {
+        let optimizer = Optimizer::new(vec![
+            Param::periods("periods", 10, 20, 10),
+            Param::range("mult", 1.5, 2.5, 0.1),
+        ]);
+
+        let kinds: Vec<IndicatorKind> = optimizer
+            .candidates(Search::Grid)
+            .iter()
+            .map(|params| IndicatorKind::BollingerBands {
+                periods: params.get_u32("periods"),
+                std_multiplier_x100: params.get_x100("mult"),
+                source: Source::Close,
+            })
+            .collect();
+
+        assert_eq!(kinds.len(), 22);
+        assert_eq!(kinds[21].key(), "bollinger_20_2.5");
+    }
+
+    #[test]
+    #[should_panic(expected = "whole number")]
+    fn get_u32_rejects_fractional_periods() {
+        let params =
+            &Optimizer::new(vec![Param::values("fast", &[2.5])]).candidates(Search::Grid)[0];
+        params.get_u32("fast");
+    }
+
+    #[test]
+    #[should_panic(expected = "whole number")]
+    fn get_u32_rejects_negative_periods() {
+        let params =
+            &Optimizer::new(vec![Param::values("fast", &[-3.0])]).candidates(Search::Grid)[0];
+        params.get_u32("fast");
+    }
+
+    #[test]
+    fn results_do_not_depend_on_thread_count() {
+        let prices = prices(200);
+        let score = |params: &Params, sample: Sample<'_>| {
+            let fast = params.get_u32("fast") as usize;
+            let closes: Vec<f64> = sample.scored().iter().map(|p| p.close).collect();
+            closes
+                .windows(fast)
+                .map(|w| w[fast - 1] - w[0])
+                .sum::<f64>()
+                * params.get("mult")
+        };
+
+        let single = optimizer()
+            .with_threads(1)
+            .search(Search::Grid, &prices, score);
+        let parallel = optimizer()
+            .with_threads(8)
+            .search(Search::Grid, &prices, score);
+
+        assert_eq!(single, parallel);
+    }
+
+    #[test]
+    fn walk_forward_windows() {
+        let rolling = WalkForward::new(100, 50).folds(260);
+        assert_eq!(
+            rolling,
+            vec![
+                (0..100, 100..150),
+                (50..150, 150..200),
+                (100..200, 200..250),
+                (150..250, 250..260)
+            ]
+        );
+
+        let anchored = WalkForward::new(100, 50).with_anchored(true).folds(200);
+        assert_eq!(anchored, vec![(0..100, 100..150), (0..150, 150..200)]);
+    }
+
+    #[test]
+    fn walk_forward_scores_out_of_sample_with_history() {
+        let prices = prices(120);
+        let optimizer = Optimizer::new(vec![Param::values("x", &[1.0, 2.0, 3.0])]);
+
+        let folds = optimizer.walk_forward(
+            Search::Grid,
+            WalkForward::new(60, 30),
+            &prices,
+            |params, sample| {
+                // In sample prefers x = 3; out of sample reports the window.
+                if sample.start >= 60 {
+                    (sample.prices.len() * 1000 + sample.start) as f64
+                } else {
+                    params.get("x")
+                }
+            },
+        );
+
+        assert_eq!(folds.len(), 2);
+        assert_eq!(folds[1].in_sample, 30..90);
+        assert_eq!(folds[1].params.get("x"), 3.0);
+        assert_eq!(folds[0].out_of_sample_score, (90 * 1000 + 60) as f64);
+        assert_eq!(folds[1].out_of_sample_score, (120 * 1000 + 90) as f64);
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    Max,
+    Min,
+}
+
+/// Rolling max/min over the last `periods` values that also tracks how many
+/// bars ago the extreme printed. Ties resolve to the most recent bar.
+#[derive(Clone, Debug)]
+pub struct RollingExtremum {
+    periods: u32,
+    side: Side,
+    // Monotonic deque of (bar index, value) over the confirmed bars.
+    buff: VecDeque<(u64, f64)>,
+    count: u64,
+    value: Option<(u64, f64)>,
+    head: u64,
+}
+
+impl RollingExtremum {
+    pub fn max(periods: u32) -> Self {
+        Self::new(periods, Side::Max)
+    }
+
+    pub fn min(periods: u32) -> Self {
+        Self::new(periods, Side::Min)
+    }
+
+    fn new(periods: u32, side: Side) -> Self {
+        assert!(periods > 0, "RollingExtremum periods must be > 0");
+        Self {
+            periods,
+            side,
+            buff: VecDeque::with_capacity(periods as usize),
+            count: 0,
+            value: None,
+            head: 0,
+        }
+    }
+
+    #[inline]
+    fn dominates(&self, x: f64, other: f64) -> bool {
+        match self.side {
+            Side::Max => x >= other,
+            Side::Min => x <= other,
+        }
+    }
+
+    pub fn update_after_close(&mut self, x: f64) {
+        let index = self.count;
+
+        while let Some(&(_, last)) = self.buff.back() {
+            if self.dominates(x, last) {
+                self.buff.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.buff.push_back((index, x));
+        self.count += 1;
+
+        while let Some(&(front, _)) = self.buff.front() {
+            if front + (self.periods as u64) <= index {
+                self.buff.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        self.head = index;
+        self.value = if self.is_ready() {
+            self.buff.front().copied()
+        } else {
+            None
+        };
+    }
+
+    /// Evaluates the window with `x` replacing the in-progress bar. The
+    /// confirmed window is left untouched, so repeated calls never lose
+    /// values that an earlier provisional tick would have evicted.
+    pub fn update_before_close(&mut self, x: f64) {
+        if !self.is_ready() {
+            return;
+        }
+
+        let index = self.count;
+        let oldest = index + 1 - self.periods as u64;
+        let best = self
+            .buff
+            .iter()
+            .take(2)
+            .find(|(position, _)| *position >= oldest)
+            .copied();
+
+        self.head = index;
+        self.value = match best {
+            Some((_, value)) if !self.dominates(x, value) => best,
+            _ => Some((index, x)),
+        };
+    }
+
+    pub fn load(&mut self, data: &[f64]) {
+        for x in data {
+            self.update_after_close(*x);
+        }
+    }
+
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.count >= self.periods as u64
+    }
+
+    #[inline]
+    pub fn get_last(&self) -> Option<f64> {
+        self.value.map(|(_, value)| value)
+    }
+
+    /// Bars between the newest bar in the window and the extreme, `0` when
+    /// the newest bar is the extreme.
+    #[inline]
+    pub fn bars_since(&self) -> Option<u32> {
+        self.value
+            .map(|(position, _)| (self.head - position) as u32)
+    }
+
+    pub fn reset(&mut self) {
+        self.buff.clear();
+        self.count = 0;
+        self.value = None;
+        self.head = 0;
+    }
+
+    #[inline]
+    pub fn period(&self) -> u32 {
+        self.periods
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RollingExtremum;
+
+    #[test]
+    fn tracks_value_and_position_of_extreme() {
+        let mut max = RollingExtremum::max(3);
+
+        max.load(&[5.0, 9.0, 7.0]);
+        assert_eq!(max.get_last(), Some(9.0));
+        assert_eq!(max.bars_since(), Some(1));
+
+        max.update_after_close(6.0);
+        assert_eq!(max.get_last(), Some(9.0));
+        assert_eq!(max.bars_since(), Some(2));
+
+        max.update_after_close(4.0);
+        assert_eq!(max.get_last(), Some(7.0));
+        assert_eq!(max.bars_since(), Some(2));
+    }
+
+    #[test]
+    fn ties_resolve_to_most_recent_bar() {
+        let mut min = RollingExtremum::min(3);
+
+        min.load(&[2.0, 5.0, 2.0]);
+        assert_eq!(min.get_last(), Some(2.0));
+        assert_eq!(min.bars_since(), Some(0));
+    }
+
+    #[test]
+    fn provisional_replacement_does_not_lose_confirmed_values() {
+        let mut live = RollingExtremum::max(3);
+        let mut close_only = RollingExtremum::max(3);
+
+        live.load(&[1.0, 8.0, 6.0, 5.0]);
+        close_only.load(&[1.0, 8.0, 6.0, 5.0]);
+
+        live.update_before_close(10.0);
+        assert_eq!(live.get_last(), Some(10.0));
+        assert_eq!(live.bars_since(), Some(0));
+
+        live.update_before_close(3.0);
+        assert_eq!(live.get_last(), Some(6.0));
+        assert_eq!(live.bars_since(), Some(2));
+
+        live.update_after_close(4.0);
+        close_only.update_after_close(4.0);
+
+        assert_eq!(live.get_last(), close_only.get_last());
+        assert_eq!(live.bars_since(), close_only.bars_since());
+    }
+}
@@ -1,7 +1,9 @@
 pub mod exp_mean;
+pub mod extremum;
 pub mod mean;
 pub mod stddev;
 
 pub use exp_mean::ExpMean;
+pub use extremum::RollingExtremum;
 pub use mean::Mean;
-pub use stddev::StdDev;
+pub use stddev::{StdDev, VarianceMode};
@@ -1,11 +1,33 @@
 use super::Mean;
-use crate::indicators::{Indicator, Price, Value};
+use crate::indicators::{Indicator, Price, Source, Value};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum VarianceMode {
+    /// Divides by `n - 1`.
+    #[default]
+    Sample,
+    /// Divides by `n`.
+    Population,
+}
+
+impl VarianceMode {
+    #[inline]
+    pub fn divisor(self, n: u32) -> f64 {
+        match self {
+            VarianceMode::Sample => (n - 1) as f64,
+            VarianceMode::Population => n as f64,
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct StdDev {
     periods: u32,
+    mode: VarianceMode,
     mean: Mean,
+    variance: Option<f64>,
     value: Option<f64>,
+    source: Source,
 }
 
 impl StdDev {
@@ -13,11 +35,30 @@ impl StdDev {
         assert!(periods > 1);
         Self {
             periods,
+            mode: VarianceMode::Sample,
             mean: Mean::new(periods),
+            variance: None,
             value: None,
+            source: Source::default(),
         }
     }
 
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: VarianceMode) -> Self {
+        self.mode = mode;
+        self.compute();
+        self
+    }
+
+    #[inline]
+    pub fn mode(&self) -> VarianceMode {
+        self.mode
+    }
+
     pub fn update_after_close_value(&mut self, x: f64) {
         self.mean.update_after_close(x);
         self.compute();
@@ -33,8 +74,14 @@ impl StdDev {
         self.value
     }
 
+    #[inline]
+    pub fn get_variance(&self) -> Option<f64> {
+        self.variance
+    }
+
     fn compute(&mut self) {
         if !self.mean.is_ready() {
+            self.variance = None;
             self.value = None;
             return;
         }
@@ -43,29 +90,31 @@ impl StdDev {
         let sum = self.mean.sum();
         let sum_sq = self.mean.sum_sq();
 
-        let var = (sum_sq - (sum * sum) / n) / (n - 1.0);
-        self.value = Some(var.max(0.0).sqrt());
+        let var = ((sum_sq - (sum * sum) / n) / self.mode.divisor(self.periods)).max(0.0);
+        self.variance = Some(var);
+        self.value = Some(var.sqrt());
     }
 }
 
 impl Indicator for StdDev {
     fn update_before_close(&mut self, price: Price) {
-        self.update_before_close_value(price.close);
+        self.update_before_close_value(self.source.get(&price));
     }
 
     fn update_after_close(&mut self, price: Price) {
-        self.update_after_close_value(price.close);
+        self.update_after_close_value(self.source.get(&price));
     }
 
     fn load(&mut self, price_data: &[Price]) {
         for p in price_data.iter() {
-            self.mean.update_after_close(p.close);
+            self.mean.update_after_close(self.source.get(p));
         }
         self.compute();
     }
 
     fn reset(&mut self) {
         self.mean.reset();
+        self.variance = None;
         self.value = None;
     }
 
@@ -81,3 +130,49 @@ impl Indicator for StdDev {
         self.periods
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "left={a}, right={b}");
+    }
+
+    #[test]
+    fn stddev_defaults_to_sample_variance() {
+        let mut sd = StdDev::new(4);
+
+        for x in [2.0, 4.0, 4.0, 6.0] {
+            sd.update_after_close_value(x);
+        }
+
+        approx_eq(sd.get_variance().unwrap(), 8.0 / 3.0);
+        approx_eq(sd.get_last_value().unwrap(), (8.0f64 / 3.0).sqrt());
+    }
+
+    #[test]
+    fn stddev_population_mode_divides_by_n() {
+        let mut sd = StdDev::new(4).with_mode(VarianceMode::Population);
+
+        for x in [2.0, 4.0, 4.0, 6.0] {
+            sd.update_after_close_value(x);
+        }
+
+        approx_eq(sd.get_variance().unwrap(), 2.0);
+    }
+
+    #[test]
+    fn stddev_reset_clears_variance() {
+        let mut sd = StdDev::new(2);
+
+        sd.update_after_close_value(1.0);
+        sd.update_after_close_value(3.0);
+        assert!(sd.get_variance().is_some());
+
+        sd.reset();
+
+        assert_eq!(sd.get_variance(), None);
+        assert_eq!(sd.get_last_value(), None);
+    }
+}
@@ -0,0 +1,507 @@
+use crate::indicators::{IndicatorKind, IndicatorSet, KindError, Price, Source};
+use serde::{Deserialize, Serialize};
+
+/// One side of a comparison.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Operand {
+    /// A named field of an indicator's value, see [`Value::field`](crate::indicators::Value::field).
+    Indicator {
+        kind: IndicatorKind,
+        #[serde(default = "default_field")]
+        field: String,
+    },
+    Price(Source),
+    Constant(f64),
+}
+
+fn default_field() -> String {
+    "value".to_string()
+}
+
+impl Operand {
+    pub fn indicator(kind: IndicatorKind, field: &str) -> Self {
+        Operand::Indicator {
+            kind,
+            field: field.to_string(),
+        }
+    }
+
+    /// Registers the operand's indicator and points it at a slot, so the
+    /// key is only built once per rule.
+    fn compile(&self, set: &mut IndicatorSet, slots: &mut Vec<Slot>) -> Result<Term, KindError> {
+        Ok(match self {
+            Operand::Indicator { kind, field } => {
+                let slot = Slot {
+                    key: set.insert(*kind)?,
+                    field: field.clone(),
+                };
+                let index = slots.iter().position(|s| *s == slot).unwrap_or_else(|| {
+                    slots.push(slot);
+                    slots.len() - 1
+                });
+                Term::Slot(index)
+            }
+            Operand::Price(source) => Term::Price(*source),
+            Operand::Constant(x) => Term::Constant(*x),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Condition {
+    Above(Operand, Operand),
+    Below(Operand, Operand),
+    /// Left was at or below right on the previous closed bar and is above now.
+    CrossesAbove(Operand, Operand),
+    /// Left was at or above right on the previous closed bar and is below now.
+    CrossesBelow(Operand, Operand),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    fn compile(&self, set: &mut IndicatorSet, slots: &mut Vec<Slot>) -> Result<Check, KindError> {
+        let mut pair = |a: &Operand, b: &Operand| -> Result<_, KindError> {
+            Ok((a.compile(set, slots)?, b.compile(set, slots)?))
+        };
+
+        Ok(match self {
+            Condition::Above(a, b) => {
+                let (a, b) = pair(a, b)?;
+                Check::Above(a, b)
+            }
+            Condition::Below(a, b) => {
+                let (a, b) = pair(a, b)?;
+                Check::Below(a, b)
+            }
+            Condition::CrossesAbove(a, b) => {
+                let (a, b) = pair(a, b)?;
+                Check::CrossesAbove(a, b)
+            }
+            Condition::CrossesBelow(a, b) => {
+                let (a, b) = pair(a, b)?;
+                Check::CrossesBelow(a, b)
+            }
+            Condition::All(conditions) => Check::All(
+                conditions
+                    .iter()
+                    .map(|condition| condition.compile(set, slots))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Condition::Any(conditions) => Check::Any(
+                conditions
+                    .iter()
+                    .map(|condition| condition.compile(set, slots))
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+/// An indicator field read by one or more rules.
+#[derive(Clone, Debug, PartialEq)]
+struct Slot {
+    key: String,
+    field: String,
+}
+
+/// An [`Operand`] with its indicator resolved to a [`Slot`] index.
+#[derive(Clone, Copy, Debug)]
+enum Term {
+    Slot(usize),
+    Price(Source),
+    Constant(f64),
+}
+
+impl Term {
+    fn resolve(&self, snapshot: &Snapshot) -> Option<f64> {
+        match self {
+            Term::Slot(index) => snapshot.slots[*index],
+            Term::Price(source) => Some(source.get(&snapshot.price)),
+            Term::Constant(x) => Some(*x),
+        }
+        .filter(|x| !x.is_nan())
+    }
+}
+
+/// A [`Condition`] compiled against the engine's slots.
+#[derive(Clone, Debug)]
+enum Check {
+    Above(Term, Term),
+    Below(Term, Term),
+    CrossesAbove(Term, Term),
+    CrossesBelow(Term, Term),
+    All(Vec<Check>),
+    Any(Vec<Check>),
+}
+
+impl Check {
+    fn evaluate(&self, prev: Option<&Snapshot>, now: &Snapshot) -> bool {
+        let pair = |a: &Term, b: &Term, snapshot: &Snapshot| {
+            Some((a.resolve(snapshot)?, b.resolve(snapshot)?))
+        };
+
+        match self {
+            Check::Above(a, b) => pair(a, b, now).is_some_and(|(a, b)| a > b),
+            Check::Below(a, b) => pair(a, b, now).is_some_and(|(a, b)| a < b),
+            Check::CrossesAbove(a, b) => prev
+                .and_then(|prev| pair(a, b, prev))
+                .zip(pair(a, b, now))
+                .is_some_and(|((pa, pb), (a, b))| pa <= pb && a > b),
+            Check::CrossesBelow(a, b) => prev
+                .and_then(|prev| pair(a, b, prev))
+                .zip(pair(a, b, now))
+                .is_some_and(|((pa, pb), (a, b))| pa >= pb && a < b),
+            Check::All(checks) => checks.iter().all(|c| c.evaluate(prev, now)),
+            Check::Any(checks) => checks.iter().any(|c| c.evaluate(prev, now)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub name: String,
+    pub condition: Condition,
+}
+
+impl Rule {
+    pub fn new(name: impl Into<String>, condition: Condition) -> Self {
+        Self {
+            name: name.into(),
+            condition,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Signal {
+    pub rule: String,
+    /// `open_time` of the candle the rule fired on.
+    pub time: u64,
+    pub close: f64,
+    /// Fired from an in-progress candle; may not survive the close.
+    pub provisional: bool,
+}
+
+/// Price and slot values at one update, in slot order.
+#[derive(Clone, Debug)]
+struct Snapshot {
+    price: Price,
+    slots: Vec<Option<f64>>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct RuleState {
+    active: bool,
+    fired_provisional: bool,
+}
+
+/// Evaluates rules against the indicators they reference.
+///
+/// Rules are edge-triggered: a rule fires when its condition turns true and
+/// fires again only after it has been false on a closed bar. A rule fires at
+/// most once provisionally per candle.
+#[derive(Debug)]
+pub struct SignalEngine {
+    rules: Vec<Rule>,
+    checks: Vec<Check>,
+    slots: Vec<Slot>,
+    states: Vec<RuleState>,
+    indicators: IndicatorSet,
+    confirmed: Option<Snapshot>,
+}
+
+impl SignalEngine {
+    /// Builds the indicators the rules reference. Fails on the first
+    /// indicator kind with invalid parameters.
+    pub fn new(rules: Vec<Rule>) -> Result<Self, KindError> {
+        let mut indicators = IndicatorSet::new();
+        let mut slots = Vec::new();
+        let checks = rules
+            .iter()
+            .map(|rule| rule.condition.compile(&mut indicators, &mut slots))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            states: vec![RuleState::default(); rules.len()],
+            rules,
+            checks,
+            slots,
+            indicators,
+            confirmed: None,
+        })
+    }
+
+    #[inline]
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    #[inline]
+    pub fn indicators(&self) -> &IndicatorSet {
+        &self.indicators
+    }
+
+    fn snapshot(&self, price: Price) -> Snapshot {
+        let slots = self
+            .slots
+            .iter()
+            .map(|slot| {
+                self.indicators
+                    .get_by_key(&slot.key)?
+                    .get_last()?
+                    .field(&slot.field)
+            })
+            .collect();
+
+        Snapshot { price, slots }
+    }
+
+    pub fn update_after_close(&mut self, price: Price) -> Vec<Signal> {
+        self.indicators.update_after_close(price);
+        let now = self.snapshot(price);
+
+        let mut signals = Vec::new();
+        for ((rule, check), state) in self.rules.iter().zip(&self.checks).zip(&mut self.states) {
+            let active = check.evaluate(self.confirmed.as_ref(), &now);
+            if active && !state.active {
+                signals.push(Signal {
+                    rule: rule.name.clone(),
+                    time: price.open_time,
+                    close: price.close,
+                    provisional: false,
+                });
+            }
+            *state = RuleState {
+                active,
+                fired_provisional: false,
+            };
+        }
+
+        self.confirmed = Some(now);
+        signals
+    }
+
+    pub fn update_before_close(&mut self, price: Price) -> Vec<Signal> {
+        self.indicators.update_before_close(price);
+        let now = self.snapshot(price);
+
+        let mut signals = Vec::new();
+        for ((rule, check), state) in self.rules.iter().zip(&self.checks).zip(&mut self.states) {
+            if state.active || state.fired_provisional {
+                continue;
+            }
+            if check.evaluate(self.confirmed.as_ref(), &now) {
+                state.fired_provisional = true;
+                signals.push(Signal {
+                    rule: rule.name.clone(),
+                    time: price.open_time,
+                    close: price.close,
+                    provisional: true,
+                });
+            }
+        }
+
+        signals
+    }
+
+    /// Replays closed candles and returns every confirmed signal.
+    pub fn load(&mut self, price_data: &[Price]) -> Vec<Signal> {
+        price_data
+            .iter()
+            .flat_map(|price| self.update_after_close(*price))
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        self.indicators.reset();
+        self.states.fill(RuleState::default());
+        self.confirmed = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(time: u64, close: f64) -> Price {
+        Price {
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            open_time: time,
+            close_time: time,
+            vlm: 1.0,
+        }
+    }
+
+    fn close() -> Operand {
+        Operand::Price(Source::Close)
+    }
+
+    fn fired(signals: &[Signal]) -> Vec<(&str, u64)> {
+        signals
+            .iter()
+            .map(|signal| (signal.rule.as_str(), signal.time))
+            .collect()
+    }
+
+    #[test]
+    fn threshold_cross_fires_once_per_crossing() {
+        let sma = Operand::indicator(
+            IndicatorKind::Sma {
+                periods: 2,
+                source: Source::Close,
+            },
+            "value",
+        );
+        let mut engine = SignalEngine::new(vec![Rule::new(
+            "sma > 10",
+            Condition::CrossesAbove(sma, Operand::Constant(10.0)),
+        )])
+        .unwrap();
+
+        let signals = engine.load(&[
+            p(0, 8.0),
+            p(1, 9.0),
+            p(2, 13.0),
+            p(3, 14.0),
+            p(4, 5.0),
+            p(5, 16.0),
+        ]);
+
+        assert_eq!(fired(&signals), vec![("sma > 10", 2), ("sma > 10", 5)]);
+        assert!(signals.iter().all(|signal| !signal.provisional));
+    }
+
+    #[test]
+    fn line_versus_line_cross() {
+        let rule = Rule::new(
+            "fast over slow",
+            Condition::CrossesAbove(
+                Operand::indicator(
+                    IndicatorKind::Sma {
+                        periods: 2,
+                        source: Source::Close,
+                    },
+                    "value",
+                ),
+                Operand::indicator(
+                    IndicatorKind::Sma {
+                        periods: 4,
+                        source: Source::Close,
+                    },
+                    "value",
+                ),
+            ),
+        );
+        let mut engine = SignalEngine::new(vec![rule]).unwrap();
+
+        let signals = engine.load(&[p(0, 10.0), p(1, 9.0), p(2, 8.0), p(3, 7.0), p(4, 12.0)]);
+
+        assert_eq!(fired(&signals), vec![("fast over slow", 4)]);
+        assert_eq!(engine.indicators().len(), 2);
+    }
+
+    #[test]
+    fn rules_share_indicator_slots() {
+        let sma = Operand::indicator(
+            IndicatorKind::Sma {
+                periods: 2,
+                source: Source::Close,
+            },
+            "value",
+        );
+        let mut engine = SignalEngine::new(vec![
+            Rule::new("up", Condition::CrossesAbove(close(), sma.clone())),
+            Rule::new("down", Condition::CrossesBelow(close(), sma)),
+        ])
+        .unwrap();
+
+        assert_eq!(engine.slots.len(), 1);
+        assert_eq!(engine.indicators().len(), 1);
+
+        let signals = engine.load(&[p(0, 10.0), p(1, 8.0), p(2, 12.0), p(3, 9.0)]);
+        assert_eq!(fired(&signals), vec![("up", 2), ("down", 3)]);
+    }
+
+    #[test]
+    fn band_touch_with_composition() {
+        let upper = Operand::indicator(
+            IndicatorKind::BollingerBands {
+                periods: 3,
+                std_multiplier_x100: 100,
+                source: Source::Close,
+            },
+            "upper",
+        );
+        let rule = Rule::new(
+            "breakout",
+            Condition::All(vec![
+                Condition::Above(Operand::Price(Source::High), upper),
+                Condition::Any(vec![
+                    Condition::Above(close(), Operand::Constant(100.0)),
+                    Condition::Below(close(), Operand::Constant(11.5)),
+                ]),
+            ]),
+        );
+        let mut engine = SignalEngine::new(vec![rule]).unwrap();
+
+        let signals = engine.load(&[p(0, 10.0), p(1, 10.0), p(2, 10.0), p(3, 11.0)]);
+
+        // Still above the band on bar 3, so no second event.
+
+        assert_eq!(fired(&signals), vec![("breakout", 2)]);
+    }
+
+    #[test]
+    fn provisional_signals_are_flagged_and_fire_once_per_candle() {
+        let mut engine = SignalEngine::new(vec![Rule::new(
+            "up",
+            Condition::CrossesAbove(close(), Operand::Constant(10.0)),
+        )])
+        .unwrap();
+        engine.load(&[p(0, 9.0)]);
+
+        let first = engine.update_before_close(p(1, 11.0));
+        assert_eq!(fired(&first), vec![("up", 1)]);
+        assert!(first[0].provisional);
+        assert!(engine.update_before_close(p(1, 12.0)).is_empty());
+
+        let confirmed = engine.update_after_close(p(1, 12.0));
+        assert_eq!(fired(&confirmed), vec![("up", 1)]);
+        assert!(!confirmed[0].provisional);
+    }
+
+    #[test]
+    fn reset_clears_rule_state() {
+        let mut engine = SignalEngine::new(vec![Rule::new(
+            "high",
+            Condition::Above(close(), Operand::Constant(10.0)),
+        )])
+        .unwrap();
+
+        assert_eq!(engine.load(&[p(0, 11.0), p(1, 12.0)]).len(), 1);
+
+        engine.reset();
+
+        assert_eq!(engine.load(&[p(0, 11.0)]).len(), 1);
+    }
+
+    #[test]
+    fn new_rejects_invalid_indicator_kinds() {
+        let rule: Rule = serde_json::from_str(
+            r#"{"name":"bad","condition":{"above":[{"indicator":{"kind":{"ema":{"periods":0}},"field":"value"}},{"constant":1.0}]}}"#,
+        )
+        .unwrap();
+
+        let err = SignalEngine::new(vec![rule]).unwrap_err();
+
+        assert_eq!(err.key(), "ema_0");
+    }
+}